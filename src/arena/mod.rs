@@ -0,0 +1,21 @@
+pub mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::*;
+use systems::*;
+
+/// Size of the playfield in world units, independent of the window size and scale factor.
+pub const ARENA_WIDTH: f32 = 1280.0;
+pub const ARENA_HEIGHT: f32 = 720.0;
+
+/// Keeps the playfield and the camera in sync with the window size.
+pub struct ArenaPlugin;
+
+impl Plugin for ArenaPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Arena>()
+            .add_systems(PreUpdate, (update_arena, update_camera).chain());
+    }
+}
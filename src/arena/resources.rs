@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ARENA_HEIGHT, ARENA_WIDTH};
+
+/// How the arena is fitted into windows of a different aspect ratio.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Serialize, Deserialize)]
+pub enum ArenaScaling {
+    /// Keep the arena size and fill the remaining window space with bars.
+    #[default]
+    Letterbox,
+    /// Keep the arena height and widen or narrow it to the window.
+    Expand,
+}
+
+impl ArenaScaling {
+    pub fn next(self) -> ArenaScaling {
+        match self {
+            ArenaScaling::Letterbox => ArenaScaling::Expand,
+            ArenaScaling::Expand => ArenaScaling::Letterbox,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            ArenaScaling::Letterbox => "settings.arena_scaling.letterbox",
+            ArenaScaling::Expand => "settings.arena_scaling.expand",
+        }
+    }
+}
+
+/// Playfield spanning from the origin to `width` and `height` in world units.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Arena {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for Arena {
+    fn default() -> Arena {
+        Arena {
+            width: ARENA_WIDTH,
+            height: ARENA_HEIGHT,
+        }
+    }
+}
+
+impl Arena {
+    pub fn center(&self) -> Vec2 {
+        Vec2::new(self.width / 2.0, self.height / 2.0)
+    }
+
+    /// Bounds `[x_min, x_max, y_min, y_max]` for the center of a character of the given size.
+    pub fn confinement(&self, character_size: f32) -> [f32; 4] {
+        let half_character_size = character_size / 2.0;
+        [
+            half_character_size,
+            self.width - half_character_size,
+            half_character_size,
+            self.height - half_character_size,
+        ]
+    }
+
+    /// Moves a position of a character of the given size back into the arena.
+    pub fn confine(&self, position: Vec3, character_size: f32) -> Vec3 {
+        let [x_min, x_max, y_min, y_max] = self.confinement(character_size);
+        Vec3::new(
+            position.x.clamp(x_min, x_max),
+            position.y.clamp(y_min, y_max),
+            position.z,
+        )
+    }
+}
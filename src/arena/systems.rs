@@ -0,0 +1,84 @@
+use bevy::{
+    prelude::*,
+    render::camera::{ScalingMode, Viewport},
+    window::PrimaryWindow,
+};
+
+use super::{resources::*, ARENA_HEIGHT, ARENA_WIDTH};
+use crate::settings::resources::Settings;
+
+pub fn update_arena(
+    mut arena: ResMut<Arena>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    if window.width() <= 0.0 || window.height() <= 0.0 {
+        // Minimized windows have no size, keep the last arena
+        return;
+    }
+
+    let new_arena = match settings.arena_scaling {
+        ArenaScaling::Letterbox => Arena::default(),
+        ArenaScaling::Expand => Arena {
+            width: ARENA_HEIGHT * window.width() / window.height(),
+            height: ARENA_HEIGHT,
+        },
+    };
+    arena.set_if_neq(new_arena);
+}
+
+/// Centers the camera on the arena and scales its projection whenever the window or arena changes.
+pub fn update_camera(
+    mut camera_query: Query<(&mut Camera, &mut OrthographicProjection, &mut Transform)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
+    settings: Res<Settings>,
+    mut last_layout: Local<Option<(UVec2, ArenaScaling)>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let window_size = window.physical_size();
+    if window_size.x == 0 || window_size.y == 0 {
+        return;
+    }
+
+    let layout = (window_size, settings.arena_scaling);
+    if *last_layout == Some(layout) && !arena.is_changed() {
+        return;
+    }
+    *last_layout = Some(layout);
+
+    for (mut camera, mut projection, mut camera_transform) in &mut camera_query {
+        camera_transform.translation = arena.center().extend(camera_transform.translation.z);
+
+        match settings.arena_scaling {
+            ArenaScaling::Letterbox => {
+                // Physical pixels are used so that the bars stay correct on HiDPI screens
+                let scale =
+                    (window_size.x as f32 / ARENA_WIDTH).min(window_size.y as f32 / ARENA_HEIGHT);
+                let viewport_size = (Vec2::new(ARENA_WIDTH, ARENA_HEIGHT) * scale)
+                    .as_uvec2()
+                    .max(UVec2::ONE);
+                camera.viewport = Some(Viewport {
+                    physical_position: (window_size - viewport_size) / 2,
+                    physical_size: viewport_size,
+                    ..default()
+                });
+                projection.scaling_mode = ScalingMode::Fixed {
+                    width: arena.width,
+                    height: arena.height,
+                };
+            }
+            ArenaScaling::Expand => {
+                camera.viewport = None;
+                projection.scaling_mode = ScalingMode::FixedVertical {
+                    viewport_height: arena.height,
+                };
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
+
+use bevy::{
+    app::ScheduleRunnerPlugin,
+    core::FrameCount,
+    log::LogPlugin,
+    prelude::*,
+    render::{settings::WgpuSettings, RenderPlugin},
+    time::TimeUpdateStrategy,
+    winit::WinitPlugin,
+};
+use clap::{Parser, ValueEnum};
+use thiserror::Error;
+
+use crate::{
+    console::console_log_layer,
+    game::{
+        bot::resources::BotRuns,
+        mode::GameMode,
+        player::{Lives, MAX_LOCAL_PLAYERS},
+        resources::RunSettings,
+        simulation::resources::ExternalStepping,
+        star::MAX_STARS,
+        versus::VERSUS_BEST_OF,
+    },
+    network::{
+        resources::{ClientSession, HostSession, RollbackSession},
+        DEFAULT_PORT,
+    },
+    replay::{Replay, ReplayMode},
+    settings::resources::{Settings, MAX_INPUT_DELAY},
+};
+
+/// Frame time used when running without a window.
+const HEADLESS_TIMESTEP: Duration = Duration::from_nanos(16_666_667);
+
+/// Command-line options for launching the game straight into a reproducible scenario.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct LaunchOptions {
+    /// Seed for every run instead of a random one.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Maximum number of stars in the arena at the same time.
+    #[arg(long, value_name = "N", default_value_t = MAX_STARS)]
+    pub max_stars: usize,
+    /// RON file with the settings to start with.
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+    /// Game mode preselected in the main menu.
+    #[arg(long, value_enum, default_value_t = GameMode::Endless)]
+    pub mode: GameMode,
+    /// Number of local players, two for co-op.
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=MAX_LOCAL_PLAYERS as i64))]
+    pub players: u8,
+    /// Whether co-op players have separate health or share it.
+    #[arg(long, value_enum, default_value_t = Lives::Separate)]
+    pub lives: Lives,
+    /// Number of rounds a versus match is played over.
+    #[arg(long, value_name = "N", default_value_t = VERSUS_BEST_OF, value_parser = clap::value_parser!(u32).range(1..))]
+    pub best_of: u32,
+    /// Screen to start on.
+    #[arg(long, value_enum, default_value_t = StartIn::MainMenu)]
+    pub start_in: StartIn,
+    /// Window size, e.g. `1280x720`.
+    #[arg(long, value_name = "WxH", value_parser = parse_window_size)]
+    pub windowed: Option<(f32, f32)>,
+    /// Run without a window or rendering.
+    #[arg(long)]
+    pub headless: bool,
+    /// Quit after the given number of frames.
+    #[arg(long, value_name = "N")]
+    pub frames: Option<u32>,
+    /// Play back the keyboard input of a recorded replay.
+    #[arg(long, value_name = "FILE", conflicts_with = "record")]
+    pub replay: Option<PathBuf>,
+    /// Record the keyboard input of this session into a replay.
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+    /// Start with all audio muted.
+    #[arg(long)]
+    pub mute: bool,
+    /// Let bots drive the players, restarting after every game over.
+    #[arg(long, conflicts_with_all = ["replay", "record"])]
+    pub bot: bool,
+    /// Quit once the bots played the given number of runs, logging their aggregated results.
+    #[arg(long, value_name = "N", requires = "bot", value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: Option<u32>,
+    /// Host an online session on the given UDP port, 7777 if omitted.
+    #[arg(long, value_name = "PORT", conflicts_with = "join")]
+    pub host: Option<Option<u16>>,
+    /// Join the online session of a host, e.g. `127.0.0.1:7777`.
+    #[arg(long, value_name = "ADDR")]
+    pub join: Option<SocketAddr>,
+    /// Play a peer-to-peer rollback session with the peer at the address, e.g. `127.0.0.1:7778`.
+    #[arg(long, value_name = "ADDR", conflicts_with_all = ["host", "join"])]
+    pub rollback: Option<SocketAddr>,
+    /// UDP port of the rollback session.
+    #[arg(long, value_name = "PORT", default_value_t = DEFAULT_PORT, requires = "rollback")]
+    pub rollback_port: u16,
+    /// Frames local inputs are held back in the rollback session instead of the setting.
+    #[arg(long, value_name = "FRAMES", requires = "rollback", value_parser = clap::value_parser!(u32).range(0..=MAX_INPUT_DELAY as i64))]
+    pub input_delay: Option<u32>,
+    /// Delay every datagram to the rollback peer by the given milliseconds.
+    #[arg(long, value_name = "MS", default_value_t = 0, requires = "rollback")]
+    pub latency: u64,
+    /// Compare checksums with the rollback peer and report diverging simulations.
+    #[arg(long, requires = "rollback")]
+    pub desync_check: bool,
+}
+
+/// State entered once all assets are loaded.
+#[derive(Resource, ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StartIn {
+    #[default]
+    MainMenu,
+    Game,
+}
+
+#[derive(Debug, Error)]
+pub enum LaunchError {
+    #[error("could not read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("could not parse {path}: {source}")]
+    Ron {
+        path: PathBuf,
+        source: ron::error::SpannedError,
+    },
+    #[error("could not open an online session: {0}")]
+    Network(#[from] std::io::Error),
+}
+
+impl LaunchOptions {
+    pub fn default_plugins(&self) -> impl PluginGroup {
+        let mut window = Window::default();
+        if let Some((width, height)) = self.windowed {
+            window.resolution = (width, height).into();
+        }
+
+        let plugins = DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(window),
+                ..default()
+            })
+            .set(LogPlugin {
+                custom_layer: console_log_layer,
+                ..default()
+            });
+        if self.headless {
+            plugins
+                .disable::<WinitPlugin>()
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }
+                    .into(),
+                    ..default()
+                })
+                .add(ScheduleRunnerPlugin::run_loop(Duration::ZERO))
+        } else {
+            plugins
+        }
+    }
+
+    /// Applies the options to the app, must be called after all game plugins were added.
+    pub fn apply(&self, app: &mut App) -> Result<(), LaunchError> {
+        let mut seed = self.seed;
+
+        if let Some(path) = &self.config {
+            let settings: Settings = read_ron(path)?;
+            app.insert_resource(settings);
+        }
+
+        if let Some(path) = &self.replay {
+            let replay: Replay = read_ron(path)?;
+            seed = Some(replay.seed);
+            app.insert_resource(ReplayMode::Playback { replay, frame: 0 });
+        } else if let Some(path) = &self.record {
+            // Recordings need a fixed seed to be replayable
+            let seed = *seed.get_or_insert_with(rand::random);
+            app.insert_resource(ReplayMode::Recording {
+                path: path.clone(),
+                replay: Replay::new(seed),
+            });
+        }
+        app.insert_resource(RunSettings {
+            mode: self.mode,
+            players: self.players.into(),
+            lives: self.lives,
+            best_of: self.best_of,
+            seed,
+            max_stars: self.max_stars,
+            bots: self.bot,
+        });
+
+        app.insert_resource(self.start_in);
+
+        if let Some(runs) = self.runs {
+            app.insert_resource(BotRuns::limited(runs));
+        }
+
+        if let Some(port) = self.host {
+            let host_session = HostSession::bind(port.unwrap_or(DEFAULT_PORT))?;
+            info!(target: "network", "Hosting on port {}", host_session.port());
+            app.insert_resource(host_session);
+        } else if let Some(host) = self.join {
+            app.insert_resource(ClientSession::connect(host)?);
+        } else if let Some(peer) = self.rollback {
+            let seed = seed.unwrap_or_else(rand::random);
+            let mut rollback_session =
+                RollbackSession::bind(self.rollback_port, peer, seed, self.mode)?;
+            rollback_session.input_delay = self.input_delay;
+            rollback_session.latency = Duration::from_millis(self.latency);
+            rollback_session.desync_check = self.desync_check;
+            info!(target: "network", "Waiting for {peer} on port {}", rollback_session.port());
+            app.insert_resource(rollback_session)
+                .insert_resource(ExternalStepping);
+        }
+
+        if self.headless {
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(HEADLESS_TIMESTEP));
+        }
+
+        if self.mute {
+            app.insert_resource(GlobalVolume::new(0.0));
+        }
+
+        if let Some(frames) = self.frames {
+            app.add_systems(
+                PostUpdate,
+                move |frame_count: Res<FrameCount>, mut app_exit_events: EventWriter<AppExit>| {
+                    if frame_count.0 + 1 >= frames {
+                        app_exit_events.send(AppExit::Success);
+                    }
+                },
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn read_ron<T: serde::de::DeserializeOwned>(path: &PathBuf) -> Result<T, LaunchError> {
+    let contents = std::fs::read_to_string(path).map_err(|source| LaunchError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    ron::from_str(&contents).map_err(|source| LaunchError::Ron {
+        path: path.clone(),
+        source,
+    })
+}
+
+fn parse_window_size(value: &str) -> Result<(f32, f32), String> {
+    let (width, height) = value
+        .split_once('x')
+        .ok_or_else(|| format!("expected WxH, got `{value}`"))?;
+    let width: u32 = width
+        .parse()
+        .map_err(|_| format!("invalid width `{width}`"))?;
+    let height: u32 = height
+        .parse()
+        .map_err(|_| format!("invalid height `{height}`"))?;
+    Ok((width as f32, height as f32))
+}
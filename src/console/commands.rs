@@ -0,0 +1,152 @@
+use crate::AppState;
+
+pub const HELP: &str = "Commands: help, clear, spawn <enemy|star> [x y], health <n>, score <n>, \
+god, timescale <factor>, count, state <main_menu|settings|game|game_over>";
+
+#[derive(Debug, PartialEq)]
+pub enum ConsoleCommand {
+    Help,
+    Clear,
+    Spawn {
+        kind: SpawnKind,
+        position: Option<(f32, f32)>,
+    },
+    SetHealth(u32),
+    SetScore(u32),
+    ToggleGodMode,
+    SetTimeScale(f32),
+    CountEntities,
+    SwitchState(AppState),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnKind {
+    Enemy,
+    Star,
+}
+
+impl ConsoleCommand {
+    pub fn parse(line: &str) -> Result<ConsoleCommand, String> {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let arguments: Vec<&str> = words.collect();
+
+        let command = match (name, arguments.as_slice()) {
+            ("help", []) => ConsoleCommand::Help,
+            ("clear", []) => ConsoleCommand::Clear,
+            ("spawn", [kind, position @ ..]) => ConsoleCommand::Spawn {
+                kind: match *kind {
+                    "enemy" => SpawnKind::Enemy,
+                    "star" => SpawnKind::Star,
+                    _ => return Err(format!("Unknown spawn kind `{kind}`")),
+                },
+                position: match position {
+                    [] => None,
+                    [x, y] => Some((parse_number(x)?, parse_number(y)?)),
+                    _ => return Err("Expected a position as `x y`".to_string()),
+                },
+            },
+            ("health", [value]) => ConsoleCommand::SetHealth(parse_number(value)?),
+            ("score", [value]) => ConsoleCommand::SetScore(parse_number(value)?),
+            ("god", []) => ConsoleCommand::ToggleGodMode,
+            ("timescale", [value]) => ConsoleCommand::SetTimeScale(parse_number(value)?),
+            ("count", []) => ConsoleCommand::CountEntities,
+            ("state", [state]) => ConsoleCommand::SwitchState(match *state {
+                "main_menu" => AppState::MainMenu,
+                "settings" => AppState::Settings,
+                "game" => AppState::Game,
+                "game_over" => AppState::GameOver,
+                _ => return Err(format!("Unknown state `{state}`")),
+            }),
+            _ => return Err(format!("Invalid command `{line}`, type `help` for a list")),
+        };
+        Ok(command)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid number `{value}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_valid_commands() {
+        assert_eq!(ConsoleCommand::parse("help"), Ok(ConsoleCommand::Help));
+        assert_eq!(
+            ConsoleCommand::parse("  clear  "),
+            Ok(ConsoleCommand::Clear)
+        );
+        assert_eq!(
+            ConsoleCommand::parse("spawn enemy"),
+            Ok(ConsoleCommand::Spawn {
+                kind: SpawnKind::Enemy,
+                position: None,
+            })
+        );
+        assert_eq!(
+            ConsoleCommand::parse("spawn star 10 -20.5"),
+            Ok(ConsoleCommand::Spawn {
+                kind: SpawnKind::Star,
+                position: Some((10.0, -20.5)),
+            })
+        );
+        assert_eq!(
+            ConsoleCommand::parse("health 3"),
+            Ok(ConsoleCommand::SetHealth(3))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("score 42"),
+            Ok(ConsoleCommand::SetScore(42))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("god"),
+            Ok(ConsoleCommand::ToggleGodMode)
+        );
+        assert_eq!(
+            ConsoleCommand::parse("timescale 0.5"),
+            Ok(ConsoleCommand::SetTimeScale(0.5))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("count"),
+            Ok(ConsoleCommand::CountEntities)
+        );
+        assert_eq!(
+            ConsoleCommand::parse("state game_over"),
+            Ok(ConsoleCommand::SwitchState(AppState::GameOver))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        assert!(ConsoleCommand::parse("").is_err());
+        assert!(ConsoleCommand::parse("fly").is_err());
+        assert!(ConsoleCommand::parse("spawn ufo").is_err());
+        assert!(ConsoleCommand::parse("state lobby").is_err());
+    }
+
+    #[test]
+    fn rejects_missing_arguments() {
+        assert!(ConsoleCommand::parse("spawn").is_err());
+        assert!(ConsoleCommand::parse("spawn star 10").is_err());
+        assert!(ConsoleCommand::parse("health").is_err());
+        assert!(ConsoleCommand::parse("timescale").is_err());
+        assert!(ConsoleCommand::parse("state").is_err());
+    }
+
+    #[test]
+    fn rejects_bad_argument_values() {
+        assert_eq!(
+            ConsoleCommand::parse("health -1"),
+            Err("Invalid number `-1`".to_string())
+        );
+        assert!(ConsoleCommand::parse("score lots").is_err());
+        assert!(ConsoleCommand::parse("timescale fast").is_err());
+        assert!(ConsoleCommand::parse("spawn enemy x 10").is_err());
+        assert!(ConsoleCommand::parse("god mode").is_err());
+    }
+}
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+#[derive(Component, Debug)]
+pub struct ConsoleOverlay;
+
+#[derive(Component, Debug)]
+pub struct ConsoleLogText;
+
+#[derive(Component, Debug)]
+pub struct ConsoleInputText;
@@ -0,0 +1,63 @@
+mod commands;
+mod components;
+mod resources;
+mod styles;
+mod systems;
+
+use bevy::{
+    input::InputSystem,
+    log::{tracing_subscriber::Layer, BoxedLayer},
+    prelude::*,
+};
+
+use resources::*;
+use systems::{
+    interactions::{
+        capture_console_input, run_console_commands, toggle_console, update_console_text,
+    },
+    layout::{despawn_console, spawn_console},
+};
+
+pub use resources::GodMode;
+
+pub const CONSOLE_TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+/// Number of log lines kept in the console history.
+const MAX_LOG_LINES: usize = 200;
+/// Number of log lines shown in the console overlay.
+const VISIBLE_LOG_LINES: usize = 18;
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConsoleLog>()
+            .init_resource::<ConsoleState>()
+            .init_resource::<GodMode>()
+            .add_event::<ConsoleCommandSubmitted>()
+            .add_systems(
+                PreUpdate,
+                (toggle_console, capture_console_input)
+                    .chain()
+                    .after(InputSystem),
+            )
+            .add_systems(
+                Update,
+                (
+                    spawn_console.run_if(|state: Res<ConsoleState>| state.open),
+                    despawn_console.run_if(|state: Res<ConsoleState>| !state.open),
+                    run_console_commands,
+                    update_console_text.after(spawn_console),
+                ),
+            );
+    }
+}
+
+/// Log layer that mirrors all log output into the console, passed to the `LogPlugin`.
+pub fn console_log_layer(app: &mut App) -> Option<BoxedLayer> {
+    let console_log = ConsoleLog::default();
+    let layer = ConsoleLogLayer {
+        lines: console_log.lines.clone(),
+    };
+    app.insert_resource(console_log);
+    Some(layer.boxed())
+}
@@ -0,0 +1,80 @@
+use std::{
+    collections::VecDeque,
+    fmt::Write,
+    sync::{Arc, Mutex},
+};
+
+use bevy::{
+    log::tracing_subscriber::{layer::Context, Layer},
+    prelude::*,
+    utils::tracing::{
+        field::{Field, Visit},
+        Event as TracingEvent, Subscriber,
+    },
+};
+
+use super::MAX_LOG_LINES;
+
+#[derive(Resource, Default, Debug)]
+pub struct ConsoleState {
+    pub open: bool,
+    pub input: String,
+    pub history: Vec<String>,
+    /// Position in the history while browsing it with the arrow keys.
+    pub history_index: Option<usize>,
+}
+
+/// Log lines shared between the log layer and the console overlay.
+#[derive(Resource, Default, Debug)]
+pub struct ConsoleLog {
+    pub lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl ConsoleLog {
+    pub fn clear(&self) {
+        self.lines.lock().unwrap().clear();
+    }
+}
+
+/// Makes the player immune to asteroid hits.
+#[derive(Resource, Default, Debug)]
+pub struct GodMode {
+    pub enabled: bool,
+}
+
+#[derive(Event, Debug)]
+pub struct ConsoleCommandSubmitted {
+    pub line: String,
+}
+
+pub struct ConsoleLogLayer {
+    pub lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl<S: Subscriber> Layer<S> for ConsoleLogLayer {
+    fn on_event(&self, event: &TracingEvent<'_>, _context: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut line = format!("{} {}:", metadata.level(), metadata.target());
+        event.record(&mut LineVisitor { line: &mut line });
+
+        let mut lines = self.lines.lock().unwrap();
+        if lines.len() == MAX_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+}
+
+struct LineVisitor<'a> {
+    line: &'a mut String,
+}
+
+impl Visit for LineVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.line, " {value:?}");
+        } else {
+            let _ = write!(self.line, " {}={value:?}", field.name());
+        }
+    }
+}
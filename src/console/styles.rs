@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+pub const CONSOLE_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.position_type = PositionType::Absolute;
+    node.top = Val::Px(0.0);
+    node.left = Val::Px(0.0);
+    node.width = Val::Percent(100.0);
+    node.height = Val::Percent(45.0);
+    node.flex_direction = FlexDirection::Column;
+    node.justify_content = JustifyContent::FlexEnd;
+    node.padding = UiRect::all(Val::Px(10.0));
+    node.row_gap = Val::Px(6.0);
+    node.overflow = Overflow::clip();
+    node
+};
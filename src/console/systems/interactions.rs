@@ -0,0 +1,204 @@
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+use crate::{
+    arena::resources::Arena,
+    console::{
+        commands::{ConsoleCommand, SpawnKind, HELP},
+        components::{ConsoleInputText, ConsoleLogText},
+        resources::*,
+        CONSOLE_TOGGLE_KEY, VISIBLE_LOG_LINES,
+    },
+    game::{
+        components::Health,
+        enemy::{components::Enemy, create_enemy_bundle},
+        player::components::Player,
+        score::resources::Score,
+        star::{components::Star, create_star_bundle},
+    },
+    loading::resources::GameAssets,
+    AppState,
+};
+
+pub fn toggle_console(
+    mut console_state: ResMut<ConsoleState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(CONSOLE_TOGGLE_KEY) {
+        console_state.open = !console_state.open;
+    }
+}
+
+/// Turns key presses into console input while the console is open and hides them from the game.
+pub fn capture_console_input(
+    mut console_state: ResMut<ConsoleState>,
+    mut keyboard_input_event_reader: EventReader<KeyboardInput>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut console_command_event_writer: EventWriter<ConsoleCommandSubmitted>,
+) {
+    if !console_state.open {
+        keyboard_input_event_reader.clear();
+        return;
+    }
+
+    for event in keyboard_input_event_reader.read() {
+        if event.state == ButtonState::Released || event.key_code == CONSOLE_TOGGLE_KEY {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console_state.input);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                console_state.history.push(line.clone());
+                console_state.history_index = None;
+                console_command_event_writer.send(ConsoleCommandSubmitted { line });
+            }
+            Key::Backspace => {
+                console_state.input.pop();
+            }
+            Key::Escape => console_state.open = false,
+            Key::ArrowUp => browse_history(&mut console_state, -1),
+            Key::ArrowDown => browse_history(&mut console_state, 1),
+            Key::Space => console_state.input.push(' '),
+            Key::Character(characters) => console_state.input.push_str(characters),
+            _ => {}
+        }
+    }
+
+    keyboard_input.reset_all();
+}
+
+fn browse_history(console_state: &mut ConsoleState, step: isize) {
+    if console_state.history.is_empty() {
+        return;
+    }
+
+    let last_index = console_state.history.len() - 1;
+    let index = match console_state.history_index {
+        None if step < 0 => last_index,
+        None => return,
+        Some(index) => index.saturating_add_signed(step).min(last_index),
+    };
+    console_state.history_index = Some(index);
+    console_state.input = console_state.history[index].clone();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_console_commands(
+    mut commands: Commands,
+    mut console_command_event_reader: EventReader<ConsoleCommandSubmitted>,
+    console_log: Res<ConsoleLog>,
+    mut god_mode: ResMut<GodMode>,
+    game_assets: Res<GameAssets>,
+    arena: Res<Arena>,
+    mut player_query: Query<&mut Health, With<Player>>,
+    mut score: Option<ResMut<Score>>,
+    mut time: ResMut<Time<Virtual>>,
+    entity_query: Query<(), ()>,
+    enemy_query: Query<(), With<Enemy>>,
+    star_query: Query<(), With<Star>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    for event in console_command_event_reader.read() {
+        info!(target: "console", "> {}", event.line);
+
+        let command = match ConsoleCommand::parse(&event.line) {
+            Ok(command) => command,
+            Err(error) => {
+                warn!(target: "console", "{error}");
+                continue;
+            }
+        };
+
+        match command {
+            ConsoleCommand::Help => info!(target: "console", "{HELP}"),
+            ConsoleCommand::Clear => console_log.clear(),
+            ConsoleCommand::Spawn { kind, position } => {
+                let (x_position, y_position) = position.unwrap_or_else(|| arena.center().into());
+                match kind {
+                    SpawnKind::Enemy => {
+                        commands.spawn(create_enemy_bundle(&game_assets, x_position, y_position))
+                    }
+                    SpawnKind::Star => {
+                        commands.spawn(create_star_bundle(&game_assets, x_position, y_position))
+                    }
+                };
+                info!(target: "console", "Spawned {kind:?} at ({x_position}, {y_position})");
+            }
+            ConsoleCommand::SetHealth(value) => {
+                if player_query.is_empty() {
+                    warn!(target: "console", "There is no player");
+                    continue;
+                }
+                for mut player_health in &mut player_query {
+                    player_health.current = value;
+                }
+                info!(target: "console", "Set health to {value}");
+            }
+            ConsoleCommand::SetScore(value) => match score.as_mut() {
+                Some(score) => {
+                    score.value = value;
+                    info!(target: "console", "Set score to {value}");
+                }
+                None => warn!(target: "console", "There is no running game"),
+            },
+            ConsoleCommand::ToggleGodMode => {
+                god_mode.enabled = !god_mode.enabled;
+                info!(target: "console", "God mode {}", if god_mode.enabled { "on" } else { "off" });
+            }
+            ConsoleCommand::SetTimeScale(factor) => {
+                if factor < 0.0 {
+                    warn!(target: "console", "Time scale must not be negative");
+                    continue;
+                }
+                time.set_relative_speed(factor);
+                info!(target: "console", "Set time scale to {factor}");
+            }
+            ConsoleCommand::CountEntities => info!(
+                target: "console",
+                "{} entities, {} enemies, {} stars",
+                entity_query.iter().count(),
+                enemy_query.iter().count(),
+                star_query.iter().count()
+            ),
+            ConsoleCommand::SwitchState(app_state) => {
+                next_app_state.set(app_state);
+                info!(target: "console", "Switching to {app_state:?}");
+            }
+        }
+    }
+}
+
+pub fn update_console_text(
+    console_state: Res<ConsoleState>,
+    console_log: Res<ConsoleLog>,
+    mut log_text_query: Query<&mut Text, (With<ConsoleLogText>, Without<ConsoleInputText>)>,
+    mut input_text_query: Query<&mut Text, (With<ConsoleInputText>, Without<ConsoleLogText>)>,
+) {
+    if let Ok(mut log_text) = log_text_query.get_single_mut() {
+        let lines = console_log.lines.lock().unwrap();
+        let visible_lines: Vec<&str> = lines
+            .iter()
+            .skip(lines.len().saturating_sub(VISIBLE_LOG_LINES))
+            .map(String::as_str)
+            .collect();
+        let value = visible_lines.join("\n");
+        if log_text.0 != value {
+            log_text.0 = value;
+        }
+    }
+    if let Ok(mut input_text) = input_text_query.get_single_mut() {
+        let value = format!("> {}_", console_state.input);
+        if input_text.0 != value {
+            input_text.0 = value;
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::{
+    console::{
+        components::{ConsoleInputText, ConsoleLogText, ConsoleOverlay},
+        styles::CONSOLE_NODE,
+    },
+    widgets::{
+        resources::{UiFont, UiTheme},
+        text_bundle,
+    },
+};
+
+pub fn spawn_console(
+    mut commands: Commands,
+    console_query: Query<(), With<ConsoleOverlay>>,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+) {
+    if !console_query.is_empty() {
+        return;
+    }
+
+    commands
+        .spawn((
+            CONSOLE_NODE,
+            BackgroundColor(ui_theme.overlay_color),
+            // Show the console on top of all menus
+            GlobalZIndex(i32::MAX),
+            ConsoleOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                text_bundle(
+                    "",
+                    &ui_font,
+                    ui_theme.small_font_size,
+                    ui_theme.muted_text_color,
+                ),
+                ConsoleLogText,
+            ));
+            parent.spawn((
+                text_bundle("", &ui_font, ui_theme.small_font_size, ui_theme.text_color),
+                ConsoleInputText,
+            ));
+        });
+}
+
+pub fn despawn_console(mut commands: Commands, console_query: Query<Entity, With<ConsoleOverlay>>) {
+    for console_entity in &console_query {
+        commands.entity(console_entity).despawn_recursive();
+    }
+}
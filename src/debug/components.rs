@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+#[derive(Component, Debug)]
+pub struct DebugOverlay;
+
+#[derive(Component, Debug)]
+pub struct DebugStatsText;
+
+/// Bar of the frame time graph, `index` counts back from the latest frame.
+#[derive(Component, Debug)]
+pub struct FrameTimeBar {
+    pub index: usize,
+}
@@ -0,0 +1,48 @@
+mod components;
+mod resources;
+mod styles;
+mod systems;
+
+use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+
+use resources::*;
+use systems::{
+    gizmos::{draw_collider_gizmos, draw_velocity_gizmos},
+    layout::{despawn_debug_overlay, spawn_debug_overlay},
+    overlay::{toggle_debug_overlay, update_debug_stats, update_frame_time_graph},
+};
+
+use crate::AppState;
+
+pub const DEBUG_TOGGLE_KEY: KeyCode = KeyCode::F3;
+/// Number of frames shown in the frame time graph.
+const FRAME_TIME_GRAPH_LENGTH: usize = 120;
+/// Frame time in milliseconds that fills the whole graph height.
+const FRAME_TIME_GRAPH_MAX: f64 = 50.0;
+/// Length of the velocity arrows per unit of speed.
+const VELOCITY_GIZMO_SCALE: f32 = 0.25;
+
+/// Developer overlay with collider and velocity gizmos, frame timing and entity counts.
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(FrameTimeDiagnosticsPlugin)
+            .init_resource::<DebugOverlayState>()
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay,
+                    (
+                        spawn_debug_overlay,
+                        (update_debug_stats, update_frame_time_graph).after(spawn_debug_overlay),
+                        (draw_collider_gizmos, draw_velocity_gizmos)
+                            .run_if(in_state(AppState::Game)),
+                    )
+                        .run_if(|state: Res<DebugOverlayState>| state.visible),
+                    despawn_debug_overlay.run_if(|state: Res<DebugOverlayState>| !state.visible),
+                )
+                    .chain(),
+            );
+    }
+}
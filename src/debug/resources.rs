@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Default, Debug)]
+pub struct DebugOverlayState {
+    pub visible: bool,
+}
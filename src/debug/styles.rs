@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+pub const DEBUG_OVERLAY_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.position_type = PositionType::Absolute;
+    node.top = Val::Px(10.0);
+    node.right = Val::Px(10.0);
+    node.flex_direction = FlexDirection::Column;
+    node.padding = UiRect::all(Val::Px(8.0));
+    node.row_gap = Val::Px(8.0);
+    node
+};
+
+pub const FRAME_TIME_GRAPH_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.height = Val::Px(60.0);
+    node.flex_direction = FlexDirection::RowReverse;
+    node.align_items = AlignItems::FlexEnd;
+    node
+};
+
+pub const FRAME_TIME_BAR_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.width = Val::Px(2.0);
+    node.height = Val::Px(0.0);
+    node
+};
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use crate::{
+    debug::VELOCITY_GIZMO_SCALE,
+    game::{
+        enemy::{components::Enemy, ENEMY_SIZE, ENEMY_SPEED},
+        player::{components::Player, PLAYER_SIZE},
+        star::{components::Star, STAR_SIZE},
+    },
+};
+
+const PLAYER_COLLIDER_COLOR: Color = Color::srgb(0.2, 0.9, 0.3);
+const ENEMY_COLLIDER_COLOR: Color = Color::srgb(0.9, 0.2, 0.2);
+const STAR_COLLIDER_COLOR: Color = Color::srgb(0.9, 0.9, 0.2);
+const VELOCITY_COLOR: Color = Color::srgb(0.3, 0.6, 1.0);
+
+pub fn draw_collider_gizmos(
+    mut gizmos: Gizmos,
+    player_query: Query<&Transform, With<Player>>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    star_query: Query<&Transform, With<Star>>,
+) {
+    for player_transform in &player_query {
+        gizmos.circle_2d(
+            player_transform.translation.truncate(),
+            PLAYER_SIZE / 2.0,
+            PLAYER_COLLIDER_COLOR,
+        );
+    }
+    for enemy_transform in &enemy_query {
+        gizmos.circle_2d(
+            enemy_transform.translation.truncate(),
+            ENEMY_SIZE / 2.0,
+            ENEMY_COLLIDER_COLOR,
+        );
+    }
+    for star_transform in &star_query {
+        gizmos.circle_2d(
+            star_transform.translation.truncate(),
+            STAR_SIZE / 2.0,
+            STAR_COLLIDER_COLOR,
+        );
+    }
+}
+
+pub fn draw_velocity_gizmos(mut gizmos: Gizmos, enemy_query: Query<(&Transform, &Enemy)>) {
+    for (enemy_transform, enemy) in &enemy_query {
+        let start = enemy_transform.translation.truncate();
+        let velocity = enemy.direction.truncate() * ENEMY_SPEED;
+        if velocity == Vec2::ZERO {
+            continue;
+        }
+        gizmos.arrow_2d(
+            start,
+            start + velocity * VELOCITY_GIZMO_SCALE,
+            VELOCITY_COLOR,
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+use crate::{
+    debug::{
+        components::{DebugOverlay, DebugStatsText, FrameTimeBar},
+        styles::{DEBUG_OVERLAY_NODE, FRAME_TIME_BAR_NODE, FRAME_TIME_GRAPH_NODE},
+        FRAME_TIME_GRAPH_LENGTH,
+    },
+    widgets::{
+        resources::{UiFont, UiTheme},
+        text_bundle,
+    },
+};
+
+const FRAME_TIME_BAR_COLOR: Color = Color::srgb(0.2, 0.9, 0.3);
+
+pub fn spawn_debug_overlay(
+    mut commands: Commands,
+    overlay_query: Query<(), With<DebugOverlay>>,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+) {
+    if !overlay_query.is_empty() {
+        return;
+    }
+
+    commands
+        .spawn((
+            DEBUG_OVERLAY_NODE,
+            BackgroundColor(ui_theme.overlay_color),
+            GlobalZIndex(i32::MAX - 1),
+            DebugOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                text_bundle("", &ui_font, ui_theme.small_font_size, ui_theme.text_color),
+                DebugStatsText,
+            ));
+            parent.spawn(FRAME_TIME_GRAPH_NODE).with_children(|parent| {
+                for index in 0..FRAME_TIME_GRAPH_LENGTH {
+                    parent.spawn((
+                        FRAME_TIME_BAR_NODE,
+                        BackgroundColor(FRAME_TIME_BAR_COLOR),
+                        FrameTimeBar { index },
+                    ));
+                }
+            });
+        });
+}
+
+pub fn despawn_debug_overlay(
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<DebugOverlay>>,
+) {
+    for overlay_entity in &overlay_query {
+        commands.entity(overlay_entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,3 @@
+pub mod gizmos;
+pub mod layout;
+pub mod overlay;
@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+
+use crate::{
+    debug::{
+        components::{DebugStatsText, FrameTimeBar},
+        resources::DebugOverlayState,
+        DEBUG_TOGGLE_KEY, FRAME_TIME_GRAPH_MAX,
+    },
+    game::{
+        enemy::components::Enemy, particles::components::Particle, player::components::Player,
+        star::components::Star, SimulationState,
+    },
+    AppState,
+};
+
+pub fn toggle_debug_overlay(
+    mut debug_overlay_state: ResMut<DebugOverlayState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(DEBUG_TOGGLE_KEY) {
+        debug_overlay_state.visible = !debug_overlay_state.visible;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_debug_stats(
+    mut stats_text_query: Query<&mut Text, With<DebugStatsText>>,
+    diagnostics: Res<DiagnosticsStore>,
+    app_state: Res<State<AppState>>,
+    simulation_state: Res<State<SimulationState>>,
+    entity_query: Query<()>,
+    player_query: Query<(), With<Player>>,
+    enemy_query: Query<(), With<Enemy>>,
+    star_query: Query<(), With<Star>>,
+    particle_query: Query<(), With<Particle>>,
+) {
+    let Ok(mut stats_text) = stats_text_query.get_single_mut() else {
+        return;
+    };
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let frame_time = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|frame_time| frame_time.smoothed())
+        .unwrap_or_default();
+
+    let mut value = String::new();
+    let _ = writeln!(value, "FPS: {fps:.0} ({frame_time:.2} ms)");
+    let _ = writeln!(value, "AppState: {:?}", app_state.get());
+    let _ = writeln!(value, "SimulationState: {:?}", simulation_state.get());
+    let _ = writeln!(value, "Entities: {}", entity_query.iter().count());
+    let _ = writeln!(value, "Players: {}", player_query.iter().count());
+    let _ = writeln!(value, "Enemies: {}", enemy_query.iter().count());
+    let _ = writeln!(value, "Stars: {}", star_query.iter().count());
+    let _ = write!(value, "Particles: {}", particle_query.iter().count());
+    stats_text.0 = value;
+}
+
+pub fn update_frame_time_graph(
+    mut bar_query: Query<(&mut Node, &FrameTimeBar)>,
+    diagnostics: Res<DiagnosticsStore>,
+) {
+    let Some(frame_time) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else {
+        return;
+    };
+    let mut frame_times: Vec<f64> = frame_time.values().copied().collect();
+    frame_times.reverse();
+
+    for (mut node, bar) in &mut bar_query {
+        let frame_time = frame_times.get(bar.index).copied().unwrap_or_default();
+        let fraction = (frame_time / FRAME_TIME_GRAPH_MAX).min(1.0) as f32;
+        node.height = Val::Percent(fraction * 100.0);
+    }
+}
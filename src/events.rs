@@ -1,6 +1,46 @@
 use bevy::prelude::*;
 
-#[derive(Event)]
+use crate::game::player::components::PlayerId;
+use crate::widgets::components::ButtonAction;
+
+#[derive(Event, Debug)]
 pub struct GameOver {
     pub score: u32,
 }
+
+/// A player lost all health while other players are still in the run.
+#[derive(Event, Debug)]
+pub struct PlayerOut {
+    pub player_id: PlayerId,
+}
+
+#[derive(Event, Debug)]
+pub struct StarCollected {
+    pub position: Vec3,
+}
+
+#[derive(Event, Debug)]
+pub struct AsteroidImpact {
+    pub position: Vec3,
+}
+
+/// An enemy bounced off an edge of the arena.
+#[derive(Event, Debug)]
+pub struct EnemyBounced;
+
+#[derive(Event)]
+pub struct MenuButtonPressed {
+    pub action: ButtonAction,
+}
+
+/// Starts a new run from the menus.
+#[derive(Event)]
+pub struct StartRun;
+
+/// Abandons the current run and returns to the main menu.
+#[derive(Event)]
+pub struct EndRun;
+
+/// Ends the current run and immediately starts a new one.
+#[derive(Event)]
+pub struct RestartRun;
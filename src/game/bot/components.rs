@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Player steered by the game itself instead of local input.
+#[derive(Component)]
+pub struct Bot;
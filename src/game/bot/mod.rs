@@ -0,0 +1,70 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::BotRuns;
+use systems::*;
+
+pub use systems::bots_playing;
+
+use super::enemy::confine_enemy_movement;
+use super::mode::insert_time_limit;
+use super::player::{handle_game_over, player_movement};
+use super::run::RunStart;
+use super::simulation::SimulationStep;
+use super::SimulationState;
+use crate::AppState;
+
+/// Seconds of enemy movement a bot looks ahead before picking a direction.
+pub const BOT_LOOKAHEAD: f32 = 0.6;
+/// Points along the lookahead at which a bot checks its distance to the enemies.
+pub const BOT_LOOKAHEAD_STEPS: usize = 12;
+/// Directions a bot chooses from, besides standing still.
+pub const BOT_DIRECTIONS: usize = 16;
+/// Distance beyond a collision at which bots start to shy away from enemies.
+pub const BOT_SAFETY_MARGIN: f32 = 48.0;
+/// How many pixels of detour towards a star a bot accepts to stay clear of enemies.
+const BOT_DANGER_WEIGHT: f32 = 400.0;
+/// Seconds after which bots finish a zen run, which would go on forever otherwise.
+pub const BOT_ZEN_RUN_DURATION: f32 = 120.0;
+
+/// Lets bots drive the players, for attract-mode demos and headless batch runs.
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BotRuns>()
+            .add_systems(
+                RunStart,
+                (
+                    reset_bot_run_time,
+                    limit_bot_zen_run.after(insert_time_limit),
+                )
+                    .run_if(bots_playing),
+            )
+            .add_systems(
+                SimulationStep,
+                // Bots see where the enemies moved this step before the players move
+                steer_bots
+                    .after(confine_enemy_movement)
+                    .before(player_movement),
+            )
+            .add_systems(
+                Update,
+                (
+                    mark_bot_players,
+                    time_bot_run.run_if(in_state(SimulationState::Running)),
+                    // Sees every game over before the run ends with it
+                    record_bot_runs.after(handle_game_over),
+                )
+                    .run_if(in_state(AppState::Game))
+                    .run_if(bots_playing),
+            )
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                restart_bot_run.run_if(bots_playing),
+            );
+    }
+}
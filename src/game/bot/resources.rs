@@ -0,0 +1,133 @@
+use std::fmt;
+
+use bevy::prelude::*;
+
+/// How a single bot run went.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BotRunResult {
+    pub score: u32,
+    pub missed: u32,
+    /// Seconds the bots stayed in the run.
+    pub survived: f32,
+}
+
+/// Results of the bot runs played so far, to quit with their summary after a batch of runs.
+#[derive(Resource, Debug, Default)]
+pub struct BotRuns {
+    /// Number of runs after which the game quits, playing on forever if `None`.
+    pub limit: Option<u32>,
+    pub results: Vec<BotRunResult>,
+    /// Seconds the current run has been going on.
+    pub elapsed: f32,
+}
+
+impl BotRuns {
+    pub fn limited(runs: u32) -> Self {
+        BotRuns {
+            limit: Some(runs),
+            ..default()
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.limit
+            .is_some_and(|limit| self.results.len() >= limit as usize)
+    }
+
+    pub fn summary(&self) -> Option<BotRunSummary> {
+        let runs = self.results.len();
+        if runs == 0 {
+            return None;
+        }
+        let scores = self.results.iter().map(|result| result.score);
+        Some(BotRunSummary {
+            runs,
+            mean_score: scores.clone().sum::<u32>() as f32 / runs as f32,
+            min_score: scores.clone().min().unwrap_or_default(),
+            max_score: scores.max().unwrap_or_default(),
+            mean_missed: self.results.iter().map(|result| result.missed).sum::<u32>() as f32
+                / runs as f32,
+            mean_survived: self
+                .results
+                .iter()
+                .map(|result| result.survived)
+                .sum::<f32>()
+                / runs as f32,
+        })
+    }
+}
+
+/// Aggregated results of a batch of bot runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BotRunSummary {
+    pub runs: usize,
+    pub mean_score: f32,
+    pub min_score: u32,
+    pub max_score: u32,
+    pub mean_missed: f32,
+    pub mean_survived: f32,
+}
+
+impl fmt::Display for BotRunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} runs, {:.1} stars on average ({} to {}), {:.1} missed, survived {:.1}s",
+            self.runs,
+            self.mean_score,
+            self.min_score,
+            self.max_score,
+            self.mean_missed,
+            self.mean_survived
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(score: u32, missed: u32, survived: f32) -> BotRunResult {
+        BotRunResult {
+            score,
+            missed,
+            survived,
+        }
+    }
+
+    #[test]
+    fn unlimited_runs_never_complete() {
+        let bot_runs = BotRuns {
+            results: vec![result(1, 0, 1.0); 100],
+            ..default()
+        };
+        assert!(!bot_runs.is_complete());
+    }
+
+    #[test]
+    fn limited_runs_complete_after_the_limit() {
+        let mut bot_runs = BotRuns::limited(2);
+        bot_runs.results.push(result(1, 0, 1.0));
+        assert!(!bot_runs.is_complete());
+        bot_runs.results.push(result(1, 0, 1.0));
+        assert!(bot_runs.is_complete());
+    }
+
+    #[test]
+    fn summary_aggregates_the_results() {
+        let mut bot_runs = BotRuns::limited(3);
+        assert_eq!(bot_runs.summary(), None);
+
+        bot_runs.results = vec![result(4, 1, 10.0), result(2, 3, 20.0), result(9, 2, 60.0)];
+        let summary = bot_runs.summary().unwrap();
+        assert_eq!(summary.runs, 3);
+        assert_eq!(summary.mean_score, 5.0);
+        assert_eq!((summary.min_score, summary.max_score), (2, 9));
+        assert_eq!(summary.mean_missed, 2.0);
+        assert_eq!(summary.mean_survived, 30.0);
+        assert_eq!(
+            summary.to_string(),
+            "3 runs, 5.0 stars on average (2 to 9), 2.0 missed, survived 30.0s"
+        );
+    }
+}
@@ -0,0 +1,417 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use super::components::Bot;
+use super::resources::{BotRunResult, BotRuns};
+use super::{
+    BOT_DANGER_WEIGHT, BOT_DIRECTIONS, BOT_LOOKAHEAD, BOT_LOOKAHEAD_STEPS, BOT_SAFETY_MARGIN,
+    BOT_ZEN_RUN_DURATION,
+};
+use crate::arena::resources::Arena;
+use crate::events::{GameOver, RestartRun};
+use crate::game::daily::resources::ActiveModifiers;
+use crate::game::enemy::components::Enemy;
+use crate::game::enemy::{ENEMY_SIZE, ENEMY_SPEED};
+use crate::game::mode::resources::TimeLimit;
+use crate::game::player::components::{Player, PlayerInput, RemoteControlled};
+use crate::game::player::{PLAYER_SIZE, PLAYER_SPEED};
+use crate::game::resources::RunSettings;
+use crate::game::score::resources::Score;
+use crate::game::simulation::components::SimulationId;
+use crate::game::spawning::components::{EnteringArena, Telegraphing};
+use crate::game::star::components::{Star, StarLifetime};
+
+type BotPlayer = (With<Bot>, Without<RemoteControlled>);
+type UnmarkedPlayer = (With<Player>, Without<RemoteControlled>, Without<Bot>);
+type EnemyState<'a> = (
+    &'a Transform,
+    &'a Enemy,
+    &'a SimulationId,
+    Option<&'a Telegraphing>,
+    Option<&'a EnteringArena>,
+);
+
+/// Where an enemy will be, assuming it keeps its direction apart from bouncing off the arena edges.
+struct EnemyTrajectory {
+    start: Vec2,
+    velocity: Vec2,
+    /// Seconds until a telegraphing enemy starts to move.
+    delay: f32,
+}
+
+impl EnemyTrajectory {
+    fn position_at(&self, time: f32, [x_min, x_max, y_min, y_max]: [f32; 4]) -> Vec2 {
+        let position = self.start + self.velocity * (time - self.delay).max(0.0);
+        Vec2::new(
+            bounce(position.x, x_min, x_max),
+            bounce(position.y, y_min, y_max),
+        )
+    }
+}
+
+pub fn bots_playing(run_settings: Res<RunSettings>) -> bool {
+    run_settings.bots
+}
+
+/// Hands every player of this machine over to a bot, also the ones restored from a save.
+pub fn mark_bot_players(mut commands: Commands, player_query: Query<Entity, UnmarkedPlayer>) {
+    for player_entity in &player_query {
+        commands.entity(player_entity).insert(Bot);
+    }
+}
+
+/// Steers each bot towards the nearest star it can reach in time, along the direction that
+/// keeps it furthest from the enemies over the next moments.
+pub fn steer_bots(
+    mut bot_query: Query<(&mut PlayerInput, &Transform), BotPlayer>,
+    enemy_query: Query<EnemyState>,
+    star_query: Query<(&Transform, &StarLifetime, &SimulationId), With<Star>>,
+    arena: Res<Arena>,
+    active_modifiers: Res<ActiveModifiers>,
+) {
+    let enemy_speed = ENEMY_SPEED * active_modifiers.enemy_speed_factor();
+    // Bots decide the same way on every machine
+    let trajectories: Vec<EnemyTrajectory> = enemy_query
+        .iter()
+        .sort::<&SimulationId>()
+        .map(
+            |(enemy_transform, enemy, _, telegraphing, entering_arena)| EnemyTrajectory {
+                start: entering_arena.map_or(enemy_transform.translation.truncate(), |entering| {
+                    entering.to
+                }),
+                velocity: enemy.direction.truncate() * enemy_speed,
+                delay: telegraphing.map_or(0.0, |telegraphing| telegraphing.timer.remaining_secs()),
+            },
+        )
+        .collect();
+    let stars: Vec<(Vec2, f32)> = star_query
+        .iter()
+        .sort::<&SimulationId>()
+        .map(|(star_transform, star_lifetime, _)| {
+            (
+                star_transform.translation.truncate(),
+                star_lifetime.timer.remaining_secs(),
+            )
+        })
+        .collect();
+
+    for (mut player_input, player_transform) in &mut bot_query {
+        let position = player_transform.translation.truncate();
+        // Without a star in reach the bot waits in the middle, where it can dodge in all directions
+        let target = nearest_reachable_star(position, &stars).unwrap_or(arena.center());
+        player_input.direction = safest_direction(position, target, &trajectories, &arena);
+    }
+}
+
+fn nearest_reachable_star(position: Vec2, stars: &[(Vec2, f32)]) -> Option<Vec2> {
+    stars
+        .iter()
+        .filter(|(star, lifetime)| position.distance(*star) / PLAYER_SPEED < *lifetime)
+        .min_by(|(a, _), (b, _)| {
+            position
+                .distance_squared(*a)
+                .total_cmp(&position.distance_squared(*b))
+        })
+        .map(|(star, _)| *star)
+}
+
+/// Plays each direction ahead and picks the one that gets closest to the target at the least risk.
+fn safest_direction(
+    position: Vec2,
+    target: Vec2,
+    trajectories: &[EnemyTrajectory],
+    arena: &Arena,
+) -> Vec2 {
+    let player_bounds = arena.confinement(PLAYER_SIZE);
+    let enemy_bounds = arena.confinement(ENEMY_SIZE);
+    let collision_distance = (PLAYER_SIZE + ENEMY_SIZE) / 2.0;
+    let step_duration = BOT_LOOKAHEAD / BOT_LOOKAHEAD_STEPS as f32;
+
+    let directions = std::iter::once(Vec2::ZERO).chain(
+        (0..BOT_DIRECTIONS)
+            .map(|index| Vec2::from_angle(index as f32 / BOT_DIRECTIONS as f32 * TAU)),
+    );
+    let mut best = (Vec2::ZERO, f32::INFINITY);
+    for direction in directions {
+        let mut predicted_position = position;
+        let mut target_distance = position.distance(target);
+        let mut danger = 0.0;
+        for step in 1..=BOT_LOOKAHEAD_STEPS {
+            let [x_min, x_max, y_min, y_max] = player_bounds;
+            predicted_position += direction * PLAYER_SPEED * step_duration;
+            predicted_position =
+                predicted_position.clamp(Vec2::new(x_min, y_min), Vec2::new(x_max, y_max));
+            target_distance = target_distance.min(predicted_position.distance(target));
+
+            let time = step as f32 * step_duration;
+            for trajectory in trajectories {
+                let clearance = predicted_position
+                    .distance(trajectory.position_at(time, enemy_bounds))
+                    - collision_distance;
+                if clearance < BOT_SAFETY_MARGIN {
+                    // Close calls in the near future count more than far ones, which can still be dodged
+                    let closeness = (BOT_SAFETY_MARGIN - clearance) / BOT_SAFETY_MARGIN;
+                    danger += closeness * closeness / step as f32;
+                }
+            }
+        }
+
+        let cost = target_distance + BOT_DANGER_WEIGHT * danger;
+        if cost < best.1 {
+            best = (direction, cost);
+        }
+    }
+    best.0
+}
+
+/// Folds a position moving along an axis back between the bounds, like a bouncing enemy.
+fn bounce(value: f32, min: f32, max: f32) -> f32 {
+    let length = max - min;
+    if length <= 0.0 {
+        return min;
+    }
+    let offset = (value - min).rem_euclid(2.0 * length);
+    match offset > length {
+        true => min + 2.0 * length - offset,
+        false => min + offset,
+    }
+}
+
+/// Gives bot zen runs an end, since nobody is there to finish them from the pause menu.
+pub fn limit_bot_zen_run(mut commands: Commands, run_settings: Res<RunSettings>) {
+    if run_settings.mode.is_finished_by_player() {
+        commands.insert_resource(TimeLimit {
+            timer: Timer::from_seconds(BOT_ZEN_RUN_DURATION, TimerMode::Once),
+        });
+    }
+}
+
+pub fn reset_bot_run_time(mut bot_runs: ResMut<BotRuns>) {
+    bot_runs.elapsed = 0.0;
+}
+
+pub fn time_bot_run(mut bot_runs: ResMut<BotRuns>, time: Res<Time>) {
+    bot_runs.elapsed += time.delta_secs();
+}
+
+pub fn record_bot_runs(
+    mut game_over_event_reader: EventReader<GameOver>,
+    mut bot_runs: ResMut<BotRuns>,
+    score: Res<Score>,
+) {
+    for event in game_over_event_reader.read() {
+        let result = BotRunResult {
+            score: event.score,
+            missed: score.missed,
+            survived: bot_runs.elapsed,
+        };
+        info!(
+            target: "bot",
+            "Bot run over with {} stars, {} missed, after {:.1}s",
+            result.score,
+            result.missed,
+            result.survived
+        );
+        bot_runs.results.push(result);
+    }
+}
+
+/// Bots play run after run, so batch runs and demos don't stop at the game over screen, until
+/// a limited batch is complete.
+pub fn restart_bot_run(
+    bot_runs: Res<BotRuns>,
+    mut restart_run_event_writer: EventWriter<RestartRun>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+) {
+    if !bot_runs.is_complete() {
+        restart_run_event_writer.send(RestartRun);
+        return;
+    }
+    if let Some(summary) = bot_runs.summary() {
+        info!(target: "bot", "Bot runs complete: {summary}");
+    }
+    app_exit_event_writer.send(AppExit::Success);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use clap::Parser;
+
+    use super::*;
+    use crate::{cli::LaunchOptions, game_app, AppState};
+
+    /// Frames after which an idle player must have lost the run.
+    const IDLE_FRAMES_LIMIT: u32 = 5_000;
+
+    fn enemy_at(start: Vec2, velocity: Vec2) -> EnemyTrajectory {
+        EnemyTrajectory {
+            start,
+            velocity,
+            delay: 0.0,
+        }
+    }
+
+    #[test]
+    fn bounce_keeps_values_inside_the_bounds() {
+        assert_eq!(bounce(5.0, 0.0, 10.0), 5.0);
+        assert_eq!(bounce(10.0, 0.0, 10.0), 10.0);
+        assert_eq!(bounce(15.0, 10.0, 20.0), 15.0);
+    }
+
+    #[test]
+    fn bounce_folds_values_back_from_the_bounds() {
+        assert_eq!(bounce(12.0, 0.0, 10.0), 8.0);
+        assert_eq!(bounce(-3.0, 0.0, 10.0), 3.0);
+        assert_eq!(bounce(23.0, 0.0, 10.0), 3.0);
+        assert_eq!(bounce(35.0, 0.0, 10.0), 5.0);
+        assert_eq!(bounce(25.0, 10.0, 20.0), 15.0);
+    }
+
+    #[test]
+    fn bounce_between_equal_bounds_stays_at_the_bound() {
+        assert_eq!(bounce(7.0, 3.0, 3.0), 3.0);
+        assert_eq!(bounce(7.0, 3.0, 1.0), 3.0);
+    }
+
+    #[test]
+    fn trajectories_bounce_off_the_arena_edges_after_the_delay() {
+        let trajectory = EnemyTrajectory {
+            start: Vec2::new(90.0, 50.0),
+            velocity: Vec2::new(20.0, 0.0),
+            delay: 1.0,
+        };
+        let bounds = [0.0, 100.0, 0.0, 100.0];
+        assert_eq!(trajectory.position_at(0.5, bounds), Vec2::new(90.0, 50.0));
+        assert_eq!(trajectory.position_at(2.5, bounds), Vec2::new(80.0, 50.0));
+    }
+
+    #[test]
+    fn nearest_reachable_star_skips_stars_that_expire_on_the_way() {
+        let position = Vec2::new(100.0, 100.0);
+        let near = Vec2::new(100.0, 100.0 + PLAYER_SPEED);
+        let far = Vec2::new(100.0 + 2.0 * PLAYER_SPEED, 100.0);
+        assert_eq!(
+            nearest_reachable_star(position, &[(far, 5.0), (near, 5.0)]),
+            Some(near)
+        );
+        // The near star is a second away but expires before
+        assert_eq!(
+            nearest_reachable_star(position, &[(far, 5.0), (near, 0.9)]),
+            Some(far)
+        );
+        assert_eq!(nearest_reachable_star(position, &[(near, 0.9)]), None);
+        assert_eq!(nearest_reachable_star(position, &[]), None);
+    }
+
+    #[test]
+    fn safest_direction_heads_for_the_target_without_enemies() {
+        let arena = Arena::default();
+        let position = arena.center();
+        let direction = safest_direction(position, position + Vec2::new(300.0, 0.0), &[], &arena);
+        assert!(direction.abs_diff_eq(Vec2::X, 1e-5), "{direction}");
+        let direction = safest_direction(position, position - Vec2::new(0.0, 300.0), &[], &arena);
+        assert!(direction.abs_diff_eq(Vec2::NEG_Y, 1e-5), "{direction}");
+    }
+
+    #[test]
+    fn safest_direction_stands_still_at_the_target() {
+        let arena = Arena::default();
+        let position = arena.center();
+        assert_eq!(
+            safest_direction(position, position, &[], &arena),
+            Vec2::ZERO
+        );
+    }
+
+    #[test]
+    fn safest_direction_gets_out_of_the_way_of_an_oncoming_enemy() {
+        let arena = Arena::default();
+        let position = arena.center();
+        let target = position + Vec2::new(300.0, 0.0);
+        let enemy = enemy_at(
+            position + Vec2::new(150.0, 0.0),
+            Vec2::new(-ENEMY_SPEED, 0.0),
+        );
+        let direction = safest_direction(position, target, &[enemy], &arena);
+        assert!(direction.y.abs() > 0.5, "{direction}");
+    }
+
+    #[test]
+    fn safest_direction_ignores_enemies_moving_away() {
+        let arena = Arena::default();
+        let position = arena.center();
+        let target = position + Vec2::new(300.0, 0.0);
+        let enemy = enemy_at(
+            position + Vec2::new(150.0, 0.0),
+            Vec2::new(0.0, ENEMY_SPEED * 4.0),
+        );
+        let direction = safest_direction(position, target, &[enemy], &arena);
+        assert!(direction.abs_diff_eq(Vec2::X, 1e-5), "{direction}");
+    }
+
+    /// Plays a seeded endless run and returns the frames until its game over, or `frames_limit`.
+    fn frames_survived(bot: bool, frames_limit: u32) -> u32 {
+        let mut args = vec![
+            "bevy_ball_game",
+            "--headless",
+            "--mute",
+            "--seed",
+            "7",
+            "--start-in",
+            "game",
+        ];
+        if bot {
+            args.push("--bot");
+        }
+        let launch_options = LaunchOptions::parse_from(args);
+        let mut app = game_app(&launch_options);
+        launch_options.apply(&mut app).unwrap();
+        app.finish();
+        app.cleanup();
+
+        let app_state = |app: &App| {
+            app.world()
+                .get_resource::<State<AppState>>()
+                .map(|app_state| *app_state.get())
+        };
+        // Loading the assets takes a while in real time
+        for _ in 0..5_000 {
+            if app_state(&app) == Some(AppState::Game) {
+                break;
+            }
+            app.update();
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(
+            app_state(&app),
+            Some(AppState::Game),
+            "the run didn't start"
+        );
+
+        for frame in 0..frames_limit {
+            app.update();
+            if app_state(&app) != Some(AppState::Game) {
+                return frame;
+            }
+        }
+        frames_limit
+    }
+
+    #[test]
+    fn bot_survives_longer_than_an_idle_player() {
+        let idle_frames = frames_survived(false, IDLE_FRAMES_LIMIT);
+        assert!(
+            idle_frames < IDLE_FRAMES_LIMIT,
+            "the idle player never lost"
+        );
+        // The bot only has to outlast the idle player, playing its whole run takes minutes
+        let bot_frames = frames_survived(true, idle_frames * 2);
+        assert!(
+            bot_frames > idle_frames,
+            "the bot lost after {bot_frames} frames, the idle player after {idle_frames}"
+        );
+    }
+}
@@ -0,0 +1,48 @@
+pub mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::*;
+use systems::*;
+
+pub use systems::apply_daily_challenge;
+
+use super::bot::bots_playing;
+use super::run::{RunEnd, RunStart};
+use super::score::remove_score;
+use super::systems::roll_run_seed;
+use crate::AppState;
+
+/// File in which the scores of past daily challenges are kept.
+pub const DAILY_RECORDS_PATH: &str = "saves/daily.ron";
+/// Number of modifiers active in each daily challenge.
+pub const DAILY_MODIFIER_COUNT: usize = 2;
+
+/// Derives a daily challenge from the current date and allows one scored attempt per day.
+pub struct DailyChallengePlugin;
+
+impl Plugin for DailyChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DailyChallenge>()
+            .init_resource::<DailyRecords>()
+            .init_resource::<ActiveModifiers>()
+            // The date might have changed since the game was started
+            .add_systems(OnEnter(AppState::MainMenu), refresh_daily_challenge)
+            .add_systems(
+                RunStart,
+                (
+                    apply_daily_challenge.before(roll_run_seed),
+                    start_daily_attempt
+                        .after(apply_daily_challenge)
+                        .run_if(not(bots_playing)),
+                ),
+            )
+            .add_systems(
+                RunEnd,
+                record_daily_attempt
+                    .before(remove_score)
+                    .run_if(not(bots_playing)),
+            );
+    }
+}
@@ -0,0 +1,276 @@
+use std::{collections::BTreeMap, fmt, path::Path, time::SystemTime};
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{DAILY_MODIFIER_COUNT, DAILY_RECORDS_PATH};
+use crate::game::{mode::GameMode, save::resources::SaveError};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Calendar date in UTC, so that everyone gets the same challenge on the same day.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DailyDate {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl DailyDate {
+    pub fn today() -> DailyDate {
+        let seconds = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        DailyDate::from_days_since_epoch((seconds / SECONDS_PER_DAY) as i64)
+    }
+
+    /// Converts days since 1970-01-01 to a date in the proleptic Gregorian calendar.
+    fn from_days_since_epoch(days: i64) -> DailyDate {
+        // Shift the epoch to 0000-03-01 so that leap days fall at the end of each year
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        DailyDate { year, month, day }
+    }
+
+    /// Seed shared by every player on this date.
+    pub fn seed(&self) -> u64 {
+        // SplitMix64 finalizer, so that consecutive days get unrelated seeds
+        let mut seed = (self.year as u64) << 16 | (self.month as u64) << 8 | self.day as u64;
+        seed = (seed ^ (seed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        seed = (seed ^ (seed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        seed ^ (seed >> 31)
+    }
+}
+
+impl fmt::Display for DailyDate {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{:04}-{:02}-{:02}",
+            self.year, self.month, self.day
+        )
+    }
+}
+
+/// Rule changes that make a daily challenge different from a regular endless run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    DoubleEnemySpeed,
+    OneHealth,
+    StarRain,
+    EnemySwarm,
+}
+
+impl Modifier {
+    pub const ALL: [Modifier; 4] = [
+        Modifier::DoubleEnemySpeed,
+        Modifier::OneHealth,
+        Modifier::StarRain,
+        Modifier::EnemySwarm,
+    ];
+
+    /// Localization key of the modifier's description.
+    pub fn key(self) -> &'static str {
+        match self {
+            Modifier::DoubleEnemySpeed => "modifier.double_enemy_speed",
+            Modifier::OneHealth => "modifier.one_health",
+            Modifier::StarRain => "modifier.star_rain",
+            Modifier::EnemySwarm => "modifier.enemy_swarm",
+        }
+    }
+}
+
+/// Seed and modifiers of the challenge of a day.
+#[derive(Resource, Debug, Clone)]
+pub struct DailyChallenge {
+    pub date: DailyDate,
+    pub seed: u64,
+    pub modifiers: Vec<Modifier>,
+}
+
+impl DailyChallenge {
+    pub fn for_date(date: DailyDate) -> DailyChallenge {
+        let seed = date.seed();
+        // The modifiers come from their own generator so they don't shift the run's random numbers
+        let mut rng = ChaCha8Rng::seed_from_u64(seed.rotate_left(32));
+        let mut modifiers: Vec<Modifier> = Modifier::ALL
+            .choose_multiple(&mut rng, DAILY_MODIFIER_COUNT)
+            .copied()
+            .collect();
+        modifiers.sort_by_key(|modifier| Modifier::ALL.iter().position(|other| other == modifier));
+
+        DailyChallenge {
+            date,
+            seed,
+            modifiers,
+        }
+    }
+}
+
+impl Default for DailyChallenge {
+    fn default() -> DailyChallenge {
+        DailyChallenge::for_date(DailyDate::today())
+    }
+}
+
+/// Modifiers of the current run, empty outside of daily challenges.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveModifiers {
+    pub modifiers: Vec<Modifier>,
+}
+
+impl ActiveModifiers {
+    pub fn contains(&self, modifier: Modifier) -> bool {
+        self.modifiers.contains(&modifier)
+    }
+
+    pub fn enemy_speed_factor(&self) -> f32 {
+        match self.contains(Modifier::DoubleEnemySpeed) {
+            true => 2.0,
+            false => 1.0,
+        }
+    }
+
+    pub fn enemy_spawn_time_factor(&self) -> f32 {
+        match self.contains(Modifier::EnemySwarm) {
+            true => 0.5,
+            false => 1.0,
+        }
+    }
+
+    pub fn star_spawn_time_factor(&self) -> f32 {
+        match self.contains(Modifier::StarRain) {
+            true => 0.25,
+            false => 1.0,
+        }
+    }
+
+    pub fn player_health(&self, health: u32) -> u32 {
+        match self.contains(Modifier::OneHealth) {
+            true => 1,
+            false => health,
+        }
+    }
+}
+
+/// Scores of the daily challenges played on this machine, keyed by date.
+#[derive(Resource, Debug)]
+pub struct DailyRecords {
+    pub scores: BTreeMap<String, u32>,
+}
+
+impl FromWorld for DailyRecords {
+    fn from_world(_world: &mut World) -> DailyRecords {
+        match DailyRecords::load(Path::new(DAILY_RECORDS_PATH)) {
+            Ok(daily_records) => daily_records,
+            Err(SaveError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+                DailyRecords {
+                    scores: BTreeMap::new(),
+                }
+            }
+            Err(error) => {
+                error!(target: "daily", "Could not load daily records: {error}");
+                DailyRecords {
+                    scores: BTreeMap::new(),
+                }
+            }
+        }
+    }
+}
+
+impl DailyRecords {
+    pub fn load(path: &Path) -> Result<DailyRecords, SaveError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(DailyRecords {
+            scores: ron::from_str(&contents)?,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        let contents = ron::ser::to_string_pretty(&self.scores, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn score(&self, date: &DailyDate) -> Option<u32> {
+        self.scores.get(&date.to_string()).copied()
+    }
+
+    /// Whether starting a run in the given mode is blocked because today's challenge was played.
+    pub fn is_locked(&self, mode: GameMode, daily_challenge: &DailyChallenge) -> bool {
+        mode == GameMode::DailyChallenge && self.score(&daily_challenge.date).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i64, month: u32, day: u32) -> DailyDate {
+        DailyDate { year, month, day }
+    }
+
+    #[test]
+    fn converts_days_since_epoch_to_dates() {
+        assert_eq!(DailyDate::from_days_since_epoch(0), date(1970, 1, 1));
+        assert_eq!(DailyDate::from_days_since_epoch(-1), date(1969, 12, 31));
+        assert_eq!(DailyDate::from_days_since_epoch(20_088), date(2024, 12, 31));
+        assert_eq!(DailyDate::from_days_since_epoch(-25_508), date(1900, 3, 1));
+    }
+
+    #[test]
+    fn handles_leap_days() {
+        assert_eq!(DailyDate::from_days_since_epoch(11_016), date(2000, 2, 29));
+        assert_eq!(DailyDate::from_days_since_epoch(11_017), date(2000, 3, 1));
+        // 2100 is divisible by 100 but not by 400, so it has no leap day
+        assert_eq!(DailyDate::from_days_since_epoch(47_540), date(2100, 2, 28));
+        assert_eq!(DailyDate::from_days_since_epoch(47_541), date(2100, 3, 1));
+    }
+
+    #[test]
+    fn formats_dates_with_padding() {
+        assert_eq!(date(2024, 3, 7).to_string(), "2024-03-07");
+    }
+
+    #[test]
+    fn seeds_are_stable_and_differ_between_days() {
+        // Every machine has to derive the same seed for the same day
+        assert_eq!(date(2024, 12, 31).seed(), 0xa915_0a52_8d33_b2bf);
+        assert_ne!(date(2024, 12, 31).seed(), date(2025, 1, 1).seed());
+        assert_ne!(date(2024, 12, 31).seed(), date(2024, 12, 30).seed());
+    }
+
+    #[test]
+    fn challenges_pick_distinct_modifiers_in_a_fixed_order() {
+        let challenge = DailyChallenge::for_date(date(2024, 12, 31));
+        assert_eq!(challenge.seed, date(2024, 12, 31).seed());
+        assert_eq!(challenge.modifiers.len(), DAILY_MODIFIER_COUNT);
+        assert!(challenge.modifiers.windows(2).all(|pair| {
+            let position = |modifier| Modifier::ALL.iter().position(|other| *other == modifier);
+            position(pair[0]) < position(pair[1])
+        }));
+        assert_eq!(
+            challenge.modifiers,
+            DailyChallenge::for_date(date(2024, 12, 31)).modifiers
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use super::resources::*;
+use super::DAILY_RECORDS_PATH;
+use crate::game::{
+    mode::GameMode, resources::RunSettings, save::resources::PendingRestore,
+    score::resources::Score,
+};
+
+pub fn refresh_daily_challenge(mut daily_challenge: ResMut<DailyChallenge>) {
+    let today = DailyDate::today();
+    if daily_challenge.date != today {
+        *daily_challenge = DailyChallenge::for_date(today);
+    }
+}
+
+pub fn apply_daily_challenge(
+    mut active_modifiers: ResMut<ActiveModifiers>,
+    mut daily_challenge: ResMut<DailyChallenge>,
+    run_settings: Res<RunSettings>,
+    pending_restore: Option<Res<PendingRestore>>,
+) {
+    if run_settings.mode != GameMode::DailyChallenge {
+        active_modifiers.modifiers.clear();
+        return;
+    }
+
+    // A restored run keeps the challenge it was started with
+    if let Some(pending_restore) = pending_restore {
+        if let Some(date) = pending_restore.snapshot.daily_date {
+            *daily_challenge = DailyChallenge::for_date(date);
+        }
+    }
+    active_modifiers.modifiers = daily_challenge.modifiers.clone();
+    info!(
+        target: "daily",
+        "Daily challenge of {} with {:?}",
+        daily_challenge.date,
+        daily_challenge.modifiers
+    );
+}
+
+/// Uses up today's attempt as soon as the run starts, so that restarting or quitting can't retry it.
+pub fn start_daily_attempt(
+    mut daily_records: ResMut<DailyRecords>,
+    daily_challenge: Res<DailyChallenge>,
+    run_settings: Res<RunSettings>,
+) {
+    if run_settings.mode != GameMode::DailyChallenge {
+        return;
+    }
+
+    // A restored run continues the attempt it was started with
+    let date = daily_challenge.date.to_string();
+    if daily_records.scores.contains_key(&date) {
+        return;
+    }
+    daily_records.scores.insert(date, 0);
+    save_daily_records(&daily_records, &daily_challenge);
+}
+
+/// Scores the attempt with the stars collected so far, also when the run was abandoned.
+pub fn record_daily_attempt(
+    mut daily_records: ResMut<DailyRecords>,
+    daily_challenge: Res<DailyChallenge>,
+    run_settings: Res<RunSettings>,
+    score: Option<Res<Score>>,
+) {
+    if run_settings.mode != GameMode::DailyChallenge {
+        return;
+    }
+    let Some(score) = score else {
+        return;
+    };
+
+    daily_records
+        .scores
+        .insert(daily_challenge.date.to_string(), score.value);
+    save_daily_records(&daily_records, &daily_challenge);
+}
+
+fn save_daily_records(daily_records: &DailyRecords, daily_challenge: &DailyChallenge) {
+    match daily_records.save(Path::new(DAILY_RECORDS_PATH)) {
+        Ok(()) => info!(target: "daily", "Recorded daily challenge of {}", daily_challenge.date),
+        Err(error) => error!(target: "daily", "Could not save daily records: {error}"),
+    }
+}
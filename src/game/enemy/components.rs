@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
+use crate::game::run::components::RunScoped;
+
 #[derive(Component)]
+#[require(RunScoped)]
 pub struct Enemy {
     pub direction: Vec3,
 }
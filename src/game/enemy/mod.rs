@@ -1,5 +1,5 @@
 pub mod components;
-mod resources;
+pub mod resources;
 mod systems;
 
 use bevy::prelude::*;
@@ -7,41 +7,62 @@ use bevy::prelude::*;
 use resources::*;
 use systems::*;
 
-use super::SimulationState;
-use crate::AppState;
+pub use systems::{confine_enemy_movement, create_enemy_bundle, spawn_enemies_over_time};
+
+use super::daily::apply_daily_challenge;
+use super::run::RunStart;
+use super::save::resources::PendingRestore;
+use super::simulation::SimulationStep;
+use super::spawning::{update_telegraphs, SpawnPlacement};
+use super::star::spawn_stars_over_time;
+use super::systems::roll_run_seed;
 
 /// Size of the enemy sprite in pixels.
 pub const ENEMY_SIZE: f32 = 64.0;
 pub const INITIAL_NUMBER_OF_ENEMIES: usize = 4;
-const ENEMY_SPEED: f32 = 200.0;
-const ENEMY_TIMESTEP: f64 = 1.0;
+pub const ENEMY_SPEED: f32 = 200.0;
+/// Seconds between two random direction changes of the enemies.
+const ENEMY_REDIRECTION_INTERVAL: f32 = 1.0;
 pub const ENEMY_SPRITE: &str = "sprites/asteroid.png";
+/// Where enemies appear once the run is underway.
+const ENEMY_SPAWN_PLACEMENT: SpawnPlacement = SpawnPlacement::Edge;
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EnemySpawnTimer>()
-            .insert_resource(Time::from_seconds(ENEMY_TIMESTEP))
-            .add_systems(OnEnter(AppState::Game), spawn_enemies)
+            .init_resource::<EnemyRedirectionTimer>()
             .add_systems(
-                Update,
+                RunStart,
                 (
-                    enemy_movement,
-                    confine_enemy_movement.after(enemy_movement),
-                    tick_enemy_spawn_timer,
-                    spawn_enemies_over_time,
-                )
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
+                    // Restoring a saved game continues the reset timer
+                    (reset_enemy_spawn_timer, reset_enemy_redirection_timer)
+                        .after(apply_daily_challenge)
+                        .before(roll_run_seed),
+                    spawn_enemies
+                        .after(roll_run_seed)
+                        .run_if(not(resource_exists::<PendingRestore>)),
+                ),
             )
             .add_systems(
-                FixedUpdate,
-                enemy_redirection
-                    .before(confine_enemy_movement)
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
+                SimulationStep,
+                (
+                    (
+                        tick_enemy_redirection_timer,
+                        enemy_redirection,
+                        enemy_movement,
+                        confine_enemy_movement,
+                    )
+                        .chain()
+                        .after(update_telegraphs),
+                    // New enemies keep their distance from the stars spawned in the same step
+                    (tick_enemy_spawn_timer, spawn_enemies_over_time)
+                        .chain()
+                        .after(spawn_stars_over_time),
+                ),
             )
-            .add_systems(OnExit(AppState::Game), despawn_enemies);
+            // Sounds follow the simulation's events, so that rollbacks can hold them back
+            .add_systems(Update, play_bounce_sound);
     }
 }
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
-const ENEMY_SPAWN_TIME: f32 = 5.0;
+use super::ENEMY_REDIRECTION_INTERVAL;
+
+pub const ENEMY_SPAWN_TIME: f32 = 5.0;
 
 #[derive(Resource)]
 pub struct EnemySpawnTimer {
@@ -14,3 +16,17 @@ impl Default for EnemySpawnTimer {
         }
     }
 }
+
+/// Enemies pick a new random direction whenever it finishes.
+#[derive(Resource)]
+pub struct EnemyRedirectionTimer {
+    pub timer: Timer,
+}
+
+impl Default for EnemyRedirectionTimer {
+    fn default() -> EnemyRedirectionTimer {
+        EnemyRedirectionTimer {
+            timer: Timer::from_seconds(ENEMY_REDIRECTION_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
@@ -1,21 +1,28 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 use rand::seq::SliceRandom;
-use rand::Rng;
 
 use super::components::Enemy;
-use super::resources::EnemySpawnTimer;
-use super::ENEMY_SPRITE;
-use super::{ENEMY_SIZE, ENEMY_SPEED, INITIAL_NUMBER_OF_ENEMIES};
-use crate::utils;
-
-fn create_enemy_bundle(
-    asset_server: &Res<AssetServer>,
+use super::resources::{EnemyRedirectionTimer, EnemySpawnTimer, ENEMY_SPAWN_TIME};
+use super::{ENEMY_SIZE, ENEMY_SPAWN_PLACEMENT, ENEMY_SPEED, INITIAL_NUMBER_OF_ENEMIES};
+use crate::arena::resources::Arena;
+use crate::events::EnemyBounced;
+use crate::game::daily::resources::ActiveModifiers;
+use crate::game::resources::{GameRng, RunSettings};
+use crate::game::simulation::components::SimulationId;
+use crate::game::spawning::{
+    components::{EnteringArena, Telegraphing},
+    SpawnPlacement, SpawnPlacer,
+};
+use crate::loading::resources::GameAssets;
+
+pub fn create_enemy_bundle(
+    game_assets: &GameAssets,
     x_position: f32,
     y_position: f32,
 ) -> impl Bundle {
     (
         // TODO: Check whether asteroid size fits the collision size of the enemies
-        Sprite::from_image(asset_server.load(ENEMY_SPRITE)),
+        Sprite::from_image(game_assets.enemy_sprite.clone()),
         Transform::from_xyz(x_position, y_position, 0.0),
         Enemy {
             direction: Vec3::ZERO,
@@ -25,59 +32,77 @@ fn create_enemy_bundle(
 
 pub fn spawn_enemies(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    mut spawn_placer: SpawnPlacer,
+    run_settings: Res<RunSettings>,
 ) {
-    let window: &Window = window_query.get_single().unwrap();
-    println!("{}", window.resolution.scale_factor());
-    let [x_min, x_max, y_min, y_max] = utils::get_confinement(window, ENEMY_SIZE);
-    let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
+    let number_of_enemies = (INITIAL_NUMBER_OF_ENEMIES as f32
+        * run_settings.mode.initial_enemy_factor())
+    .round() as usize;
+    let positions = spawn_placer.positions(number_of_enemies, ENEMY_SIZE, SpawnPlacement::Anywhere);
+
+    for position in positions {
+        commands.spawn((
+            create_enemy_bundle(&game_assets, position.x, position.y),
+            Telegraphing::default(),
+        ));
+    }
+}
 
-    for _ in 0..INITIAL_NUMBER_OF_ENEMIES {
-        let x_position: f32 = rng.gen_range(x_min..=x_max);
-        let y_position: f32 = rng.gen_range(y_min..=y_max);
+pub fn reset_enemy_redirection_timer(mut enemy_redirection_timer: ResMut<EnemyRedirectionTimer>) {
+    *enemy_redirection_timer = EnemyRedirectionTimer::default();
+}
 
-        commands.spawn(create_enemy_bundle(&asset_server, x_position, y_position));
-    }
+pub fn tick_enemy_redirection_timer(
+    mut enemy_redirection_timer: ResMut<EnemyRedirectionTimer>,
+    time: Res<Time>,
+) {
+    enemy_redirection_timer.timer.tick(time.delta());
 }
 
-pub fn despawn_enemies(mut commands: Commands, enemy_query: Query<Entity, With<Enemy>>) {
-    for enemy_entity in &enemy_query {
-        commands.entity(enemy_entity).despawn();
+pub fn enemy_redirection(
+    mut enemy_query: Query<(&mut Enemy, &SimulationId), Without<Telegraphing>>,
+    enemy_redirection_timer: Res<EnemyRedirectionTimer>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    if !enemy_redirection_timer.timer.finished() {
+        return;
     }
-}
 
-pub fn enemy_redirection(mut enemy_query: Query<&mut Enemy>) {
     let sample_directions: [f32; 3] = [-1.0, 0.0, 1.0];
-    let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
+    let rng = &mut game_rng.rng;
 
-    for mut enemy in &mut enemy_query {
+    // The random directions are drawn in the same order on every machine
+    for (mut enemy, _) in enemy_query.iter_mut().sort::<&SimulationId>() {
         let mut direction = Vec3::ZERO;
         let x_random: &f32 = sample_directions
-            .choose(&mut rng)
+            .choose(rng)
             .expect("Random x direction should have been generated.");
         let y_random: &f32 = sample_directions
-            .choose(&mut rng)
+            .choose(rng)
             .expect("Random y direction should have been generated.");
         direction += Vec3::new(*x_random, *y_random, 0.0);
         enemy.direction = direction.normalize_or_zero();
     }
 }
 
-pub fn enemy_movement(mut enemy_query: Query<(&mut Transform, &Enemy)>, time: Res<Time>) {
+pub fn enemy_movement(
+    mut enemy_query: Query<(&mut Transform, &Enemy), Without<Telegraphing>>,
+    active_modifiers: Res<ActiveModifiers>,
+    time: Res<Time>,
+) {
+    let speed = ENEMY_SPEED * active_modifiers.enemy_speed_factor();
     for (mut enemy_transform, enemy) in &mut enemy_query {
-        enemy_transform.translation += enemy.direction * ENEMY_SPEED * time.delta_secs();
+        enemy_transform.translation += enemy.direction * speed * time.delta_secs();
     }
 }
 
 pub fn confine_enemy_movement(
-    mut commands: Commands,
-    mut enemy_query: Query<(&mut Transform, &mut Enemy)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
+    mut enemy_query: Query<(&mut Transform, &mut Enemy), Without<Telegraphing>>,
+    arena: Res<Arena>,
+    mut enemy_bounced_event_writer: EventWriter<EnemyBounced>,
 ) {
-    let window: &Window = window_query.get_single().unwrap();
-    let [x_min, x_max, y_min, y_max] = utils::get_confinement(window, ENEMY_SIZE);
+    let [x_min, x_max, y_min, y_max] = arena.confinement(ENEMY_SIZE);
 
     for (mut enemy_transform, mut enemy) in &mut enemy_query {
         let mut changed_direction: bool = false;
@@ -102,34 +127,66 @@ pub fn confine_enemy_movement(
         }
 
         if changed_direction {
-            commands.spawn((
-                AudioPlayer::<AudioSource>(asset_server.load("audio/pluck_001.ogg")),
-                PlaybackSettings::DESPAWN,
-            ));
+            enemy_bounced_event_writer.send(EnemyBounced);
         }
     }
 }
 
+pub fn play_bounce_sound(
+    mut commands: Commands,
+    mut enemy_bounced_event_reader: EventReader<EnemyBounced>,
+    game_assets: Res<GameAssets>,
+) {
+    for _event in enemy_bounced_event_reader.read() {
+        commands.spawn((
+            AudioPlayer(game_assets.bounce_sound.clone()),
+            PlaybackSettings::DESPAWN,
+        ));
+    }
+}
+
+pub fn reset_enemy_spawn_timer(
+    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+    run_settings: Res<RunSettings>,
+    active_modifiers: Res<ActiveModifiers>,
+) {
+    enemy_spawn_timer.timer = Timer::from_seconds(
+        ENEMY_SPAWN_TIME
+            * run_settings.mode.enemy_spawn_time_factor()
+            * active_modifiers.enemy_spawn_time_factor(),
+        TimerMode::Repeating,
+    );
+}
+
 pub fn tick_enemy_spawn_timer(mut enemy_spawn_timer: ResMut<EnemySpawnTimer>, time: Res<Time>) {
     enemy_spawn_timer.timer.tick(time.delta());
 }
 
 pub fn spawn_enemies_over_time(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
     enemy_spawn_timer: Res<EnemySpawnTimer>,
+    mut spawn_placer: SpawnPlacer,
 ) {
     if !enemy_spawn_timer.timer.finished() {
         return;
     }
 
-    let window: &Window = window_query.get_single().unwrap();
-    let [x_min, x_max, y_min, y_max] = utils::get_confinement(window, ENEMY_SIZE);
-    let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
-
-    let x_position: f32 = rng.gen_range(x_min..=x_max);
-    let y_position: f32 = rng.gen_range(y_min..=y_max);
-
-    commands.spawn(create_enemy_bundle(&asset_server, x_position, y_position));
+    let position = spawn_placer.position(ENEMY_SIZE, ENEMY_SPAWN_PLACEMENT);
+    let entering_arena = (ENEMY_SPAWN_PLACEMENT == SpawnPlacement::Edge).then(|| EnteringArena {
+        from: spawn_placer.entry_position(position, ENEMY_SIZE),
+        to: position,
+    });
+    let start_position = entering_arena
+        .as_ref()
+        .map(|entering_arena| entering_arena.from)
+        .unwrap_or(position);
+
+    let mut enemy = commands.spawn((
+        create_enemy_bundle(&game_assets, start_position.x, start_position.y),
+        Telegraphing::default(),
+    ));
+    if let Some(entering_arena) = entering_arena {
+        enemy.insert(entering_arena);
+    }
 }
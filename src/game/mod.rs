@@ -1,36 +1,102 @@
-mod components;
-mod enemy;
+pub mod bot;
+pub mod components;
+pub mod daily;
+pub mod enemy;
+pub mod mode;
+pub mod particles;
 pub mod player;
-mod score;
-mod star;
-mod systems;
+pub mod resources;
+pub mod run;
+pub mod save;
+pub mod score;
+pub mod simulation;
+pub mod spawning;
+pub mod star;
+mod starfield;
+pub mod systems;
 mod ui;
+pub mod versus;
 
+use bot::BotPlugin;
+use daily::DailyChallengePlugin;
 use enemy::EnemyPlugin;
+use mode::GameModePlugin;
+use particles::ParticlePlugin;
 use player::PlayerPlugin;
+use run::{RunLifecyclePlugin, RunStart};
+use save::SavePlugin;
 use score::ScorePlugin;
+use simulation::SimulationPlugin;
+use spawning::SpawningPlugin;
 use star::StarPlugin;
+use starfield::StarfieldPlugin;
+use versus::VersusPlugin;
 
 use bevy::prelude::*;
 
-use crate::{events::GameOver, AppState};
+use crate::{
+    arena::resources::Arena,
+    events::{AsteroidImpact, EnemyBounced, GameOver, PlayerOut, StarCollected},
+    network::resources::{HostSession, RollbackSession},
+    settings::resources::Settings,
+    AppState,
+};
 
-use self::{systems::toggle_simulation, ui::GameUIPlugin};
+use self::{
+    resources::{GameRng, RunSeed, RunSettings},
+    systems::{
+        apply_color_palette, auto_pause_simulation, draw_high_contrast_outlines,
+        keep_entities_in_arena, roll_run_seed, toggle_simulation,
+    },
+    ui::GameUIPlugin,
+};
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<SimulationState>()
+            .add_sub_state::<PauseMenuView>()
+            .init_resource::<RunSeed>()
+            .init_resource::<RunSettings>()
+            .init_resource::<GameRng>()
             .add_event::<GameOver>()
+            .add_event::<PlayerOut>()
+            .add_event::<StarCollected>()
+            .add_event::<AsteroidImpact>()
+            .add_event::<EnemyBounced>()
             .add_plugins((
+                BotPlugin,
+                DailyChallengePlugin,
                 EnemyPlugin,
+                GameModePlugin,
+                ParticlePlugin,
                 PlayerPlugin,
+                RunLifecyclePlugin,
+                SavePlugin,
                 ScorePlugin,
+                SimulationPlugin,
+                SpawningPlugin,
                 StarPlugin,
+                StarfieldPlugin,
+                VersusPlugin,
                 GameUIPlugin,
             ))
-            .add_systems(Update, toggle_simulation.run_if(in_state(AppState::Game)));
+            .add_systems(RunStart, roll_run_seed)
+            .add_systems(
+                Update,
+                (
+                    // Peers and clients keep playing, so online runs can't be paused
+                    (toggle_simulation, auto_pause_simulation)
+                        .run_if(not(resource_exists::<RollbackSession>))
+                        .run_if(not(resource_exists::<HostSession>)),
+                    keep_entities_in_arena.run_if(resource_changed::<Arena>),
+                    apply_color_palette,
+                    draw_high_contrast_outlines
+                        .run_if(|settings: Res<Settings>| settings.high_contrast),
+                )
+                    .run_if(in_state(AppState::Game)),
+            );
     }
 }
 
@@ -40,3 +106,12 @@ pub enum SimulationState {
     Paused,
     Running,
 }
+
+/// Which part of the pause menu is shown while the simulation is paused.
+#[derive(SubStates, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[source(SimulationState = SimulationState::Paused)]
+pub enum PauseMenuView {
+    #[default]
+    Menu,
+    ConfirmRestart,
+}
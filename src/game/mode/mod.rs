@@ -0,0 +1,44 @@
+pub mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::*;
+use systems::*;
+
+pub use resources::GameMode;
+pub use systems::insert_time_limit;
+
+use super::enemy::spawn_enemies_over_time;
+use super::run::{RunEnd, RunStart};
+use super::score::resources::Score;
+use super::simulation::SimulationStep;
+use crate::AppState;
+
+/// Seconds a time attack run lasts.
+pub const TIME_ATTACK_DURATION: f32 = 120.0;
+/// How much longer enemies take to spawn in zen mode.
+pub const ZEN_SPAWN_TIME_FACTOR: f32 = 2.0;
+
+/// Applies the rules of the selected [`GameMode`] that aren't covered by the gameplay plugins.
+pub struct GameModePlugin;
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(RunStart, insert_time_limit)
+            .add_systems(
+                SimulationStep,
+                // The time is up after everything else of the step happened
+                tick_time_limit
+                    .after(spawn_enemies_over_time)
+                    .run_if(resource_exists::<TimeLimit>),
+            )
+            .add_systems(
+                Update,
+                finish_run
+                    .run_if(in_state(AppState::Game))
+                    .run_if(resource_exists::<Score>),
+            )
+            .add_systems(RunEnd, remove_time_limit);
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::{TIME_ATTACK_DURATION, ZEN_SPAWN_TIME_FACTOR};
+use crate::game::versus::VERSUS_ROUND_DURATION;
+
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GameMode {
+    /// Survive as long as possible while collecting stars.
+    #[default]
+    Endless,
+    /// Collect as many stars as possible before the time runs out.
+    TimeAttack,
+    /// Collect stars without taking damage and with fewer enemies.
+    Zen,
+    /// Endless run with a seed and modifiers that change every day.
+    DailyChallenge,
+    /// Two players race for stars over a match of timed rounds.
+    Versus,
+}
+
+impl GameMode {
+    pub fn next(self) -> GameMode {
+        match self {
+            GameMode::Endless => GameMode::TimeAttack,
+            GameMode::TimeAttack => GameMode::Zen,
+            GameMode::Zen => GameMode::DailyChallenge,
+            GameMode::DailyChallenge => GameMode::Versus,
+            GameMode::Versus => GameMode::Endless,
+        }
+    }
+
+    /// Localization key of the main menu's mode selection label.
+    pub fn key(self) -> &'static str {
+        match self {
+            GameMode::Endless => "main_menu.mode.endless",
+            GameMode::TimeAttack => "main_menu.mode.time_attack",
+            GameMode::Zen => "main_menu.mode.zen",
+            GameMode::DailyChallenge => "main_menu.mode.daily_challenge",
+            GameMode::Versus => "main_menu.mode.versus",
+        }
+    }
+
+    /// Seconds after which the run ends, if the mode is timed.
+    pub fn time_limit(self) -> Option<f32> {
+        match self {
+            GameMode::TimeAttack => Some(TIME_ATTACK_DURATION),
+            GameMode::Versus => Some(VERSUS_ROUND_DURATION),
+            GameMode::Endless | GameMode::Zen | GameMode::DailyChallenge => None,
+        }
+    }
+
+    pub fn deals_damage(self) -> bool {
+        self != GameMode::Zen
+    }
+
+    /// Zen runs never end on their own, so they're finished from the pause menu instead.
+    pub fn is_finished_by_player(self) -> bool {
+        self == GameMode::Zen
+    }
+
+    /// Versus rounds are won against the other player instead of counting towards the high scores.
+    pub fn has_high_scores(self) -> bool {
+        self != GameMode::Versus
+    }
+
+    /// Factor applied to the time between enemy spawns.
+    pub fn enemy_spawn_time_factor(self) -> f32 {
+        match self {
+            GameMode::Zen => ZEN_SPAWN_TIME_FACTOR,
+            GameMode::Endless
+            | GameMode::TimeAttack
+            | GameMode::DailyChallenge
+            | GameMode::Versus => 1.0,
+        }
+    }
+
+    /// Factor applied to the number of enemies at the start of a run.
+    pub fn initial_enemy_factor(self) -> f32 {
+        match self {
+            GameMode::Zen => 0.5,
+            GameMode::Endless
+            | GameMode::TimeAttack
+            | GameMode::DailyChallenge
+            | GameMode::Versus => 1.0,
+        }
+    }
+}
+
+/// Countdown of a timed run.
+#[derive(Resource, Debug)]
+pub struct TimeLimit {
+    pub timer: Timer,
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use super::resources::TimeLimit;
+use crate::events::{GameOver, MenuButtonPressed};
+use crate::game::resources::RunSettings;
+use crate::game::score::resources::Score;
+use crate::widgets::components::ButtonAction;
+use crate::AppState;
+
+pub fn insert_time_limit(mut commands: Commands, run_settings: Res<RunSettings>) {
+    if let Some(time_limit) = run_settings.mode.time_limit() {
+        commands.insert_resource(TimeLimit {
+            timer: Timer::from_seconds(time_limit, TimerMode::Once),
+        });
+    }
+}
+
+pub fn remove_time_limit(mut commands: Commands) {
+    commands.remove_resource::<TimeLimit>();
+}
+
+pub fn tick_time_limit(
+    mut time_limit: ResMut<TimeLimit>,
+    mut game_over_event_writer: EventWriter<GameOver>,
+    score: Res<Score>,
+    time: Res<Time>,
+) {
+    time_limit.timer.tick(time.delta());
+
+    if time_limit.timer.just_finished() {
+        info!(target: "mode", "Time is up!");
+        game_over_event_writer.send(GameOver { score: score.value });
+    }
+}
+
+/// Ends the run like a game over, so that its score is recorded.
+pub fn finish_run(
+    mut menu_button_event_reader: EventReader<MenuButtonPressed>,
+    mut game_over_event_writer: EventWriter<GameOver>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    score: Res<Score>,
+) {
+    for event in menu_button_event_reader.read() {
+        if event.action == ButtonAction::FinishRun {
+            info!(target: "mode", "Finished the run.");
+            game_over_event_writer.send(GameOver { score: score.value });
+            next_app_state.set(AppState::GameOver);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+/// Describes how the particles of an effect look and behave over their lifetime.
+#[derive(Clone, Copy, Debug)]
+pub struct ParticleEffect {
+    pub lifetime: f32,
+    pub speed: f32,
+    /// Maximum deviation of the emission direction in radians.
+    pub spread: f32,
+    pub start_color: Color,
+    pub end_color: Color,
+    pub start_size: f32,
+    pub end_size: f32,
+}
+
+pub const STAR_SPARKLE: ParticleEffect = ParticleEffect {
+    lifetime: 0.5,
+    speed: 180.0,
+    spread: std::f32::consts::PI,
+    start_color: Color::srgba(1.0, 0.95, 0.5, 1.0),
+    end_color: Color::srgba(1.0, 0.6, 0.1, 0.0),
+    start_size: 6.0,
+    end_size: 1.0,
+};
+
+pub const ASTEROID_DEBRIS: ParticleEffect = ParticleEffect {
+    lifetime: 0.8,
+    speed: 140.0,
+    spread: std::f32::consts::PI,
+    start_color: Color::srgba(0.6, 0.5, 0.45, 1.0),
+    end_color: Color::srgba(0.3, 0.25, 0.2, 0.0),
+    start_size: 7.0,
+    end_size: 2.0,
+};
+
+pub const THRUSTER_TRAIL: ParticleEffect = ParticleEffect {
+    lifetime: 0.35,
+    speed: 120.0,
+    spread: 0.3,
+    start_color: Color::srgba(0.4, 0.8, 1.0, 0.9),
+    end_color: Color::srgba(1.0, 0.3, 0.1, 0.0),
+    start_size: 8.0,
+    end_size: 2.0,
+};
+
+/// Continuously emits particles opposite to the movement of its entity while the entity moves.
+#[derive(Component)]
+pub struct ParticleEmitter {
+    pub effect: ParticleEffect,
+    /// Emitted particles per second.
+    pub rate: f32,
+    /// Distance from the entity center at which particles are emitted.
+    pub offset: f32,
+    pub accumulator: f32,
+    pub last_position: Option<Vec3>,
+}
+
+impl ParticleEmitter {
+    pub fn new(effect: ParticleEffect, rate: f32, offset: f32) -> ParticleEmitter {
+        ParticleEmitter {
+            effect,
+            rate,
+            offset,
+            accumulator: 0.0,
+            last_position: None,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec3,
+    pub age: f32,
+    pub effect: ParticleEffect,
+}
@@ -0,0 +1,40 @@
+pub mod components;
+mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::*;
+use systems::*;
+
+use super::run::RunEnd;
+use super::SimulationState;
+use crate::{settings::resources::Settings, AppState};
+
+/// Upper bound for the number of particle entities, both alive and pooled.
+const MAX_PARTICLES: usize = 512;
+const PARTICLE_Z: f32 = -0.5;
+const STAR_SPARKLE_COUNT: usize = 16;
+const ASTEROID_DEBRIS_COUNT: usize = 24;
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParticlePool>()
+            .add_systems(
+                Update,
+                (
+                    spawn_star_sparkles,
+                    spawn_asteroid_debris,
+                    emit_particles,
+                    update_particles,
+                )
+                    .chain()
+                    .run_if(|settings: Res<Settings>| !settings.reduced_motion)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+            )
+            .add_systems(RunEnd, despawn_particles);
+    }
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+/// Particle entities that finished their lifetime and can be reused.
+#[derive(Resource, Default, Debug)]
+pub struct ParticlePool {
+    pub free: Vec<Entity>,
+    pub total: usize,
+}
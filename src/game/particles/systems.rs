@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use super::components::*;
+use super::resources::ParticlePool;
+use super::{ASTEROID_DEBRIS_COUNT, MAX_PARTICLES, PARTICLE_Z, STAR_SPARKLE_COUNT};
+use crate::events::{AsteroidImpact, StarCollected};
+
+type PooledParticle<'a> = (&'a mut Particle, &'a mut Transform, &'a mut Visibility);
+
+fn emit_particle(
+    commands: &mut Commands,
+    particle_pool: &mut ParticlePool,
+    particle_query: &mut Query<PooledParticle, Without<ParticleEmitter>>,
+    position: Vec3,
+    direction: Vec3,
+    effect: ParticleEffect,
+) {
+    let mut rng = rand::thread_rng();
+    let angle = rng.gen_range(-effect.spread..=effect.spread);
+    let velocity = Quat::from_rotation_z(angle)
+        * direction.normalize_or_zero()
+        * effect.speed
+        * rng.gen_range(0.5..=1.0);
+    let translation = Vec3::new(position.x, position.y, PARTICLE_Z);
+    let particle = Particle {
+        velocity,
+        age: 0.0,
+        effect,
+    };
+
+    if let Some(particle_entity) = particle_pool.free.pop() {
+        if let Ok((mut pooled_particle, mut transform, mut visibility)) =
+            particle_query.get_mut(particle_entity)
+        {
+            *pooled_particle = particle;
+            transform.translation = translation;
+            *visibility = Visibility::Inherited;
+            return;
+        }
+    }
+
+    if particle_pool.total >= MAX_PARTICLES {
+        return;
+    }
+    particle_pool.total += 1;
+    commands.spawn((
+        Sprite {
+            color: effect.start_color,
+            custom_size: Some(Vec2::splat(effect.start_size)),
+            ..default()
+        },
+        Transform::from_translation(translation),
+        particle,
+    ));
+}
+
+fn emit_burst(
+    commands: &mut Commands,
+    particle_pool: &mut ParticlePool,
+    particle_query: &mut Query<PooledParticle, Without<ParticleEmitter>>,
+    position: Vec3,
+    count: usize,
+    effect: ParticleEffect,
+) {
+    for index in 0..count {
+        let angle = std::f32::consts::TAU * index as f32 / count as f32;
+        let direction = Vec3::new(angle.cos(), angle.sin(), 0.0);
+        emit_particle(
+            commands,
+            particle_pool,
+            particle_query,
+            position,
+            direction,
+            effect,
+        );
+    }
+}
+
+pub fn spawn_star_sparkles(
+    mut commands: Commands,
+    mut star_collected_event_reader: EventReader<StarCollected>,
+    mut particle_pool: ResMut<ParticlePool>,
+    mut particle_query: Query<PooledParticle, Without<ParticleEmitter>>,
+) {
+    for event in star_collected_event_reader.read() {
+        emit_burst(
+            &mut commands,
+            &mut particle_pool,
+            &mut particle_query,
+            event.position,
+            STAR_SPARKLE_COUNT,
+            STAR_SPARKLE,
+        );
+    }
+}
+
+pub fn spawn_asteroid_debris(
+    mut commands: Commands,
+    mut asteroid_impact_event_reader: EventReader<AsteroidImpact>,
+    mut particle_pool: ResMut<ParticlePool>,
+    mut particle_query: Query<PooledParticle, Without<ParticleEmitter>>,
+) {
+    for event in asteroid_impact_event_reader.read() {
+        emit_burst(
+            &mut commands,
+            &mut particle_pool,
+            &mut particle_query,
+            event.position,
+            ASTEROID_DEBRIS_COUNT,
+            ASTEROID_DEBRIS,
+        );
+    }
+}
+
+pub fn emit_particles(
+    mut commands: Commands,
+    mut emitter_query: Query<(&mut ParticleEmitter, &Transform)>,
+    mut particle_pool: ResMut<ParticlePool>,
+    mut particle_query: Query<PooledParticle, Without<ParticleEmitter>>,
+    time: Res<Time>,
+) {
+    for (mut emitter, emitter_transform) in &mut emitter_query {
+        let position = emitter_transform.translation;
+        let movement = position - emitter.last_position.unwrap_or(position);
+        emitter.last_position = Some(position);
+
+        if movement.length_squared() == 0.0 {
+            emitter.accumulator = 0.0;
+            continue;
+        }
+
+        let direction = -movement.normalize();
+        emitter.accumulator += emitter.rate * time.delta_secs();
+        while emitter.accumulator >= 1.0 {
+            emitter.accumulator -= 1.0;
+            emit_particle(
+                &mut commands,
+                &mut particle_pool,
+                &mut particle_query,
+                position + direction * emitter.offset,
+                direction,
+                emitter.effect,
+            );
+        }
+    }
+}
+
+pub fn update_particles(
+    mut particle_query: Query<(
+        Entity,
+        &mut Particle,
+        &mut Transform,
+        &mut Visibility,
+        &mut Sprite,
+    )>,
+    mut particle_pool: ResMut<ParticlePool>,
+    time: Res<Time>,
+) {
+    for (particle_entity, mut particle, mut transform, mut visibility, mut sprite) in
+        &mut particle_query
+    {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        particle.age += time.delta_secs();
+        if particle.age >= particle.effect.lifetime {
+            *visibility = Visibility::Hidden;
+            particle_pool.free.push(particle_entity);
+            continue;
+        }
+
+        let progress = particle.age / particle.effect.lifetime;
+        let effect = particle.effect;
+        transform.translation += particle.velocity * time.delta_secs();
+        sprite.color = effect.start_color.mix(&effect.end_color, progress);
+        sprite.custom_size = Some(Vec2::splat(
+            effect.start_size.lerp(effect.end_size, progress),
+        ));
+    }
+}
+
+pub fn despawn_particles(
+    mut commands: Commands,
+    particle_query: Query<Entity, With<Particle>>,
+    mut particle_pool: ResMut<ParticlePool>,
+) {
+    for particle_entity in &particle_query {
+        commands.entity(particle_entity).despawn();
+    }
+    *particle_pool = ParticlePool::default();
+}
@@ -1,4 +1,52 @@
+use std::fmt;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::run::components::RunScoped;
 
 #[derive(Component)]
+#[require(RunScoped, PlayerId, PlayerInput)]
 pub struct Player;
+
+/// Direction the player wants to move in, at most of length one.
+#[derive(Component, Debug, Default)]
+pub struct PlayerInput {
+    pub direction: Vec2,
+}
+
+/// Player whose input arrives over the network instead of from this machine.
+#[derive(Component)]
+pub struct RemoteControlled;
+
+/// Which of the players controls the ship, counted from zero.
+#[derive(
+    Component,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+pub struct PlayerId(pub usize);
+
+impl PlayerId {
+    pub const ONE: PlayerId = PlayerId(0);
+
+    /// Number shown to the players, counted from one.
+    pub fn number(self) -> usize {
+        self.0 + 1
+    }
+}
+
+impl fmt::Display for PlayerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Player {}", self.number())
+    }
+}
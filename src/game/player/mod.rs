@@ -1,32 +1,74 @@
 pub mod components;
+pub mod resources;
 mod systems;
 
 use bevy::prelude::*;
 
+use resources::PlayerBindings;
 use systems::*;
 
+pub use resources::Lives;
+pub use systems::{
+    check_player_health, confine_player_movement, create_player_bundle, handle_game_over,
+    player_hit_star, player_movement, read_local_input, PLAYER_SPEED,
+};
+
+use super::enemy::confine_enemy_movement;
+use super::run::RunStart;
+use super::save::resources::PendingRestore;
+use super::simulation::SimulationStep;
+use super::systems::roll_run_seed;
 use super::SimulationState;
 use crate::AppState;
 
 pub const INITIAL_PLAYER_HEALTH: u32 = 3;
+pub const PLAYER_SIZE: f32 = 64.0; // this is the size of the player sprite
 pub const PLAYER_SPRITE: &str = "sprites/spacecraft.png";
+/// Number of players in a run, including players that join over the network.
+pub const MAX_PLAYERS: usize = 4;
+/// Number of players that can share one keyboard in co-op and versus.
+pub const MAX_LOCAL_PLAYERS: usize = 2;
+/// Horizontal distance between the players when a multiplayer run starts.
+const PLAYER_SPACING: f32 = 2.0 * PLAYER_SIZE;
+/// Sprite tint of each player, so that they can be told apart in multiplayer.
+pub const PLAYER_TINTS: [Color; MAX_PLAYERS] = [
+    Color::WHITE,
+    Color::srgb(0.55, 0.85, 1.0),
+    Color::srgb(1.0, 0.7, 0.55),
+    Color::srgb(0.7, 1.0, 0.6),
+];
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Game), spawn_player)
+        app.init_resource::<PlayerBindings>()
             .add_systems(
-                Update,
+                RunStart,
+                spawn_players
+                    .after(roll_run_seed)
+                    .run_if(not(resource_exists::<PendingRestore>)),
+            )
+            .add_systems(
+                SimulationStep,
                 (
                     player_movement,
-                    confine_player_movement.after(player_movement),
+                    confine_player_movement,
                     player_hit_star,
-                    (player_hit_enemy, check_player_health, handle_game_over).chain(),
+                    player_hit_enemy,
+                    // Players are out at the same step on every machine
+                    check_player_health,
                 )
+                    .chain()
+                    .after(confine_enemy_movement),
+            )
+            // Sounds follow the simulation's events, so that rollbacks can hold them back
+            .add_systems(Update, (play_star_collected_sound, play_impact_sound))
+            .add_systems(
+                Update,
+                (read_local_input, handle_game_over)
                     .run_if(in_state(AppState::Game))
                     .run_if(in_state(SimulationState::Running)),
-            )
-            .add_systems(OnExit(AppState::Game), despawn_player);
+            );
     }
 }
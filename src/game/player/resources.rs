@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::components::PlayerId;
+use super::MAX_LOCAL_PLAYERS;
+
+/// How the health of the players is counted in co-op.
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Lives {
+    /// Every player has their own health and is out once it's gone.
+    #[default]
+    Separate,
+    /// All players draw from one health pool and are out together.
+    Shared,
+}
+
+/// Keys that move a single player.
+#[derive(Debug, Clone, Copy)]
+pub struct MovementKeys {
+    pub up: KeyCode,
+    pub left: KeyCode,
+    pub down: KeyCode,
+    pub right: KeyCode,
+}
+
+impl MovementKeys {
+    pub const WASD: MovementKeys = MovementKeys {
+        up: KeyCode::KeyW,
+        left: KeyCode::KeyA,
+        down: KeyCode::KeyS,
+        right: KeyCode::KeyD,
+    };
+    pub const ARROWS: MovementKeys = MovementKeys {
+        up: KeyCode::ArrowUp,
+        left: KeyCode::ArrowLeft,
+        down: KeyCode::ArrowDown,
+        right: KeyCode::ArrowRight,
+    };
+
+    pub fn direction(&self, keyboard_input: &ButtonInput<KeyCode>) -> Vec2 {
+        let mut direction = Vec2::ZERO;
+        if keyboard_input.pressed(self.left) {
+            direction.x -= 1.0;
+        }
+        if keyboard_input.pressed(self.right) {
+            direction.x += 1.0;
+        }
+        if keyboard_input.pressed(self.down) {
+            direction.y -= 1.0;
+        }
+        if keyboard_input.pressed(self.up) {
+            direction.y += 1.0;
+        }
+        direction
+    }
+}
+
+/// Direction of the left stick and the directional pad of a gamepad.
+pub fn gamepad_direction(gamepad: &Gamepad) -> Vec2 {
+    gamepad.left_stick() + gamepad.dpad()
+}
+
+/// Movement keys of each local player, gamepads are assigned to the players in the order they connected.
+#[derive(Resource, Debug)]
+pub struct PlayerBindings {
+    pub keys: [MovementKeys; MAX_LOCAL_PLAYERS],
+}
+
+impl Default for PlayerBindings {
+    fn default() -> PlayerBindings {
+        PlayerBindings {
+            keys: [MovementKeys::WASD, MovementKeys::ARROWS],
+        }
+    }
+}
+
+impl PlayerBindings {
+    pub fn keys(&self, player_id: PlayerId) -> Option<&MovementKeys> {
+        self.keys.get(player_id.0)
+    }
+
+    /// Direction of all bindings and gamepads together, for a single player on this machine.
+    pub fn combined_direction<'a>(
+        &self,
+        keyboard_input: &ButtonInput<KeyCode>,
+        gamepads: impl IntoIterator<Item = &'a Gamepad>,
+    ) -> Vec2 {
+        let keys_direction: Vec2 = self
+            .keys
+            .iter()
+            .map(|keys| keys.direction(keyboard_input))
+            .sum();
+        let gamepads_direction: Vec2 = gamepads.into_iter().map(gamepad_direction).sum();
+        (keys_direction + gamepads_direction).normalize_or_zero()
+    }
+}
@@ -1,79 +1,125 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 
-use super::components::Player;
-use super::{INITIAL_PLAYER_HEALTH, PLAYER_SPRITE};
-use crate::events::GameOver;
+use super::components::{Player, PlayerId, PlayerInput, RemoteControlled};
+use super::resources::{gamepad_direction, Lives, PlayerBindings};
+use super::{INITIAL_PLAYER_HEALTH, PLAYER_SIZE, PLAYER_SPACING, PLAYER_TINTS};
+use crate::arena::resources::Arena;
+use crate::console::GodMode;
+use crate::events::{AsteroidImpact, GameOver, PlayerOut, StarCollected};
+use crate::game::bot::components::Bot;
 use crate::game::components::Health;
+use crate::game::daily::resources::ActiveModifiers;
 use crate::game::enemy::components::Enemy;
 use crate::game::enemy::ENEMY_SIZE;
+use crate::game::particles::components::{ParticleEmitter, THRUSTER_TRAIL};
+use crate::game::resources::RunSettings;
 use crate::game::score::resources::Score;
+use crate::game::simulation::components::SimulationId;
+use crate::game::spawning::components::Telegraphing;
 use crate::game::star::components::Star;
 use crate::game::star::STAR_SIZE;
 use crate::game::SimulationState;
-use crate::{utils, AppState};
+use crate::loading::resources::GameAssets;
+use crate::AppState;
 
-pub const PLAYER_SIZE: f32 = 64.0; // this is the size of the player sprite
 pub const PLAYER_SPEED: f32 = 500.0;
 pub const COLLISION_REBOUND_STRENGTH: f32 = 50.0;
+const THRUSTER_PARTICLE_RATE: f32 = 60.0;
 
-pub fn spawn_player(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
-) {
-    let window: &Window = window_query.get_single().unwrap();
+type LocalPlayer = (With<Player>, Without<RemoteControlled>, Without<Bot>);
 
-    commands.spawn((
-        Sprite::from_image(asset_server.load(PLAYER_SPRITE)),
-        Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
-        Player,
-        Health {
-            current: INITIAL_PLAYER_HEALTH,
+pub fn create_player_bundle(
+    game_assets: &GameAssets,
+    player_id: PlayerId,
+    x_position: f32,
+    y_position: f32,
+    health: u32,
+) -> impl Bundle {
+    (
+        Sprite {
+            color: PLAYER_TINTS[player_id.0],
+            ..Sprite::from_image(game_assets.player_sprite.clone())
         },
-    ));
+        Transform::from_xyz(x_position, y_position, 0.0),
+        Player,
+        player_id,
+        Health { current: health },
+        ParticleEmitter::new(THRUSTER_TRAIL, THRUSTER_PARTICLE_RATE, PLAYER_SIZE / 2.0),
+    )
 }
 
-pub fn despawn_player(mut commands: Commands, player_query: Query<Entity, With<Player>>) {
-    for player_entity in &player_query {
-        commands.entity(player_entity).despawn();
+pub fn spawn_players(
+    mut commands: Commands,
+    arena: Res<Arena>,
+    game_assets: Res<GameAssets>,
+    active_modifiers: Res<ActiveModifiers>,
+    run_settings: Res<RunSettings>,
+) {
+    let health =
+        run_settings.starting_health(active_modifiers.player_health(INITIAL_PLAYER_HEALTH));
+    // Players are lined up around the arena center
+    let player_count = run_settings.player_count();
+    let first_offset = (player_count - 1) as f32 * PLAYER_SPACING / 2.0;
+    for index in 0..player_count {
+        commands.spawn(create_player_bundle(
+            &game_assets,
+            PlayerId(index),
+            arena.width / 2.0 + index as f32 * PLAYER_SPACING - first_offset,
+            arena.height / 2.0,
+            health,
+        ));
     }
 }
 
-pub fn player_movement(
-    mut player_query: Query<&mut Transform, With<Player>>,
+/// Fills the input of the players controlled on this machine from the keyboard and gamepads.
+pub fn read_local_input(
+    mut player_query: Query<(&mut PlayerInput, &PlayerId), LocalPlayer>,
+    gamepad_query: Query<(Entity, &Gamepad)>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    time: Res<Time>,
+    player_bindings: Res<PlayerBindings>,
 ) {
-    if let Ok(mut player_transform) = player_query.get_single_mut() {
-        let mut direction = Vec3::ZERO;
-
-        if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
-            direction += Vec3::new(-1.0, 0.0, 0.0)
-        }
-        if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD) {
-            direction += Vec3::new(1.0, 0.0, 0.0)
-        }
-        if keyboard_input.pressed(KeyCode::ArrowDown) || keyboard_input.pressed(KeyCode::KeyS) {
-            direction += Vec3::new(0.0, -1.0, 0.0)
-        }
-        if keyboard_input.pressed(KeyCode::ArrowUp) || keyboard_input.pressed(KeyCode::KeyW) {
-            direction += Vec3::new(0.0, 1.0, 0.0)
-        }
+    let mut gamepads: Vec<(Entity, &Gamepad)> = gamepad_query.iter().collect();
+    gamepads.sort_by_key(|(gamepad_entity, _)| *gamepad_entity);
+    let local_players = player_query.iter().count();
 
-        direction = direction.normalize_or_zero();
+    for (mut player_input, player_id) in &mut player_query {
+        let direction = match local_players {
+            // A single player can use every binding and gamepad
+            1 => player_bindings.combined_direction(
+                &keyboard_input,
+                gamepads.iter().map(|(_, gamepad)| *gamepad),
+            ),
+            _ => {
+                let keys_direction = player_bindings
+                    .keys(*player_id)
+                    .map_or(Vec2::ZERO, |keys| keys.direction(&keyboard_input));
+                let gamepad_direction = gamepads
+                    .get(player_id.0)
+                    .map_or(Vec2::ZERO, |(_, gamepad)| gamepad_direction(gamepad));
+                keys_direction + gamepad_direction
+            }
+        };
+        player_input.direction = direction.normalize_or_zero();
+    }
+}
 
-        player_transform.translation += direction * PLAYER_SPEED * time.delta_secs();
+pub fn player_movement(
+    mut player_query: Query<(&mut Transform, &PlayerInput), With<Player>>,
+    time: Res<Time>,
+) {
+    for (mut player_transform, player_input) in &mut player_query {
+        let direction = player_input.direction.clamp_length_max(1.0);
+        player_transform.translation += direction.extend(0.0) * PLAYER_SPEED * time.delta_secs();
     }
 }
 
 pub fn confine_player_movement(
     mut player_query: Query<&mut Transform, With<Player>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
 ) {
-    if let Ok(mut player_transform) = player_query.get_single_mut() {
-        let window: &Window = window_query.get_single().unwrap();
-        let [x_min, x_max, y_min, y_max] = utils::get_confinement(window, PLAYER_SIZE);
+    let [x_min, x_max, y_min, y_max] = arena.confinement(PLAYER_SIZE);
 
+    for mut player_transform in &mut player_query {
         if player_transform.translation.x < x_min {
             player_transform.translation.x = x_min;
         } else if player_transform.translation.x > x_max {
@@ -87,48 +133,61 @@ pub fn confine_player_movement(
     }
 }
 
+type PlayerNotStar = (With<Player>, Without<Star>);
+
 pub fn player_hit_star(
     mut commands: Commands,
-    player_query: Query<&mut Transform, (With<Player>, Without<Star>)>,
-    star_query: Query<(Entity, &mut Transform), With<Star>>,
-    asset_server: Res<AssetServer>,
+    player_query: Query<(&Transform, &PlayerId), PlayerNotStar>,
+    star_query: Query<(Entity, &Transform), With<Star>>,
     mut score: ResMut<Score>,
+    mut star_collected_event_writer: EventWriter<StarCollected>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        let collision_distance = (PLAYER_SIZE + STAR_SIZE) / 2.0;
-        for (star_entity, star_transform) in &star_query {
-            let relative_vector_in_plane = Vec3 {
-                x: player_transform.translation.x - star_transform.translation.x,
-                y: player_transform.translation.y - star_transform.translation.y,
-                z: 0.0,
-            };
-
-            if relative_vector_in_plane.length() > collision_distance {
-                continue;
-            }
+    let collision_distance = (PLAYER_SIZE + STAR_SIZE) / 2.0;
+    for (star_entity, star_transform) in &star_query {
+        // Only the first player within reach collects the star
+        let Some((_, player_id)) =
+            player_query
+                .iter()
+                .sort::<&PlayerId>()
+                .find(|(player_transform, _)| {
+                    player_transform
+                        .translation
+                        .truncate()
+                        .distance(star_transform.translation.truncate())
+                        <= collision_distance
+                })
+        else {
+            continue;
+        };
 
-            commands.spawn((
-                AudioPlayer::<AudioSource>(asset_server.load("audio/laserLarge_000.ogg")),
-                PlaybackSettings::DESPAWN,
-            ));
-
-            commands.entity(star_entity).despawn();
-            score.value += 1;
-        }
+        star_collected_event_writer.send(StarCollected {
+            position: star_transform.translation,
+        });
+        commands.entity(star_entity).despawn();
+        score.value += 1;
+        score.by_player[player_id.0] += 1;
     }
 }
 
 type OnlyPlayer = (With<Player>, Without<Enemy>);
+type HarmfulEnemy = (With<Enemy>, Without<Telegraphing>);
 
 pub fn player_hit_enemy(
-    mut commands: Commands,
-    mut enemy_query: Query<&mut Transform, With<Enemy>>,
-    mut player_query: Query<(&mut Transform, &mut Health), OnlyPlayer>,
-    asset_server: Res<AssetServer>,
+    mut enemy_query: Query<(&mut Transform, &SimulationId), HarmfulEnemy>,
+    mut player_query: Query<(&mut Transform, &mut Health, &PlayerId), OnlyPlayer>,
+    mut asteroid_impact_event_writer: EventWriter<AsteroidImpact>,
+    god_mode: Res<GodMode>,
+    run_settings: Res<RunSettings>,
 ) {
-    if let Ok((mut player_transform, mut player_health)) = player_query.get_single_mut() {
-        let collision_distance = (PLAYER_SIZE + ENEMY_SIZE) / 2.0;
-        for mut enemy_transform in &mut enemy_query {
+    let collision_distance = (PLAYER_SIZE + ENEMY_SIZE) / 2.0;
+    let deals_damage = !god_mode.enabled && run_settings.mode.deals_damage();
+    let mut shared_damage = 0;
+
+    // Rebounds add up in the same order on every machine
+    for (mut player_transform, mut player_health, player_id) in
+        player_query.iter_mut().sort::<&PlayerId>()
+    {
+        for (mut enemy_transform, _) in enemy_query.iter_mut().sort::<&SimulationId>() {
             let mut relative_vector_in_plane = Vec3 {
                 x: player_transform.translation.x - enemy_transform.translation.x,
                 y: player_transform.translation.y - enemy_transform.translation.y,
@@ -139,31 +198,93 @@ pub fn player_hit_enemy(
                 continue;
             }
 
-            commands.spawn((
-                AudioPlayer::<AudioSource>(asset_server.load("audio/explosionCrunch_000.ogg")),
-                PlaybackSettings::DESPAWN,
-            ));
+            asteroid_impact_event_writer.send(AsteroidImpact {
+                position: (player_transform.translation + enemy_transform.translation) / 2.0,
+            });
 
             relative_vector_in_plane = relative_vector_in_plane.normalize_or_zero();
             enemy_transform.translation -= COLLISION_REBOUND_STRENGTH * relative_vector_in_plane;
             player_transform.translation += COLLISION_REBOUND_STRENGTH * relative_vector_in_plane;
 
-            player_health.current -= 1;
-            println!("You lost a health point ({} left)!", player_health.current)
+            if !deals_damage {
+                continue;
+            }
+            match run_settings.player_lives() {
+                Lives::Separate => {
+                    player_health.current = player_health.current.saturating_sub(1);
+                    info!(target: "player", "{player_id} lost a health point ({} left)!", player_health.current);
+                }
+                Lives::Shared => shared_damage += 1,
+            }
         }
     }
+
+    if shared_damage == 0 {
+        return;
+    }
+    // Every player mirrors the shared health pool
+    let mut remaining_health = 0;
+    for (_, mut player_health, _) in &mut player_query {
+        player_health.current = player_health.current.saturating_sub(shared_damage);
+        remaining_health = player_health.current;
+    }
+    info!(target: "player", "The team lost {shared_damage} health ({remaining_health} left)!");
+}
+
+pub fn play_star_collected_sound(
+    mut commands: Commands,
+    mut star_collected_event_reader: EventReader<StarCollected>,
+    game_assets: Res<GameAssets>,
+) {
+    for _event in star_collected_event_reader.read() {
+        commands.spawn((
+            AudioPlayer(game_assets.star_collected_sound.clone()),
+            PlaybackSettings::DESPAWN,
+        ));
+    }
+}
+
+pub fn play_impact_sound(
+    mut commands: Commands,
+    mut asteroid_impact_event_reader: EventReader<AsteroidImpact>,
+    game_assets: Res<GameAssets>,
+) {
+    for _event in asteroid_impact_event_reader.read() {
+        commands.spawn((
+            AudioPlayer(game_assets.explosion_sound.clone()),
+            PlaybackSettings::DESPAWN,
+        ));
+    }
 }
 
 pub fn check_player_health(
+    mut commands: Commands,
     mut game_over_event_writer: EventWriter<GameOver>,
-    player_query: Query<&Health, With<Player>>,
+    mut player_out_event_writer: EventWriter<PlayerOut>,
+    player_query: Query<(Entity, &Health, &PlayerId), With<Player>>,
     score: Res<Score>,
 ) {
-    if let Ok(player_health) = player_query.get_single() {
+    if player_query.is_empty() {
+        return;
+    }
+    // The run is only over once every player is out
+    if player_query
+        .iter()
+        .all(|(_, player_health, _)| player_health.current == 0)
+    {
+        game_over_event_writer.send(GameOver { score: score.value });
+        return;
+    }
+
+    for (player_entity, player_health, player_id) in &player_query {
         if player_health.current > 0 {
-            return;
+            continue;
         }
-        game_over_event_writer.send(GameOver { score: score.value });
+        info!(target: "player", "{player_id} is out!");
+        commands.entity(player_entity).despawn();
+        player_out_event_writer.send(PlayerOut {
+            player_id: *player_id,
+        });
     }
 }
 
@@ -175,10 +296,13 @@ pub fn handle_game_over(
     mut next_simulation_state: ResMut<NextState<SimulationState>>,
 ) {
     for _event in &mut game_over_event_reader.read() {
-        if let Ok(player_entity) = player_query.get_single() {
+        if player_query.is_empty() {
+            continue;
+        }
+        for player_entity in &player_query {
             commands.entity(player_entity).despawn();
-            next_app_state.set(AppState::GameOver);
-            next_simulation_state.set(SimulationState::Paused);
         }
+        next_app_state.set(AppState::GameOver);
+        next_simulation_state.set(SimulationState::Paused);
     }
 }
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use super::mode::GameMode;
+use super::player::{Lives, MAX_LOCAL_PLAYERS};
+use super::star::MAX_STARS;
+use super::versus::VERSUS_BEST_OF;
+
+/// Seed from which the procedural content of the current run is generated.
+#[derive(Resource, Debug)]
+pub struct RunSeed {
+    pub value: u64,
+}
+
+impl Default for RunSeed {
+    fn default() -> RunSeed {
+        RunSeed {
+            value: rand::random(),
+        }
+    }
+}
+
+/// Options that are chosen before a run and kept when it's restarted.
+#[derive(Resource, Debug, Clone)]
+pub struct RunSettings {
+    pub mode: GameMode,
+    /// Number of local players, more than one for co-op.
+    pub players: usize,
+    /// Whether co-op players have separate health or share it.
+    pub lives: Lives,
+    /// Number of rounds a versus match is played over.
+    pub best_of: u32,
+    /// Fixed seed for every run, a new random seed is rolled per run otherwise.
+    pub seed: Option<u64>,
+    /// Maximum number of stars in the arena at the same time.
+    pub max_stars: usize,
+    /// Whether bots drive the players of this machine instead of their input.
+    pub bots: bool,
+}
+
+impl Default for RunSettings {
+    fn default() -> RunSettings {
+        RunSettings {
+            mode: GameMode::default(),
+            players: 1,
+            lives: Lives::default(),
+            best_of: VERSUS_BEST_OF,
+            seed: None,
+            max_stars: MAX_STARS,
+            bots: false,
+        }
+    }
+}
+
+impl RunSettings {
+    /// Number of players in the run, versus needs at least two of them.
+    pub fn player_count(&self) -> usize {
+        match self.mode {
+            GameMode::Versus => self.players.max(MAX_LOCAL_PLAYERS),
+            _ => self.players,
+        }
+    }
+
+    /// Versus players always have their own health.
+    pub fn player_lives(&self) -> Lives {
+        match self.mode {
+            GameMode::Versus => Lives::Separate,
+            _ => self.lives,
+        }
+    }
+
+    pub fn is_multiplayer(&self) -> bool {
+        self.player_count() > 1
+    }
+
+    /// Switches between single player and the co-op variants of the main menu.
+    pub fn cycle_players(&mut self) {
+        if self.mode == GameMode::Versus {
+            return;
+        }
+        (self.players, self.lives) = match (self.players > 1, self.lives) {
+            (false, _) => (MAX_LOCAL_PLAYERS, Lives::Separate),
+            (true, Lives::Separate) => (MAX_LOCAL_PLAYERS, Lives::Shared),
+            (true, Lives::Shared) => (1, Lives::Separate),
+        };
+    }
+
+    /// Localization key of the main menu's player selection label.
+    pub fn players_key(&self) -> &'static str {
+        if self.mode == GameMode::Versus {
+            return "main_menu.players.versus";
+        }
+        match (self.players > 1, self.lives) {
+            (false, _) => "main_menu.players.single",
+            (true, Lives::Separate) => "main_menu.players.coop_separate",
+            (true, Lives::Shared) => "main_menu.players.coop_shared",
+        }
+    }
+
+    /// Health each player starts with, a shared pool holds the health of all players.
+    pub fn starting_health(&self, health: u32) -> u32 {
+        match self.player_lives() {
+            Lives::Separate => health,
+            Lives::Shared => health * self.player_count() as u32,
+        }
+    }
+}
+
+/// Random number generator for all gameplay decisions, reseeded from the [`RunSeed`] every run.
+#[derive(Resource, Debug)]
+pub struct GameRng {
+    pub rng: ChaCha8Rng,
+}
+
+impl Default for GameRng {
+    fn default() -> GameRng {
+        GameRng {
+            rng: ChaCha8Rng::from_entropy(),
+        }
+    }
+}
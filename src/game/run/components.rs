@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Gameplay entity that belongs to the current run and is despawned when it ends.
+#[derive(Component, Default, Debug)]
+pub struct RunScoped;
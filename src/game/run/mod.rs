@@ -0,0 +1,53 @@
+pub mod components;
+mod systems;
+
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+use systems::*;
+
+pub use systems::{despawn_run_entities, handle_run_events, RunLock};
+
+use super::{PauseMenuView, SimulationState};
+use crate::{
+    events::{EndRun, RestartRun, StartRun},
+    AppState,
+};
+
+/// Runs when a run begins, both when entering the game and when restarting it.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RunStart;
+
+/// Runs when a run ends, both when leaving the game and before restarting it.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RunEnd;
+
+/// Starts, ends and restarts runs through the [`StartRun`], [`EndRun`] and [`RestartRun`] events.
+pub struct RunLifecyclePlugin;
+
+impl Plugin for RunLifecyclePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_schedule(RunStart)
+            .init_schedule(RunEnd)
+            .add_event::<StartRun>()
+            .add_event::<EndRun>()
+            .add_event::<RestartRun>()
+            .add_systems(OnEnter(AppState::Game), run_start_schedule)
+            .add_systems(OnExit(AppState::Game), run_end_schedule)
+            .add_systems(RunEnd, despawn_run_entities)
+            .add_systems(
+                Update,
+                (
+                    (
+                        restart_run_on_key.run_if(not(in_state(PauseMenuView::ConfirmRestart))),
+                        end_run_on_key,
+                    )
+                        .run_if(
+                            in_state(SimulationState::Paused)
+                                .and(in_state(AppState::Game).or(in_state(AppState::GameOver))),
+                        ),
+                    (handle_run_buttons, handle_run_events, restart_run).chain(),
+                )
+                    .chain(),
+            );
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::{
+    ecs::{event::EventCursor, system::SystemParam},
+    prelude::*,
+};
+
+use super::{components::RunScoped, RunEnd, RunStart};
+use crate::{
+    events::{EndRun, MenuButtonPressed, RestartRun, StartRun},
+    game::{
+        daily::resources::{DailyChallenge, DailyRecords},
+        resources::RunSettings,
+        save::resources::PendingRestore,
+        PauseMenuView, SimulationState,
+    },
+    network::resources::RollbackSession,
+    widgets::components::ButtonAction,
+    AppState,
+};
+
+/// Checks whether a new run may be started in the selected mode.
+#[derive(SystemParam)]
+pub struct RunLock<'w> {
+    run_settings: Res<'w, RunSettings>,
+    daily_challenge: Res<'w, DailyChallenge>,
+    daily_records: Res<'w, DailyRecords>,
+    pending_restore: Option<Res<'w, PendingRestore>>,
+}
+
+impl RunLock<'_> {
+    pub fn is_locked(&self) -> bool {
+        // Continuing a saved run doesn't use up an attempt
+        if self.pending_restore.is_some() {
+            return false;
+        }
+        let locked = self
+            .daily_records
+            .is_locked(self.run_settings.mode, &self.daily_challenge);
+        if locked {
+            info!(target: "daily", "Today's daily challenge was already played.");
+        }
+        locked
+    }
+}
+
+fn run_is_locked(run_lock: RunLock) -> bool {
+    run_lock.is_locked()
+}
+
+pub fn run_start_schedule(world: &mut World) {
+    world.run_schedule(RunStart);
+}
+
+pub fn run_end_schedule(world: &mut World) {
+    world.run_schedule(RunEnd);
+}
+
+pub fn despawn_run_entities(mut commands: Commands, run_query: Query<Entity, With<RunScoped>>) {
+    for run_entity in &run_query {
+        commands.entity(run_entity).despawn_recursive();
+    }
+}
+
+/// Goes through the same flow as the restart buttons, so restarting mid-run asks for confirmation.
+pub fn restart_run_on_key(
+    mut menu_button_event_writer: EventWriter<MenuButtonPressed>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyR) {
+        menu_button_event_writer.send(MenuButtonPressed {
+            action: ButtonAction::Restart,
+        });
+    }
+}
+
+pub fn end_run_on_key(
+    mut end_run_event_writer: EventWriter<EndRun>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyM) {
+        end_run_event_writer.send(EndRun);
+    }
+}
+
+pub fn handle_run_buttons(
+    mut menu_button_event_reader: EventReader<MenuButtonPressed>,
+    app_state: Res<State<AppState>>,
+    mut next_pause_menu_view: ResMut<NextState<PauseMenuView>>,
+    mut start_run_event_writer: EventWriter<StartRun>,
+    mut end_run_event_writer: EventWriter<EndRun>,
+    mut restart_run_event_writer: EventWriter<RestartRun>,
+    run_lock: RunLock,
+) {
+    for event in menu_button_event_reader.read() {
+        match event.action {
+            ButtonAction::Play => {
+                start_run_event_writer.send(StartRun);
+            }
+            // Restarting mid-run throws away progress, so it has to be confirmed first
+            ButtonAction::Restart => match app_state.get() {
+                // A daily challenge's attempt is used up once it started, so it can't be restarted
+                AppState::Game if run_lock.is_locked() => {}
+                AppState::Game => next_pause_menu_view.set(PauseMenuView::ConfirmRestart),
+                _ => {
+                    restart_run_event_writer.send(RestartRun);
+                }
+            },
+            ButtonAction::ConfirmRestart => {
+                restart_run_event_writer.send(RestartRun);
+            }
+            ButtonAction::CancelRestart => {
+                next_pause_menu_view.set(PauseMenuView::Menu);
+            }
+            ButtonAction::MainMenu => {
+                end_run_event_writer.send(EndRun);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn handle_run_events(
+    mut start_run_event_reader: EventReader<StartRun>,
+    mut end_run_event_reader: EventReader<EndRun>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_simulation_state: ResMut<NextState<SimulationState>>,
+    run_lock: RunLock,
+) {
+    if start_run_event_reader.read().count() > 0 && !run_lock.is_locked() {
+        next_app_state.set(AppState::Game);
+        next_simulation_state.set(SimulationState::Running);
+        info!(target: "state", "Starting a new run.");
+    }
+    if end_run_event_reader.read().count() > 0 {
+        next_app_state.set(AppState::MainMenu);
+        next_simulation_state.set(SimulationState::Paused);
+        info!(target: "state", "Ending the run.");
+    }
+}
+
+/// Restarts the run in place while in the game, or starts a new one from the game over screen.
+pub fn restart_run(
+    world: &mut World,
+    mut restart_run_event_cursor: Local<EventCursor<RestartRun>>,
+) {
+    let restart_requested = restart_run_event_cursor
+        .read(world.resource::<Events<RestartRun>>())
+        .count()
+        > 0;
+    if !restart_requested {
+        return;
+    }
+    if world.run_system_cached(run_is_locked).unwrap_or(false) {
+        return;
+    }
+    // Both peers would have to restart on the same frame to stay in sync
+    if world.contains_resource::<RollbackSession>() {
+        info!(target: "state", "Runs of a rollback session can't be restarted.");
+        return;
+    }
+
+    if *world.resource::<State<AppState>>().get() == AppState::Game {
+        // Staying in the game state doesn't trigger `OnExit` and `OnEnter`, so both ends of the run are run here
+        world.run_schedule(RunEnd);
+        world.run_schedule(RunStart);
+    } else {
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Game);
+    }
+    world
+        .resource_mut::<NextState<SimulationState>>()
+        .set(SimulationState::Running);
+    info!(target: "state", "Restarting the run.");
+}
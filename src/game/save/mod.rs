@@ -0,0 +1,51 @@
+pub mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::*;
+use systems::*;
+
+use super::bot::bots_playing;
+use super::mode::insert_time_limit;
+use super::run::{despawn_run_entities, RunEnd, RunStart};
+use super::systems::roll_run_seed;
+use crate::AppState;
+
+/// File in which the run left through the main menu is kept.
+pub const SAVE_SLOT_PATH: &str = "saves/slot.ron";
+
+/// Saves the run when returning to the main menu and restores it through the main menu's continue button.
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveSlot>()
+            .add_systems(Update, load_saved_game.run_if(in_state(AppState::MainMenu)))
+            .add_systems(
+                RunStart,
+                restore_game_snapshot
+                    .after(roll_run_seed)
+                    .after(insert_time_limit)
+                    .run_if(resource_exists::<PendingRestore>),
+            )
+            .add_systems(
+                RunEnd,
+                // The state is already switched when leaving it, so only runs left for the main menu are saved
+                save_game_snapshot
+                    .before(despawn_run_entities)
+                    .run_if(in_state(AppState::MainMenu))
+                    .run_if(not(bots_playing)),
+            )
+            .add_systems(
+                RunEnd,
+                (
+                    // Only the saved run ending in a game over uses up the slot, other runs leave it alone
+                    delete_save_slot.run_if(in_state(AppState::GameOver)),
+                    remove_resumed_from_slot,
+                )
+                    .chain()
+                    .run_if(resource_exists::<ResumedFromSlot>),
+            );
+    }
+}
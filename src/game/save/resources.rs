@@ -0,0 +1,294 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{
+    de::{SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use thiserror::Error;
+
+use crate::game::{
+    daily::resources::DailyDate,
+    mode::GameMode,
+    player::{components::PlayerId, Lives, MAX_PLAYERS},
+};
+
+use super::SAVE_SLOT_PATH;
+
+/// Everything needed to continue a run where it was left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    #[serde(default)]
+    pub mode: GameMode,
+    /// Date of the daily challenge the run belongs to.
+    #[serde(default)]
+    pub daily_date: Option<DailyDate>,
+    /// Number of players the run was started with, including those that are out.
+    #[serde(default = "default_player_count")]
+    pub player_count: usize,
+    #[serde(default)]
+    pub lives: Lives,
+    pub seed: u64,
+    pub rng: ChaCha8Rng,
+    /// Players that are still in the run.
+    #[serde(default)]
+    pub players: Vec<PlayerSnapshot>,
+    /// Only player of saves from before co-op, moved to `players` when loading.
+    #[serde(default, rename = "player", skip_serializing)]
+    pub legacy_player: Option<PlayerSnapshot>,
+    pub enemies: Vec<EnemySnapshot>,
+    pub stars: Vec<StarSnapshot>,
+    pub score: u32,
+    #[serde(default)]
+    pub missed_stars: u32,
+    /// Collected stars of each player, as many as the saving version supported.
+    #[serde(default, deserialize_with = "deserialize_player_scores")]
+    pub player_scores: Vec<u32>,
+    /// Elapsed seconds of the enemy spawn timer.
+    pub enemy_spawn_elapsed: f32,
+    /// Elapsed seconds of the star spawn timer.
+    pub star_spawn_elapsed: f32,
+    /// Elapsed seconds of the time limit in timed modes.
+    #[serde(default)]
+    pub time_limit_elapsed: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    #[serde(default)]
+    pub id: PlayerId,
+    pub position: [f32; 2],
+    pub health: u32,
+}
+
+fn default_player_count() -> usize {
+    1
+}
+
+/// Reads the scores both as a list and as the tuple older versions wrote for a fixed number of players.
+fn deserialize_player_scores<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<u32>, D::Error> {
+    struct PlayerScoresVisitor;
+
+    impl<'de> Visitor<'de> for PlayerScoresVisitor {
+        type Value = Vec<u32>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a sequence of scores")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u32>, A::Error> {
+            let mut scores = Vec::new();
+            while let Some(score) = seq.next_element()? {
+                scores.push(score);
+            }
+            Ok(scores)
+        }
+    }
+
+    // The format has to decide whether it's a list or a tuple
+    deserializer.deserialize_any(PlayerScoresVisitor)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemySnapshot {
+    pub position: [f32; 2],
+    pub direction: [f32; 2],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarSnapshot {
+    pub position: [f32; 2],
+    /// Seconds the star has been in the arena.
+    pub age: f32,
+}
+
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("could not access save slot: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not serialize game: {0}")]
+    Serialize(#[from] ron::Error),
+    #[error("could not parse save slot: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+}
+
+impl GameSnapshot {
+    pub fn load(path: &Path) -> Result<GameSnapshot, SaveError> {
+        let contents = std::fs::read_to_string(path)?;
+        GameSnapshot::parse(&contents)
+    }
+
+    /// Reads a snapshot and brings it into a shape the current version can restore.
+    pub fn parse(contents: &str) -> Result<GameSnapshot, SaveError> {
+        let mut snapshot: GameSnapshot = ron::from_str(contents)?;
+        if let Some(player) = snapshot.legacy_player.take() {
+            if snapshot.players.is_empty() {
+                snapshot.players.push(player);
+            }
+        }
+
+        // Saves from versions with fewer players hold fewer scores
+        snapshot.player_scores.resize(MAX_PLAYERS, 0);
+
+        // Player ids index the tints and bindings, so edited or corrupted saves must not exceed them
+        snapshot.player_count = snapshot.player_count.clamp(1, MAX_PLAYERS);
+        let player_count = snapshot.player_count;
+        let mut seen_ids = Vec::new();
+        snapshot.players.retain(|player| {
+            let valid = player.id.0 < player_count && !seen_ids.contains(&player.id);
+            if valid {
+                seen_ids.push(player.id);
+            } else {
+                warn!(target: "save", "Dropped saved player with invalid id {}", player.id.0);
+            }
+            valid
+        });
+        Ok(snapshot)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Snapshot that replaces the regular spawning when entering the game.
+#[derive(Resource, Debug)]
+pub struct PendingRestore {
+    pub snapshot: GameSnapshot,
+}
+
+/// Marks a run that continues the saved one, whose slot is used up once the run is over.
+#[derive(Resource, Debug)]
+pub struct ResumedFromSlot;
+
+/// Where the run left through the main menu is kept, [`SAVE_SLOT_PATH`] unless overridden.
+#[derive(Resource, Debug, Clone)]
+pub struct SaveSlot {
+    pub path: PathBuf,
+}
+
+impl Default for SaveSlot {
+    fn default() -> Self {
+        SaveSlot {
+            path: PathBuf::from(SAVE_SLOT_PATH),
+        }
+    }
+}
+
+impl SaveSlot {
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+
+    fn player(id: usize) -> PlayerSnapshot {
+        PlayerSnapshot {
+            id: PlayerId(id),
+            position: [10.0, 20.0],
+            health: 3,
+        }
+    }
+
+    fn snapshot(player_count: usize, players: Vec<PlayerSnapshot>) -> GameSnapshot {
+        GameSnapshot {
+            mode: GameMode::Endless,
+            daily_date: None,
+            player_count,
+            lives: Lives::default(),
+            seed: 7,
+            rng: ChaCha8Rng::seed_from_u64(7),
+            players,
+            legacy_player: None,
+            enemies: Vec::new(),
+            stars: Vec::new(),
+            score: 4,
+            missed_stars: 0,
+            player_scores: vec![0; MAX_PLAYERS],
+            enemy_spawn_elapsed: 0.0,
+            star_spawn_elapsed: 0.0,
+            time_limit_elapsed: 0.0,
+        }
+    }
+
+    fn to_ron(snapshot: &GameSnapshot) -> String {
+        ron::ser::to_string_pretty(snapshot, ron::ser::PrettyConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn round_trips_players() {
+        let contents = to_ron(&snapshot(2, vec![player(0), player(1)]));
+        let restored = GameSnapshot::parse(&contents).unwrap();
+        let ids: Vec<PlayerId> = restored.players.iter().map(|player| player.id).collect();
+        assert_eq!(ids, [PlayerId(0), PlayerId(1)]);
+        assert!(!contents.contains("legacy_player") && !contents.contains("player:"));
+    }
+
+    #[test]
+    fn migrates_the_single_player_of_old_saves() {
+        let contents = to_ron(&snapshot(1, Vec::new())).replace(
+            "players: [],",
+            "player: Some((position: (10.0, 20.0), health: 2)),",
+        );
+        assert!(contents.contains("player: Some"));
+
+        let restored = GameSnapshot::parse(&contents).unwrap();
+        assert_eq!(restored.players.len(), 1);
+        assert_eq!(restored.players[0].id, PlayerId::ONE);
+        assert_eq!(restored.players[0].health, 2);
+        assert!(restored.legacy_player.is_none());
+    }
+
+    #[test]
+    fn drops_players_with_invalid_ids() {
+        let contents = to_ron(&snapshot(
+            1,
+            vec![player(0), player(0), player(1), player(MAX_PLAYERS + 3)],
+        ));
+        let restored = GameSnapshot::parse(&contents).unwrap();
+        let ids: Vec<PlayerId> = restored.players.iter().map(|player| player.id).collect();
+        assert_eq!(ids, [PlayerId(0)]);
+    }
+
+    #[test]
+    fn fills_up_the_scores_of_saves_with_fewer_players() {
+        let mut two_player_snapshot = snapshot(2, vec![player(0), player(1)]);
+        two_player_snapshot.player_scores = vec![3, 5];
+        // Builds with two players wrote the scores as a fixed-size array
+        let contents = to_ron(&two_player_snapshot).replace(
+            "player_scores: [\n        3,\n        5,\n    ],",
+            "player_scores: (3, 5),",
+        );
+        assert!(contents.contains("player_scores: (3, 5),"));
+
+        let restored = GameSnapshot::parse(&contents).unwrap();
+        let mut expected_scores = vec![0; MAX_PLAYERS];
+        expected_scores[..2].copy_from_slice(&[3, 5]);
+        assert_eq!(restored.player_scores, expected_scores);
+        assert_eq!(restored.players.len(), 2);
+    }
+
+    #[test]
+    fn clamps_the_player_count() {
+        let restored = GameSnapshot::parse(&to_ron(&snapshot(0, Vec::new()))).unwrap();
+        assert_eq!(restored.player_count, 1);
+        let restored = GameSnapshot::parse(&to_ron(&snapshot(99, Vec::new()))).unwrap();
+        assert_eq!(restored.player_count, MAX_PLAYERS);
+    }
+}
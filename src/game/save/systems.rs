@@ -0,0 +1,308 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::resources::*;
+use crate::{
+    events::{MenuButtonPressed, StartRun},
+    game::{
+        components::Health,
+        daily::resources::DailyChallenge,
+        enemy::{components::Enemy, create_enemy_bundle, resources::EnemySpawnTimer},
+        mode::{resources::TimeLimit, GameMode},
+        player::{
+            components::{Player, PlayerId},
+            create_player_bundle, MAX_PLAYERS,
+        },
+        resources::{GameRng, RunSeed, RunSettings},
+        score::resources::Score,
+        star::{
+            components::{Star, StarLifetime},
+            create_star_bundle,
+            resources::StarSpawnTimer,
+        },
+    },
+    loading::resources::GameAssets,
+    widgets::components::ButtonAction,
+};
+
+pub fn load_saved_game(
+    mut commands: Commands,
+    mut menu_button_event_reader: EventReader<MenuButtonPressed>,
+    mut start_run_event_writer: EventWriter<StartRun>,
+    mut run_settings: ResMut<RunSettings>,
+    save_slot: Res<SaveSlot>,
+) {
+    for event in menu_button_event_reader.read() {
+        if event.action != ButtonAction::ResumeSavedGame {
+            continue;
+        }
+
+        match GameSnapshot::load(&save_slot.path) {
+            Ok(snapshot) => {
+                // The run continues in the mode it was started in
+                run_settings.mode = snapshot.mode;
+                run_settings.players = snapshot.player_count;
+                run_settings.lives = snapshot.lives;
+                commands.insert_resource(PendingRestore { snapshot });
+                start_run_event_writer.send(StartRun);
+            }
+            Err(error) => error!(target: "save", "Could not load saved game: {error}"),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn save_game_snapshot(
+    player_query: Query<(&Transform, &Health, &PlayerId), With<Player>>,
+    enemy_query: Query<(&Transform, &Enemy)>,
+    star_query: Query<(&Transform, &StarLifetime), With<Star>>,
+    score: Res<Score>,
+    run_seed: Res<RunSeed>,
+    game_rng: Res<GameRng>,
+    enemy_spawn_timer: Res<EnemySpawnTimer>,
+    star_spawn_timer: Res<StarSpawnTimer>,
+    time_limit: Option<Res<TimeLimit>>,
+    run_settings: Res<RunSettings>,
+    daily_challenge: Res<DailyChallenge>,
+    save_slot: Res<SaveSlot>,
+) {
+    let snapshot = GameSnapshot {
+        mode: run_settings.mode,
+        daily_date: (run_settings.mode == GameMode::DailyChallenge).then_some(daily_challenge.date),
+        seed: run_seed.value,
+        rng: game_rng.rng.clone(),
+        player_count: run_settings.players,
+        lives: run_settings.lives,
+        players: player_query
+            .iter()
+            .map(
+                |(player_transform, player_health, player_id)| PlayerSnapshot {
+                    id: *player_id,
+                    position: player_transform.translation.truncate().to_array(),
+                    health: player_health.current,
+                },
+            )
+            .collect(),
+        legacy_player: None,
+        enemies: enemy_query
+            .iter()
+            .map(|(enemy_transform, enemy)| EnemySnapshot {
+                position: enemy_transform.translation.truncate().to_array(),
+                direction: enemy.direction.truncate().to_array(),
+            })
+            .collect(),
+        stars: star_query
+            .iter()
+            .map(|(star_transform, star_lifetime)| StarSnapshot {
+                position: star_transform.translation.truncate().to_array(),
+                age: star_lifetime.timer.elapsed_secs(),
+            })
+            .collect(),
+        score: score.value,
+        missed_stars: score.missed,
+        player_scores: score.by_player.to_vec(),
+        enemy_spawn_elapsed: enemy_spawn_timer.timer.elapsed_secs(),
+        star_spawn_elapsed: star_spawn_timer.timer.elapsed_secs(),
+        time_limit_elapsed: time_limit.map_or(0.0, |time_limit| time_limit.timer.elapsed_secs()),
+    };
+
+    match snapshot.save(&save_slot.path) {
+        Ok(()) => info!(target: "save", "Saved game to {}", save_slot.path.display()),
+        Err(error) => error!(target: "save", "Could not save game: {error}"),
+    }
+}
+
+pub fn restore_game_snapshot(
+    mut commands: Commands,
+    pending_restore: Res<PendingRestore>,
+    game_assets: Res<GameAssets>,
+    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+    mut star_spawn_timer: ResMut<StarSpawnTimer>,
+    time_limit: Option<ResMut<TimeLimit>>,
+) {
+    let snapshot = &pending_restore.snapshot;
+
+    for player in &snapshot.players {
+        let [x_position, y_position] = player.position;
+        commands.spawn(create_player_bundle(
+            &game_assets,
+            player.id,
+            x_position,
+            y_position,
+            player.health,
+        ));
+    }
+    for enemy in &snapshot.enemies {
+        let [x_position, y_position] = enemy.position;
+        commands
+            .spawn(create_enemy_bundle(&game_assets, x_position, y_position))
+            .insert(Enemy {
+                direction: Vec2::from_array(enemy.direction).extend(0.0),
+            });
+    }
+    for star in &snapshot.stars {
+        let [x_position, y_position] = star.position;
+        let mut star_lifetime = StarLifetime::default();
+        star_lifetime
+            .timer
+            .set_elapsed(Duration::from_secs_f32(star.age));
+        commands
+            .spawn(create_star_bundle(&game_assets, x_position, y_position))
+            .insert(star_lifetime);
+    }
+
+    let mut by_player = [0; MAX_PLAYERS];
+    for (stars, saved_stars) in by_player.iter_mut().zip(&snapshot.player_scores) {
+        *stars = *saved_stars;
+    }
+    commands.insert_resource(Score {
+        value: snapshot.score,
+        missed: snapshot.missed_stars,
+        by_player,
+    });
+    enemy_spawn_timer
+        .timer
+        .set_elapsed(Duration::from_secs_f32(snapshot.enemy_spawn_elapsed));
+    star_spawn_timer
+        .timer
+        .set_elapsed(Duration::from_secs_f32(snapshot.star_spawn_elapsed));
+    if let Some(mut time_limit) = time_limit {
+        time_limit
+            .timer
+            .set_elapsed(Duration::from_secs_f32(snapshot.time_limit_elapsed));
+    }
+
+    commands.remove_resource::<PendingRestore>();
+    commands.insert_resource(ResumedFromSlot);
+    info!(target: "save", "Restored saved game");
+}
+
+pub fn delete_save_slot(save_slot: Res<SaveSlot>) {
+    // The saved run is over, so it can't be continued anymore
+    if let Err(error) = std::fs::remove_file(&save_slot.path) {
+        if error.kind() != std::io::ErrorKind::NotFound {
+            error!(target: "save", "Could not delete save slot: {error}");
+        }
+    }
+}
+
+pub fn remove_resumed_from_slot(mut commands: Commands) {
+    commands.remove_resource::<ResumedFromSlot>();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, thread};
+
+    use clap::Parser;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::{cli::LaunchOptions, events::GameOver, game::player::Lives, game_app, AppState};
+
+    fn saved_snapshot() -> GameSnapshot {
+        GameSnapshot {
+            mode: GameMode::Endless,
+            daily_date: None,
+            player_count: 1,
+            lives: Lives::default(),
+            seed: 7,
+            rng: ChaCha8Rng::seed_from_u64(7),
+            players: vec![PlayerSnapshot {
+                id: PlayerId(0),
+                position: [200.0, 200.0],
+                health: 3,
+            }],
+            legacy_player: None,
+            enemies: Vec::new(),
+            stars: Vec::new(),
+            score: 4,
+            missed_stars: 1,
+            player_scores: vec![4; MAX_PLAYERS],
+            enemy_spawn_elapsed: 0.0,
+            star_spawn_elapsed: 0.0,
+            time_limit_elapsed: 0.0,
+        }
+    }
+
+    fn slot_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("bevy_ball_game_{}_{name}.ron", std::process::id()))
+    }
+
+    fn app_state(app: &App) -> Option<AppState> {
+        app.world()
+            .get_resource::<State<AppState>>()
+            .map(|app_state| *app_state.get())
+    }
+
+    /// Starts a headless run with a saved game in the slot at the path, then ends it with a game over.
+    fn play_until_game_over(save_slot: SaveSlot, pending_restore: Option<PendingRestore>) -> App {
+        saved_snapshot().save(&save_slot.path).unwrap();
+
+        let launch_options = LaunchOptions::parse_from([
+            "bevy_ball_game",
+            "--headless",
+            "--mute",
+            "--start-in",
+            "game",
+        ]);
+        let mut app = game_app(&launch_options);
+        launch_options.apply(&mut app).unwrap();
+        app.insert_resource(save_slot);
+        if let Some(pending_restore) = pending_restore {
+            app.insert_resource(pending_restore);
+        }
+        app.finish();
+        app.cleanup();
+
+        // Loading the assets takes a while in real time
+        for _ in 0..5_000 {
+            if app_state(&app) == Some(AppState::Game) {
+                break;
+            }
+            app.update();
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(
+            app_state(&app),
+            Some(AppState::Game),
+            "the run didn't start"
+        );
+
+        app.update();
+        app.world_mut().send_event(GameOver { score: 0 });
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(app_state(&app), Some(AppState::GameOver));
+        app
+    }
+
+    #[test]
+    fn fresh_run_leaves_the_save_slot_alone_at_game_over() {
+        let save_slot = SaveSlot {
+            path: slot_path("fresh_run"),
+        };
+        let app = play_until_game_over(save_slot.clone(), None);
+
+        assert!(!app.world().contains_resource::<ResumedFromSlot>());
+        assert!(save_slot.exists());
+        std::fs::remove_file(&save_slot.path).unwrap();
+    }
+
+    #[test]
+    fn resumed_run_uses_up_the_save_slot_at_game_over() {
+        let save_slot = SaveSlot {
+            path: slot_path("resumed_run"),
+        };
+        let pending_restore = PendingRestore {
+            snapshot: saved_snapshot(),
+        };
+        let app = play_until_game_over(save_slot.clone(), Some(pending_restore));
+
+        assert!(!app.world().contains_resource::<ResumedFromSlot>());
+        assert!(!save_slot.exists());
+    }
+}
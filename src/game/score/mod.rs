@@ -6,15 +6,26 @@ use bevy::prelude::*;
 use resources::*;
 use systems::*;
 
+pub use systems::remove_score;
+
+use super::bot::bots_playing;
+use super::run::{RunEnd, RunStart};
+use super::save::resources::PendingRestore;
 use super::SimulationState;
 use crate::AppState;
 
+/// Number of entries kept in the high-score table of each game mode.
+pub const HIGH_SCORE_TABLE_SIZE: usize = 10;
+
 pub struct ScorePlugin;
 
 impl Plugin for ScorePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<HighScores>()
-            .add_systems(OnEnter(AppState::Game), insert_score)
+            .add_systems(
+                RunStart,
+                insert_score.run_if(not(resource_exists::<PendingRestore>)),
+            )
             .add_systems(
                 Update,
                 update_score
@@ -22,8 +33,13 @@ impl Plugin for ScorePlugin {
                     .run_if(in_state(SimulationState::Running)),
             )
             .add_systems(
-                OnExit(AppState::Game),
-                (update_high_scores, high_scores_updated, remove_score).chain(),
+                RunEnd,
+                (
+                    // Bots don't compete for the high scores
+                    (update_high_scores, high_scores_updated).run_if(not(bots_playing)),
+                    remove_score,
+                )
+                    .chain(),
             );
     }
 }
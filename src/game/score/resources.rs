@@ -1,11 +1,75 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 
-#[derive(Resource, Default, Debug)]
+use super::HIGH_SCORE_TABLE_SIZE;
+use crate::game::mode::GameMode;
+use crate::game::player::MAX_PLAYERS;
+
+#[derive(Resource, Default, Debug, PartialEq)]
 pub struct Score {
+    /// Number of collected stars.
     pub value: u32,
+    /// Number of stars that expired before they were collected.
+    pub missed: u32,
+    /// Number of collected stars of each player.
+    pub by_player: [u32; MAX_PLAYERS],
 }
 
+/// Best scores of each game mode, highest first.
 #[derive(Resource, Default, Debug)]
 pub struct HighScores {
-    pub scores: Vec<(String, u32)>,
+    pub tables: HashMap<GameMode, Vec<(String, u32)>>,
+}
+
+impl HighScores {
+    pub fn table(&self, mode: GameMode) -> &[(String, u32)] {
+        self.tables.get(&mode).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn best(&self, mode: GameMode) -> Option<u32> {
+        self.table(mode).first().map(|(_, score)| *score)
+    }
+
+    /// Whether a score that was just inserted beats every earlier one, instead of only tying it.
+    pub fn is_new_best(&self, mode: GameMode, score: u32) -> bool {
+        match self.table(mode) {
+            [(_, best), rest @ ..] => {
+                score > 0 && *best == score && rest.first().is_none_or(|(_, next)| *next < score)
+            }
+            [] => false,
+        }
+    }
+
+    pub fn insert(&mut self, mode: GameMode, name: String, score: u32) {
+        let table = self.tables.entry(mode).or_default();
+        let index = table.partition_point(|(_, table_score)| *table_score >= score);
+        table.insert(index, (name, score));
+        table.truncate(HIGH_SCORE_TABLE_SIZE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_higher_score_is_a_new_best() {
+        let mut high_scores = HighScores::default();
+        high_scores.insert(GameMode::Endless, "Player".to_string(), 5);
+        assert!(high_scores.is_new_best(GameMode::Endless, 5));
+
+        high_scores.insert(GameMode::Endless, "Player".to_string(), 5);
+        assert!(!high_scores.is_new_best(GameMode::Endless, 5));
+
+        high_scores.insert(GameMode::Endless, "Player".to_string(), 7);
+        assert!(high_scores.is_new_best(GameMode::Endless, 7));
+        assert!(!high_scores.is_new_best(GameMode::Endless, 3));
+    }
+
+    #[test]
+    fn zero_is_never_a_new_best() {
+        let mut high_scores = HighScores::default();
+        assert!(!high_scores.is_new_best(GameMode::Zen, 0));
+        high_scores.insert(GameMode::Zen, "Player".to_string(), 0);
+        assert!(!high_scores.is_new_best(GameMode::Zen, 0));
+    }
 }
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use super::resources::*;
 use crate::events::GameOver;
+use crate::game::resources::RunSettings;
 
 pub fn insert_score(mut commands: Commands) {
     commands.insert_resource(Score::default());
@@ -13,23 +14,31 @@ pub fn remove_score(mut commands: Commands) {
 
 pub fn update_score(score: Res<Score>) {
     if score.is_changed() {
-        println!("{}", score.value)
+        info!(target: "score", "Score: {} ({} missed)", score.value, score.missed);
     }
 }
 
 pub fn update_high_scores(
     mut game_over_event_reader: EventReader<GameOver>,
     mut high_scores: ResMut<HighScores>,
+    run_settings: Res<RunSettings>,
 ) {
+    if !run_settings.mode.has_high_scores() {
+        return;
+    }
+
     for event in &mut game_over_event_reader.read() {
-        high_scores
-            .scores
-            .push(("Player name:".to_string(), event.score));
+        high_scores.insert(run_settings.mode, "Player name:".to_string(), event.score);
     }
 }
 
-pub fn high_scores_updated(high_scores: Res<HighScores>) {
+pub fn high_scores_updated(high_scores: Res<HighScores>, run_settings: Res<RunSettings>) {
     if high_scores.is_changed() {
-        println!("Your high scores are: {:?}", high_scores);
+        info!(
+            target: "score",
+            "Your {:?} high scores are: {:?}",
+            run_settings.mode,
+            high_scores.table(run_settings.mode)
+        );
     }
 }
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Identifies a gameplay entity the same way on every machine, unlike its [`Entity`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SimulationId(pub u32);
@@ -0,0 +1,60 @@
+pub mod components;
+pub mod resources;
+pub mod snapshot;
+mod systems;
+
+use bevy::{
+    ecs::schedule::{ExecutorKind, LogLevel, ScheduleBuildSettings, ScheduleLabel},
+    prelude::*,
+};
+
+use resources::*;
+use systems::*;
+
+use super::run::RunStart;
+use super::SimulationState;
+use crate::AppState;
+
+/// Steps of the deterministic gameplay simulation per second.
+pub const SIMULATION_HZ: f64 = 60.0;
+
+/// Advances all gameplay state by one fixed step, the same way on every machine.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SimulationStep;
+
+/// Runs movement, collisions and spawning in fixed steps, which can be snapshotted and restored.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        // Systems run on a single thread in the same order on every machine, so every
+        // system of the step has to be ordered against the ones it could conflict with
+        app.edit_schedule(SimulationStep, |simulation_step| {
+            simulation_step
+                .set_executor_kind(ExecutorKind::SingleThreaded)
+                .set_build_settings(ScheduleBuildSettings {
+                    ambiguity_detection: LogLevel::Error,
+                    ..default()
+                });
+        })
+        .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+        .init_resource::<NextSimulationId>()
+        .add_systems(RunStart, reset_simulation_ids)
+        .add_systems(
+            FixedUpdate,
+            run_simulation_step
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(SimulationState::Running))
+                .run_if(not(resource_exists::<ExternalStepping>)),
+        );
+    }
+}
+
+/// Runs a single [`SimulationStep`], must be called from `FixedUpdate` to step by the fixed timestep.
+pub fn step_simulation(world: &mut World) {
+    // Entities spawned during the last step or the run start get their ids first
+    if let Err(error) = world.run_system_cached(assign_simulation_ids) {
+        error!(target: "simulation", "Could not assign simulation ids: {error}");
+    }
+    world.run_schedule(SimulationStep);
+}
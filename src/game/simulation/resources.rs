@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// Next [`SimulationId`](super::components::SimulationId) to hand out, restarted every run.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct NextSimulationId(pub u32);
+
+/// Set while another plugin, like the rollback netcode, steps the simulation itself.
+#[derive(Resource)]
+pub struct ExternalStepping;
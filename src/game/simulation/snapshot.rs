@@ -0,0 +1,480 @@
+use std::hash::{Hash, Hasher};
+
+use bevy::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+use super::{
+    components::SimulationId, resources::NextSimulationId, systems::assign_simulation_ids,
+};
+use crate::{
+    game::{
+        components::Health,
+        enemy::{
+            components::Enemy,
+            create_enemy_bundle,
+            resources::{EnemyRedirectionTimer, EnemySpawnTimer},
+        },
+        mode::resources::TimeLimit,
+        player::{
+            components::{Player, PlayerId},
+            create_player_bundle, MAX_PLAYERS,
+        },
+        resources::GameRng,
+        score::resources::Score,
+        spawning::components::{EnteringArena, Telegraphing},
+        star::{
+            components::{Star, StarLifetime},
+            create_star_bundle,
+            resources::StarSpawnTimer,
+        },
+    },
+    loading::resources::GameAssets,
+};
+
+/// All gameplay state between two simulation steps, kept in memory to roll back to it.
+#[derive(Debug, Clone)]
+pub struct SimulationSnapshot {
+    players: Vec<SimulatedPlayer>,
+    enemies: Vec<SimulatedEnemy>,
+    stars: Vec<SimulatedStar>,
+    rng: ChaCha8Rng,
+    score: u32,
+    missed_stars: u32,
+    player_scores: [u32; MAX_PLAYERS],
+    next_simulation_id: NextSimulationId,
+    enemy_spawn_timer: Timer,
+    enemy_redirection_timer: Timer,
+    star_spawn_timer: Timer,
+    time_limit: Option<Timer>,
+}
+
+#[derive(Debug, Clone)]
+struct SimulatedPlayer {
+    simulation_id: SimulationId,
+    player_id: PlayerId,
+    translation: Vec3,
+    health: u32,
+}
+
+#[derive(Debug, Clone)]
+struct SimulatedEnemy {
+    simulation_id: SimulationId,
+    translation: Vec3,
+    direction: Vec3,
+    telegraph: Option<Timer>,
+    entering_arena: Option<(Vec2, Vec2)>,
+}
+
+#[derive(Debug, Clone)]
+struct SimulatedStar {
+    simulation_id: SimulationId,
+    translation: Vec3,
+    lifetime: Timer,
+}
+
+type SimulatedEntity = Or<(With<Player>, With<Enemy>, With<Star>)>;
+
+impl SimulationSnapshot {
+    pub fn capture(world: &mut World) -> SimulationSnapshot {
+        if let Err(error) = world.run_system_cached(assign_simulation_ids) {
+            error!(target: "simulation", "Could not assign simulation ids: {error}");
+        }
+
+        let mut players: Vec<SimulatedPlayer> = world
+            .query_filtered::<(&SimulationId, &PlayerId, &Transform, &Health), With<Player>>()
+            .iter(world)
+            .map(
+                |(simulation_id, player_id, transform, health)| SimulatedPlayer {
+                    simulation_id: *simulation_id,
+                    player_id: *player_id,
+                    translation: transform.translation,
+                    health: health.current,
+                },
+            )
+            .collect();
+        let mut enemies: Vec<SimulatedEnemy> = world
+            .query::<(
+                &SimulationId,
+                &Transform,
+                &Enemy,
+                Option<&Telegraphing>,
+                Option<&EnteringArena>,
+            )>()
+            .iter(world)
+            .map(
+                |(simulation_id, transform, enemy, telegraphing, entering_arena)| SimulatedEnemy {
+                    simulation_id: *simulation_id,
+                    translation: transform.translation,
+                    direction: enemy.direction,
+                    telegraph: telegraphing.map(|telegraphing| telegraphing.timer.clone()),
+                    entering_arena: entering_arena
+                        .map(|entering_arena| (entering_arena.from, entering_arena.to)),
+                },
+            )
+            .collect();
+        let mut stars: Vec<SimulatedStar> = world
+            .query_filtered::<(&SimulationId, &Transform, &StarLifetime), With<Star>>()
+            .iter(world)
+            .map(|(simulation_id, transform, star_lifetime)| SimulatedStar {
+                simulation_id: *simulation_id,
+                translation: transform.translation,
+                lifetime: star_lifetime.timer.clone(),
+            })
+            .collect();
+        // Sorted by id, the snapshot is the same on every machine and restores in the same order
+        players.sort_by_key(|player| player.simulation_id);
+        enemies.sort_by_key(|enemy| enemy.simulation_id);
+        stars.sort_by_key(|star| star.simulation_id);
+
+        let score = world.resource::<Score>();
+        SimulationSnapshot {
+            players,
+            enemies,
+            stars,
+            rng: world.resource::<GameRng>().rng.clone(),
+            score: score.value,
+            missed_stars: score.missed,
+            player_scores: score.by_player,
+            next_simulation_id: *world.resource::<NextSimulationId>(),
+            enemy_spawn_timer: world.resource::<EnemySpawnTimer>().timer.clone(),
+            enemy_redirection_timer: world.resource::<EnemyRedirectionTimer>().timer.clone(),
+            star_spawn_timer: world.resource::<StarSpawnTimer>().timer.clone(),
+            time_limit: world
+                .get_resource::<TimeLimit>()
+                .map(|time_limit| time_limit.timer.clone()),
+        }
+    }
+
+    /// Replaces all gameplay entities and resources with the ones of the snapshot.
+    pub fn restore(&self, world: &mut World) {
+        let simulated_entities: Vec<Entity> = world
+            .query_filtered::<Entity, SimulatedEntity>()
+            .iter(world)
+            .collect();
+        for entity in simulated_entities {
+            world.entity_mut(entity).despawn_recursive();
+        }
+
+        world.resource_scope(|world, game_assets: Mut<GameAssets>| {
+            for player in &self.players {
+                world
+                    .spawn(create_player_bundle(
+                        &game_assets,
+                        player.player_id,
+                        player.translation.x,
+                        player.translation.y,
+                        player.health,
+                    ))
+                    .insert((
+                        Transform::from_translation(player.translation),
+                        player.simulation_id,
+                    ));
+            }
+            for enemy in &self.enemies {
+                let mut enemy_entity = world.spawn(create_enemy_bundle(
+                    &game_assets,
+                    enemy.translation.x,
+                    enemy.translation.y,
+                ));
+                enemy_entity.insert((
+                    Transform::from_translation(enemy.translation),
+                    Enemy {
+                        direction: enemy.direction,
+                    },
+                    enemy.simulation_id,
+                ));
+                if let Some(timer) = &enemy.telegraph {
+                    enemy_entity.insert(Telegraphing {
+                        timer: timer.clone(),
+                    });
+                }
+                if let Some((from, to)) = enemy.entering_arena {
+                    enemy_entity.insert(EnteringArena { from, to });
+                }
+            }
+            for star in &self.stars {
+                world
+                    .spawn(create_star_bundle(
+                        &game_assets,
+                        star.translation.x,
+                        star.translation.y,
+                    ))
+                    .insert((
+                        Transform::from_translation(star.translation),
+                        StarLifetime {
+                            timer: star.lifetime.clone(),
+                        },
+                        star.simulation_id,
+                    ));
+            }
+        });
+
+        world.resource_mut::<GameRng>().rng = self.rng.clone();
+        // Unchanged scores don't update the HUD
+        world.resource_mut::<Score>().set_if_neq(Score {
+            value: self.score,
+            missed: self.missed_stars,
+            by_player: self.player_scores,
+        });
+        world.insert_resource(self.next_simulation_id);
+        world.resource_mut::<EnemySpawnTimer>().timer = self.enemy_spawn_timer.clone();
+        world.resource_mut::<EnemyRedirectionTimer>().timer = self.enemy_redirection_timer.clone();
+        world.resource_mut::<StarSpawnTimer>().timer = self.star_spawn_timer.clone();
+        if let (Some(mut time_limit), Some(timer)) =
+            (world.get_resource_mut::<TimeLimit>(), &self.time_limit)
+        {
+            time_limit.timer = timer.clone();
+        }
+    }
+
+    /// Hash of the gameplay state, equal on machines whose simulations didn't diverge.
+    pub fn checksum(&self) -> u64 {
+        let mut hasher = Fnv1aHasher::default();
+        for player in &self.players {
+            player.simulation_id.hash(&mut hasher);
+            player.player_id.hash(&mut hasher);
+            hash_floats(&mut hasher, &player.translation.to_array());
+            player.health.hash(&mut hasher);
+        }
+        for enemy in &self.enemies {
+            enemy.simulation_id.hash(&mut hasher);
+            hash_floats(&mut hasher, &enemy.translation.to_array());
+            hash_floats(&mut hasher, &enemy.direction.to_array());
+            enemy
+                .telegraph
+                .as_ref()
+                .map(Timer::elapsed)
+                .hash(&mut hasher);
+        }
+        for star in &self.stars {
+            star.simulation_id.hash(&mut hasher);
+            hash_floats(&mut hasher, &star.translation.to_array());
+            star.lifetime.elapsed().hash(&mut hasher);
+        }
+        self.rng.get_word_pos().hash(&mut hasher);
+        (self.score, self.missed_stars, self.player_scores).hash(&mut hasher);
+        self.next_simulation_id.0.hash(&mut hasher);
+        self.enemy_spawn_timer.elapsed().hash(&mut hasher);
+        self.enemy_redirection_timer.elapsed().hash(&mut hasher);
+        self.star_spawn_timer.elapsed().hash(&mut hasher);
+        self.time_limit
+            .as_ref()
+            .map(Timer::elapsed)
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// 64-bit FNV-1a, which unlike the standard library's hasher is specified to hash the same in every
+/// build. Integers are hashed as little-endian `u64`s, independent of the machine's word size and byte order.
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Fnv1aHasher {
+        Fnv1aHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write_u64(value.into());
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write_u64(value.into());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write_u64(value.into());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn hash_floats(hasher: &mut impl Hasher, values: &[f32]) {
+    for value in values {
+        value.to_bits().hash(hasher);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rand::{RngCore, SeedableRng};
+
+    use super::*;
+
+    fn simulation_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(GameAssets {
+            font: default(),
+            player_sprite: default(),
+            enemy_sprite: default(),
+            star_sprite: default(),
+            heart_sprite: default(),
+            bounce_sound: default(),
+            star_collected_sound: default(),
+            explosion_sound: default(),
+            main_menu_music: default(),
+            game_music: default(),
+            game_intensity_music: default(),
+            game_over_music: default(),
+        });
+        world.insert_resource(GameRng {
+            rng: ChaCha8Rng::seed_from_u64(3),
+        });
+        world.init_resource::<Score>();
+        world.init_resource::<NextSimulationId>();
+        world.init_resource::<EnemySpawnTimer>();
+        world.init_resource::<EnemyRedirectionTimer>();
+        world.init_resource::<StarSpawnTimer>();
+
+        let game_assets = world.remove_resource::<GameAssets>().unwrap();
+        world.spawn(create_player_bundle(
+            &game_assets,
+            PlayerId(0),
+            -50.0,
+            0.0,
+            3,
+        ));
+        world.spawn(create_player_bundle(
+            &game_assets,
+            PlayerId(1),
+            50.0,
+            0.0,
+            2,
+        ));
+        world
+            .spawn(create_enemy_bundle(&game_assets, 200.0, 100.0))
+            .insert(Enemy {
+                direction: Vec3::new(0.6, 0.8, 0.0),
+            });
+        world
+            .spawn(create_enemy_bundle(&game_assets, -300.0, 120.0))
+            .insert((
+                Telegraphing {
+                    timer: Timer::from_seconds(1.0, TimerMode::Once),
+                },
+                EnteringArena {
+                    from: Vec2::new(-400.0, 120.0),
+                    to: Vec2::new(-300.0, 120.0),
+                },
+            ));
+        world.spawn(create_star_bundle(&game_assets, 10.0, -80.0));
+        world.insert_resource(game_assets);
+        world
+    }
+
+    fn count<F: bevy::ecs::query::QueryFilter>(world: &mut World) -> usize {
+        world.query_filtered::<(), F>().iter(world).count()
+    }
+
+    #[test]
+    fn hashes_with_fnv1a() {
+        let mut hasher = Fnv1aHasher::default();
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn checksums_equal_states_equally() {
+        let mut world = simulation_world();
+        let snapshot = SimulationSnapshot::capture(&mut world);
+        world.flush();
+        assert_eq!(
+            snapshot.checksum(),
+            SimulationSnapshot::capture(&mut world).checksum()
+        );
+        assert_eq!(snapshot.checksum(), snapshot.clone().checksum());
+    }
+
+    #[test]
+    fn checksums_differ_after_changes() {
+        let mut world = simulation_world();
+        let snapshot = SimulationSnapshot::capture(&mut world);
+        world.flush();
+
+        let mut enemy_query = world.query_filtered::<&mut Transform, With<Enemy>>();
+        enemy_query
+            .iter_mut(&mut world)
+            .next()
+            .unwrap()
+            .translation
+            .x += 0.001;
+        let moved = SimulationSnapshot::capture(&mut world);
+        assert_ne!(snapshot.checksum(), moved.checksum());
+
+        world.resource_mut::<GameRng>().rng.next_u32();
+        assert_ne!(
+            moved.checksum(),
+            SimulationSnapshot::capture(&mut world).checksum()
+        );
+    }
+
+    #[test]
+    fn restores_captured_state() {
+        let mut world = simulation_world();
+        let snapshot = SimulationSnapshot::capture(&mut world);
+        world.flush();
+
+        // Change every part of the state the snapshot covers
+        let star_entity = world.query_filtered::<Entity, With<Star>>().single(&world);
+        world.despawn(star_entity);
+        let mut player_query = world.query::<(&mut Transform, &mut Health)>();
+        for (mut transform, mut health) in player_query.iter_mut(&mut world) {
+            transform.translation.y += 40.0;
+            health.current = 0;
+        }
+        world.resource_mut::<GameRng>().rng.next_u64();
+        world.resource_mut::<Score>().value = 12;
+        world.resource_mut::<NextSimulationId>().0 += 5;
+        world
+            .resource_mut::<EnemySpawnTimer>()
+            .timer
+            .tick(Duration::from_secs_f32(0.5));
+        assert_ne!(
+            snapshot.checksum(),
+            SimulationSnapshot::capture(&mut world).checksum()
+        );
+
+        snapshot.restore(&mut world);
+        world.flush();
+        assert_eq!(count::<With<Player>>(&mut world), 2);
+        assert_eq!(count::<With<Enemy>>(&mut world), 2);
+        assert_eq!(count::<With<Telegraphing>>(&mut world), 1);
+        assert_eq!(count::<With<EnteringArena>>(&mut world), 1);
+        assert_eq!(count::<With<Star>>(&mut world), 1);
+        // Restored entities keep their ids instead of getting new ones
+        assert_eq!(
+            count::<(Without<SimulationId>, SimulatedEntity)>(&mut world),
+            0
+        );
+        assert_eq!(
+            snapshot.checksum(),
+            SimulationSnapshot::capture(&mut world).checksum()
+        );
+    }
+}
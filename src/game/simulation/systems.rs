@@ -0,0 +1,51 @@
+use std::cmp::Ordering;
+
+use bevy::prelude::*;
+
+use super::{components::SimulationId, resources::NextSimulationId, step_simulation};
+use crate::game::{enemy::components::Enemy, player::components::Player, star::components::Star};
+
+type NewEntity<'a> = (Entity, &'a Transform, Has<Player>, Has<Enemy>);
+type Unidentified = (
+    Without<SimulationId>,
+    Or<(With<Player>, With<Enemy>, With<Star>)>,
+);
+
+pub fn reset_simulation_ids(mut next_simulation_id: ResMut<NextSimulationId>) {
+    *next_simulation_id = NextSimulationId::default();
+}
+
+pub fn assign_simulation_ids(
+    mut commands: Commands,
+    unidentified_query: Query<NewEntity, Unidentified>,
+    mut next_simulation_id: ResMut<NextSimulationId>,
+) {
+    // The iteration order differs between machines, the kind and position of the new entities doesn't
+    let mut new_entities: Vec<_> = unidentified_query.iter().collect();
+    new_entities.sort_by(|first, second| {
+        let (_, first_transform, first_is_player, first_is_enemy) = first;
+        let (_, second_transform, second_is_player, second_is_enemy) = second;
+        (first_is_player, first_is_enemy)
+            .cmp(&(second_is_player, second_is_enemy))
+            .then_with(|| compare_positions(first_transform, second_transform))
+    });
+
+    for (entity, ..) in new_entities {
+        commands
+            .entity(entity)
+            .insert(SimulationId(next_simulation_id.0));
+        next_simulation_id.0 += 1;
+    }
+}
+
+fn compare_positions(first: &Transform, second: &Transform) -> Ordering {
+    first
+        .translation
+        .x
+        .total_cmp(&second.translation.x)
+        .then(first.translation.y.total_cmp(&second.translation.y))
+}
+
+pub fn run_simulation_step(world: &mut World) {
+    step_simulation(world);
+}
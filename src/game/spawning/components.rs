@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use super::TELEGRAPH_DURATION;
+
+/// Marks a freshly spawned enemy that neither moves nor hurts the player yet.
+#[derive(Component, Debug)]
+pub struct Telegraphing {
+    pub timer: Timer,
+}
+
+impl Default for Telegraphing {
+    fn default() -> Telegraphing {
+        Telegraphing {
+            timer: Timer::from_seconds(TELEGRAPH_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+/// Slides a telegraphing enemy from outside the arena to its position inside.
+#[derive(Component, Debug)]
+pub struct EnteringArena {
+    pub from: Vec2,
+    pub to: Vec2,
+}
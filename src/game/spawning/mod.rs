@@ -0,0 +1,39 @@
+pub mod components;
+mod placement;
+mod systems;
+
+use bevy::prelude::*;
+
+use systems::*;
+
+pub use placement::{SpawnPlacement, SpawnPlacer};
+pub use systems::update_telegraphs;
+
+use super::simulation::SimulationStep;
+use super::SimulationState;
+use crate::AppState;
+
+/// Minimum distance between a spawned entity and the player.
+const PLAYER_CLEARANCE: f32 = 200.0;
+/// Minimum gap between a spawned entity and other enemies and stars.
+const ENTITY_CLEARANCE: f32 = 16.0;
+/// Number of random positions tried before settling for the one with the most clearance.
+const MAX_PLACEMENT_ATTEMPTS: usize = 30;
+/// Time in seconds during which a newly spawned enemy is harmless.
+pub const TELEGRAPH_DURATION: f32 = 1.0;
+/// Blinks per second of telegraphing enemies.
+const TELEGRAPH_BLINK_RATE: f32 = 4.0;
+
+pub struct SpawningPlugin;
+
+impl Plugin for SpawningPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(SimulationStep, update_telegraphs)
+            .add_systems(
+                Update,
+                draw_telegraph_markers
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+            );
+    }
+}
@@ -0,0 +1,130 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::Rng;
+
+use super::{ENTITY_CLEARANCE, MAX_PLACEMENT_ATTEMPTS, PLAYER_CLEARANCE};
+use crate::{
+    arena::resources::Arena,
+    game::{
+        enemy::{components::Enemy, ENEMY_SIZE},
+        player::components::Player,
+        resources::GameRng,
+        star::components::Star,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnPlacement {
+    /// Anywhere inside the arena.
+    Anywhere,
+    /// Along the arena border, entering from outside.
+    Edge,
+}
+
+type Obstacles = Or<(With<Enemy>, With<Star>)>;
+
+/// Finds spawn positions that keep clear of the players and of other enemies and stars.
+#[derive(SystemParam)]
+pub struct SpawnPlacer<'w, 's> {
+    arena: Res<'w, Arena>,
+    game_rng: ResMut<'w, GameRng>,
+    player_query: Query<'w, 's, &'static Transform, With<Player>>,
+    obstacle_query: Query<'w, 's, &'static Transform, Obstacles>,
+}
+
+impl SpawnPlacer<'_, '_> {
+    /// Returns `count` positions for characters of the given size that keep clear of each other.
+    pub fn positions(&mut self, count: usize, size: f32, placement: SpawnPlacement) -> Vec<Vec2> {
+        let mut positions: Vec<Vec2> = Vec::with_capacity(count);
+        for _ in 0..count {
+            let position = self.position_avoiding(size, placement, &positions);
+            positions.push(position);
+        }
+        positions
+    }
+
+    pub fn position(&mut self, size: f32, placement: SpawnPlacement) -> Vec2 {
+        self.position_avoiding(size, placement, &[])
+    }
+
+    /// Position just outside the arena border from which an entity at `position` enters.
+    pub fn entry_position(&self, position: Vec2, size: f32) -> Vec2 {
+        let [x_min, x_max, y_min, y_max] = self.arena.confinement(size);
+        let distances = [
+            (position.x - x_min, Vec2::new(-size, position.y)),
+            (
+                x_max - position.x,
+                Vec2::new(self.arena.width + size, position.y),
+            ),
+            (position.y - y_min, Vec2::new(position.x, -size)),
+            (
+                y_max - position.y,
+                Vec2::new(position.x, self.arena.height + size),
+            ),
+        ];
+        distances
+            .into_iter()
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, entry_position)| entry_position)
+            .unwrap_or(position)
+    }
+
+    fn position_avoiding(&mut self, size: f32, placement: SpawnPlacement, placed: &[Vec2]) -> Vec2 {
+        let mut best_position = self.arena.center();
+        let mut best_clearance = f32::NEG_INFINITY;
+
+        for _ in 0..MAX_PLACEMENT_ATTEMPTS {
+            let candidate = self.candidate(size, placement);
+            let clearance = self.clearance(candidate, size, placed);
+            if clearance >= 0.0 {
+                return candidate;
+            }
+            if clearance > best_clearance {
+                best_position = candidate;
+                best_clearance = clearance;
+            }
+        }
+        best_position
+    }
+
+    fn candidate(&mut self, size: f32, placement: SpawnPlacement) -> Vec2 {
+        let [x_min, x_max, y_min, y_max] = self.arena.confinement(size);
+        let rng = &mut self.game_rng.rng;
+
+        match placement {
+            SpawnPlacement::Anywhere => {
+                Vec2::new(rng.gen_range(x_min..=x_max), rng.gen_range(y_min..=y_max))
+            }
+            SpawnPlacement::Edge => match rng.gen_range(0..4) {
+                0 => Vec2::new(x_min, rng.gen_range(y_min..=y_max)),
+                1 => Vec2::new(x_max, rng.gen_range(y_min..=y_max)),
+                2 => Vec2::new(rng.gen_range(x_min..=x_max), y_min),
+                _ => Vec2::new(rng.gen_range(x_min..=x_max), y_max),
+            },
+        }
+    }
+
+    /// Smallest distance to the required clearances, negative if one of them is violated.
+    fn clearance(&self, candidate: Vec2, size: f32, placed: &[Vec2]) -> f32 {
+        // The players are spawned around the arena center and might not exist yet when entering the game
+        let player_distance = self
+            .player_query
+            .iter()
+            .map(|player_transform| candidate.distance(player_transform.translation.truncate()))
+            .reduce(f32::min)
+            .unwrap_or_else(|| candidate.distance(self.arena.center()));
+        let player_clearance = player_distance - PLAYER_CLEARANCE;
+
+        let obstacle_positions = self
+            .obstacle_query
+            .iter()
+            .map(|transform| transform.translation.truncate())
+            .chain(placed.iter().copied());
+        // Enemies are the largest obstacles, so their size keeps every overlap check conservative
+        let obstacle_distance = (size + ENEMY_SIZE) / 2.0 + ENTITY_CLEARANCE;
+        let obstacle_clearance = obstacle_positions
+            .map(|position| candidate.distance(position) - obstacle_distance)
+            .fold(f32::INFINITY, f32::min);
+
+        player_clearance.min(obstacle_clearance)
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use super::{
+    components::{EnteringArena, Telegraphing},
+    TELEGRAPH_BLINK_RATE,
+};
+use crate::{
+    game::enemy::{components::Enemy, ENEMY_SIZE},
+    settings::resources::Settings,
+};
+
+const TELEGRAPH_MARKER_COLOR: Color = Color::srgb(1.0, 0.3, 0.2);
+
+pub fn update_telegraphs(
+    mut commands: Commands,
+    mut telegraph_query: Query<(
+        Entity,
+        &mut Telegraphing,
+        &mut Sprite,
+        &mut Transform,
+        Option<&EnteringArena>,
+    )>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+) {
+    for (entity, mut telegraphing, mut sprite, mut transform, entering_arena) in
+        &mut telegraph_query
+    {
+        telegraphing.timer.tick(time.delta());
+        let progress = telegraphing.timer.fraction();
+
+        if let Some(entering_arena) = entering_arena {
+            let position = entering_arena.from.lerp(entering_arena.to, progress);
+            transform.translation = position.extend(transform.translation.z);
+        }
+
+        if telegraphing.timer.finished() {
+            sprite.color.set_alpha(1.0);
+            commands
+                .entity(entity)
+                .remove::<(Telegraphing, EnteringArena)>();
+            continue;
+        }
+
+        let alpha = match settings.reduced_motion {
+            true => 0.5,
+            false => {
+                let blink = (telegraphing.timer.elapsed_secs()
+                    * TELEGRAPH_BLINK_RATE
+                    * std::f32::consts::TAU)
+                    .cos();
+                0.5 + 0.25 * blink
+            }
+        };
+        sprite.color.set_alpha(alpha);
+    }
+}
+
+pub fn draw_telegraph_markers(
+    mut gizmos: Gizmos,
+    telegraph_query: Query<(&Telegraphing, Option<&EnteringArena>, &Transform), With<Enemy>>,
+) {
+    for (telegraphing, entering_arena, transform) in &telegraph_query {
+        let target = entering_arena
+            .map(|entering_arena| entering_arena.to)
+            .unwrap_or(transform.translation.truncate());
+        // The marker shrinks onto the enemy until it becomes dangerous
+        let radius = ENEMY_SIZE / 2.0 * (2.0 - telegraphing.timer.fraction());
+        gizmos.circle_2d(target, radius, TELEGRAPH_MARKER_COLOR);
+    }
+}
@@ -1,4 +1,22 @@
 use bevy::prelude::*;
 
+use super::STAR_LIFETIME;
+use crate::game::run::components::RunScoped;
+
 #[derive(Component)]
+#[require(RunScoped)]
 pub struct Star;
+
+/// Time left until an uncollected star disappears.
+#[derive(Component, Debug)]
+pub struct StarLifetime {
+    pub timer: Timer,
+}
+
+impl Default for StarLifetime {
+    fn default() -> StarLifetime {
+        StarLifetime {
+            timer: Timer::from_seconds(STAR_LIFETIME, TimerMode::Once),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 pub mod components;
-mod resources;
+pub mod resources;
 mod systems;
 
 use bevy::prelude::*;
@@ -7,24 +7,49 @@ use bevy::prelude::*;
 use resources::*;
 use systems::*;
 
-use super::SimulationState;
-use crate::AppState;
+pub use systems::{create_star_bundle, spawn_stars_over_time};
+
+use super::daily::apply_daily_challenge;
+use super::player::check_player_health;
+use super::run::RunStart;
+use super::save::resources::PendingRestore;
+use super::simulation::SimulationStep;
+use super::systems::roll_run_seed;
 
 pub const STAR_SIZE: f32 = 30.0; // this is the size of the star sprite
 const NUMBER_OF_STARS: usize = 10;
+/// Default for the maximum number of stars in the arena at the same time.
+pub const MAX_STARS: usize = 20;
+/// Seconds a star stays in the arena before it's missed.
+pub const STAR_LIFETIME: f32 = 8.0;
+/// Seconds before expiring during which a star blinks.
+const STAR_BLINK_TIME: f32 = 2.0;
+const STAR_BLINK_RATE: f32 = 5.0;
 
 pub struct StarPlugin;
 
 impl Plugin for StarPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<StarSpawnTimer>()
-            .add_systems(OnEnter(AppState::Game), spawn_stars)
             .add_systems(
-                Update,
-                (tick_star_spawn_timer, spawn_stars_over_time)
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
+                RunStart,
+                (
+                    reset_star_spawn_timer
+                        .after(apply_daily_challenge)
+                        .before(roll_run_seed),
+                    spawn_stars
+                        .after(roll_run_seed)
+                        .run_if(not(resource_exists::<PendingRestore>)),
+                ),
             )
-            .add_systems(OnExit(AppState::Game), despawn_stars);
+            .add_systems(
+                SimulationStep,
+                (
+                    expire_stars,
+                    (tick_star_spawn_timer, spawn_stars_over_time).chain(),
+                )
+                    .chain()
+                    .after(check_player_health),
+            );
     }
 }
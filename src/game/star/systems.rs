@@ -1,62 +1,99 @@
-use bevy::{prelude::*, window::PrimaryWindow};
-use rand::Rng;
+use bevy::prelude::*;
 
 use super::components::*;
 use super::resources::*;
-use super::{NUMBER_OF_STARS, STAR_SIZE};
-use crate::utils;
+use super::{NUMBER_OF_STARS, STAR_BLINK_RATE, STAR_BLINK_TIME, STAR_SIZE};
+use crate::game::daily::resources::ActiveModifiers;
+use crate::game::resources::RunSettings;
+use crate::game::score::resources::Score;
+use crate::game::spawning::{SpawnPlacement, SpawnPlacer};
+use crate::loading::resources::GameAssets;
+use crate::settings::resources::Settings;
+
+pub fn create_star_bundle(
+    game_assets: &GameAssets,
+    x_position: f32,
+    y_position: f32,
+) -> impl Bundle {
+    (
+        Sprite::from_image(game_assets.star_sprite.clone()),
+        Transform::from_xyz(x_position, y_position, 0.0),
+        Star,
+        StarLifetime::default(),
+    )
+}
 
 pub fn spawn_stars(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    mut spawn_placer: SpawnPlacer,
 ) {
-    let window: &Window = window_query.get_single().unwrap();
-    let [x_min, x_max, y_min, y_max] = utils::get_confinement(window, STAR_SIZE);
-    let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
-
-    for _ in 0..NUMBER_OF_STARS {
-        let x_position: f32 = rng.gen_range(x_min..=x_max);
-        let y_position: f32 = rng.gen_range(y_min..=y_max);
-
-        commands.spawn((
-            Sprite::from_image(asset_server.load("sprites/star.png")),
-            Transform::from_xyz(x_position, y_position, 0.0),
-            Star,
-        ));
+    let positions = spawn_placer.positions(NUMBER_OF_STARS, STAR_SIZE, SpawnPlacement::Anywhere);
+
+    for position in positions {
+        commands.spawn(create_star_bundle(&game_assets, position.x, position.y));
     }
 }
 
+pub fn reset_star_spawn_timer(
+    mut star_spawn_timer: ResMut<StarSpawnTimer>,
+    active_modifiers: Res<ActiveModifiers>,
+) {
+    star_spawn_timer.timer = Timer::from_seconds(
+        STAR_SPAWN_TIME * active_modifiers.star_spawn_time_factor(),
+        TimerMode::Repeating,
+    );
+}
+
 pub fn tick_star_spawn_timer(mut star_spawn_timer: ResMut<StarSpawnTimer>, time: Res<Time>) {
     star_spawn_timer.timer.tick(time.delta());
 }
 
 pub fn spawn_stars_over_time(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
     star_spawn_timer: Res<StarSpawnTimer>,
+    mut spawn_placer: SpawnPlacer,
+    star_query: Query<(), With<Star>>,
+    run_settings: Res<RunSettings>,
 ) {
-    if !star_spawn_timer.timer.finished() {
+    if !star_spawn_timer.timer.finished() || star_query.iter().count() >= run_settings.max_stars {
         return;
     }
 
-    let window: &Window = window_query.get_single().unwrap();
-    let [x_min, x_max, y_min, y_max] = utils::get_confinement(window, STAR_SIZE);
-    let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
+    let position = spawn_placer.position(STAR_SIZE, SpawnPlacement::Anywhere);
+    commands.spawn(create_star_bundle(&game_assets, position.x, position.y));
+}
 
-    let x_position: f32 = rng.gen_range(x_min..=x_max);
-    let y_position: f32 = rng.gen_range(y_min..=y_max);
+/// Blinks stars shortly before their lifetime ends and removes them as missed afterwards.
+pub fn expire_stars(
+    mut commands: Commands,
+    mut star_query: Query<(Entity, &mut StarLifetime, &mut Sprite)>,
+    mut score: ResMut<Score>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+) {
+    for (star_entity, mut star_lifetime, mut sprite) in &mut star_query {
+        star_lifetime.timer.tick(time.delta());
 
-    commands.spawn((
-        Sprite::from_image(asset_server.load("sprites/star.png")),
-        Transform::from_xyz(x_position, y_position, 0.0),
-        Star,
-    ));
-}
+        if star_lifetime.timer.finished() {
+            commands.entity(star_entity).despawn();
+            score.missed += 1;
+            continue;
+        }
 
-pub fn despawn_stars(mut commands: Commands, star_query: Query<Entity, With<Star>>) {
-    for star_entity in &star_query {
-        commands.entity(star_entity).despawn();
+        let remaining = star_lifetime.timer.remaining_secs();
+        if remaining > STAR_BLINK_TIME {
+            continue;
+        }
+        let alpha = match settings.reduced_motion {
+            // Fade out instead of blinking
+            true => remaining / STAR_BLINK_TIME,
+            false => match (remaining * STAR_BLINK_RATE).fract() < 0.5 {
+                true => 0.25,
+                false => 1.0,
+            },
+        };
+        sprite.color.set_alpha(alpha);
     }
 }
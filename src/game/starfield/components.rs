@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+use crate::game::run::components::RunScoped;
+
+#[derive(Component)]
+#[require(RunScoped)]
+pub struct BackgroundStar {
+    pub base_position: Vec2,
+    pub parallax_factor: f32,
+    pub brightness: f32,
+    pub twinkle_speed: f32,
+    pub twinkle_phase: f32,
+}
@@ -0,0 +1,40 @@
+mod components;
+mod systems;
+
+use bevy::prelude::*;
+
+use systems::*;
+
+use super::run::RunStart;
+use super::systems::roll_run_seed;
+use crate::{arena::resources::Arena, settings::resources::Settings, AppState};
+
+/// Parallax factor, number of stars, star size and brightness of each background layer.
+const STARFIELD_LAYERS: [(f32, usize, f32, f32); 3] = [
+    (0.02, 120, 1.5, 0.35),
+    (0.05, 60, 2.5, 0.6),
+    (0.1, 25, 3.5, 0.9),
+];
+const STARFIELD_Z: f32 = -10.0;
+
+pub struct StarfieldPlugin;
+
+impl Plugin for StarfieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(RunStart, spawn_starfield.after(roll_run_seed))
+            .add_systems(
+                Update,
+                (scroll_starfield, twinkle_starfield)
+                    .run_if(|settings: Res<Settings>| !settings.reduced_motion)
+                    .run_if(in_state(AppState::Game)),
+            )
+            .add_systems(
+                Update,
+                // Regenerate the starfield from the run seed so that it covers the resized arena
+                (despawn_starfield, spawn_starfield)
+                    .chain()
+                    .run_if(resource_changed::<Arena>)
+                    .run_if(in_state(AppState::Game)),
+            );
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use super::components::BackgroundStar;
+use super::{STARFIELD_LAYERS, STARFIELD_Z};
+use crate::arena::resources::Arena;
+use crate::game::player::components::Player;
+use crate::game::resources::RunSeed;
+
+pub fn spawn_starfield(mut commands: Commands, arena: Res<Arena>, run_seed: Res<RunSeed>) {
+    let mut rng = ChaCha8Rng::seed_from_u64(run_seed.value);
+
+    for (layer_index, (parallax_factor, number_of_stars, size, brightness)) in
+        STARFIELD_LAYERS.into_iter().enumerate()
+    {
+        // Stars cover a margin around the arena so that scrolling never reveals empty space.
+        let x_margin = arena.width * parallax_factor;
+        let y_margin = arena.height * parallax_factor;
+
+        for _ in 0..number_of_stars {
+            let base_position = Vec2::new(
+                rng.gen_range(-x_margin..=arena.width + x_margin),
+                rng.gen_range(-y_margin..=arena.height + y_margin),
+            );
+
+            commands.spawn((
+                Sprite {
+                    color: Color::srgba(1.0, 1.0, 1.0, brightness),
+                    custom_size: Some(Vec2::splat(size)),
+                    ..default()
+                },
+                Transform::from_translation(base_position.extend(STARFIELD_Z - layer_index as f32)),
+                BackgroundStar {
+                    base_position,
+                    parallax_factor,
+                    brightness,
+                    twinkle_speed: rng.gen_range(0.5..=3.0),
+                    twinkle_phase: rng.gen_range(0.0..=std::f32::consts::TAU),
+                },
+            ));
+        }
+    }
+}
+
+pub fn scroll_starfield(
+    mut star_query: Query<(&mut Transform, &BackgroundStar), Without<Player>>,
+    player_query: Query<&Transform, With<Player>>,
+    arena: Res<Arena>,
+) {
+    if player_query.is_empty() {
+        return;
+    }
+    // The parallax follows the center of all players
+    let player_center = player_query
+        .iter()
+        .map(|player_transform| player_transform.translation.truncate())
+        .sum::<Vec2>()
+        / player_query.iter().count() as f32;
+    let player_offset = player_center - arena.center();
+
+    for (mut star_transform, star) in &mut star_query {
+        let position = star.base_position - player_offset * star.parallax_factor;
+        star_transform.translation.x = position.x;
+        star_transform.translation.y = position.y;
+    }
+}
+
+pub fn twinkle_starfield(mut star_query: Query<(&mut Sprite, &BackgroundStar)>, time: Res<Time>) {
+    for (mut sprite, star) in &mut star_query {
+        let twinkle = (time.elapsed_secs() * star.twinkle_speed + star.twinkle_phase).sin();
+        sprite
+            .color
+            .set_alpha(star.brightness * (0.75 + 0.25 * twinkle));
+    }
+}
+
+pub fn despawn_starfield(mut commands: Commands, star_query: Query<Entity, With<BackgroundStar>>) {
+    for star_entity in &star_query {
+        commands.entity(star_entity).despawn();
+    }
+}
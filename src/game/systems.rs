@@ -1,6 +1,43 @@
-use bevy::prelude::*;
+use bevy::{
+    input::gamepad::{GamepadConnection, GamepadConnectionEvent},
+    prelude::*,
+    window::{WindowFocused, WindowOccluded},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
+use super::daily::resources::DailyChallenge;
+use super::enemy::{components::Enemy, ENEMY_SIZE};
+use super::mode::GameMode;
+use super::player::{components::Player, PLAYER_SIZE};
+use super::resources::{GameRng, RunSeed, RunSettings};
+use super::save::resources::PendingRestore;
+use super::star::{components::Star, STAR_SIZE};
 use super::SimulationState;
+use crate::arena::resources::Arena;
+use crate::settings::resources::Settings;
+
+const PLAYER_OUTLINE_COLOR: Color = Color::WHITE;
+
+pub fn roll_run_seed(
+    mut run_seed: ResMut<RunSeed>,
+    mut game_rng: ResMut<GameRng>,
+    run_settings: Res<RunSettings>,
+    daily_challenge: Res<DailyChallenge>,
+    pending_restore: Option<Res<PendingRestore>>,
+) {
+    if let Some(pending_restore) = pending_restore {
+        run_seed.value = pending_restore.snapshot.seed;
+        game_rng.rng = pending_restore.snapshot.rng.clone();
+        return;
+    }
+
+    run_seed.value = match run_settings.mode {
+        GameMode::DailyChallenge => daily_challenge.seed,
+        _ => run_settings.seed.unwrap_or_else(rand::random),
+    };
+    game_rng.rng = ChaCha8Rng::seed_from_u64(run_seed.value);
+}
 
 pub fn toggle_simulation(
     mut next_simulation_state: ResMut<NextState<SimulationState>>,
@@ -10,10 +47,107 @@ pub fn toggle_simulation(
     if keyboard_input.just_pressed(KeyCode::Space) {
         if *simulation_state.get() == SimulationState::Running {
             next_simulation_state.set(SimulationState::Paused);
-            println!("Simulation is paused.");
+            info!(target: "simulation", "Simulation is paused.");
         } else if *simulation_state.get() == SimulationState::Paused {
             next_simulation_state.set(SimulationState::Running);
-            println!("Simulation is running.");
+            info!(target: "simulation", "Simulation is running.");
+        }
+    }
+}
+
+/// Pauses the running game when the window loses focus, gets minimized or a gamepad disconnects.
+pub fn auto_pause_simulation(
+    mut window_focused_event_reader: EventReader<WindowFocused>,
+    mut window_occluded_event_reader: EventReader<WindowOccluded>,
+    mut gamepad_connection_event_reader: EventReader<GamepadConnectionEvent>,
+    mut next_simulation_state: ResMut<NextState<SimulationState>>,
+    simulation_state: Res<State<SimulationState>>,
+    settings: Res<Settings>,
+) {
+    // Read all events so that old ones don't pause the game once it runs again
+    let focus_lost = window_focused_event_reader
+        .read()
+        .any(|event| !event.focused);
+    let occluded = window_occluded_event_reader
+        .read()
+        .any(|event| event.occluded);
+    let gamepad_disconnected = gamepad_connection_event_reader
+        .read()
+        .any(|event| event.connection == GamepadConnection::Disconnected);
+
+    if !settings.auto_pause || *simulation_state.get() != SimulationState::Running {
+        return;
+    }
+    if focus_lost || occluded || gamepad_disconnected {
+        next_simulation_state.set(SimulationState::Paused);
+        info!(target: "simulation", "Simulation is paused automatically.");
+    }
+}
+
+type ArenaEntities = Or<(With<Player>, With<Enemy>, With<Star>)>;
+
+/// Moves the player, enemies and stars back into the arena after it got smaller.
+pub fn keep_entities_in_arena(
+    mut entity_query: Query<(&mut Transform, Has<Player>, Has<Enemy>), ArenaEntities>,
+    arena: Res<Arena>,
+) {
+    for (mut transform, is_player, is_enemy) in &mut entity_query {
+        let size = match (is_player, is_enemy) {
+            (true, _) => PLAYER_SIZE,
+            (_, true) => ENEMY_SIZE,
+            _ => STAR_SIZE,
+        };
+        transform.translation = arena.confine(transform.translation, size);
+    }
+}
+
+pub fn apply_color_palette(
+    mut enemy_query: Query<(&mut Sprite, Ref<Enemy>), Without<Star>>,
+    mut star_query: Query<(&mut Sprite, Ref<Star>), Without<Enemy>>,
+    settings: Res<Settings>,
+) {
+    let palette = settings.color_palette;
+
+    for (mut sprite, enemy) in &mut enemy_query {
+        if settings.is_changed() || enemy.is_added() {
+            sprite.color = palette.enemy_color();
         }
     }
+    for (mut sprite, star) in &mut star_query {
+        if settings.is_changed() || star.is_added() {
+            sprite.color = palette.pickup_color();
+        }
+    }
+}
+
+pub fn draw_high_contrast_outlines(
+    mut gizmos: Gizmos,
+    player_query: Query<&Transform, With<Player>>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    star_query: Query<&Transform, With<Star>>,
+    settings: Res<Settings>,
+) {
+    let palette = settings.color_palette;
+
+    for player_transform in &player_query {
+        gizmos.circle_2d(
+            player_transform.translation.truncate(),
+            PLAYER_SIZE / 2.0,
+            PLAYER_OUTLINE_COLOR,
+        );
+    }
+    for enemy_transform in &enemy_query {
+        gizmos.circle_2d(
+            enemy_transform.translation.truncate(),
+            ENEMY_SIZE / 2.0,
+            palette.enemy_outline_color(),
+        );
+    }
+    for star_transform in &star_query {
+        gizmos.circle_2d(
+            star_transform.translation.truncate(),
+            STAR_SIZE / 2.0,
+            palette.pickup_outline_color(),
+        );
+    }
 }
@@ -3,11 +3,5 @@ use bevy::prelude::*;
 #[derive(Component)]
 pub struct GameOverMenu;
 
-#[derive(Component)]
-pub struct GameOverRestartButton;
-
-#[derive(Component)]
-pub struct GameOverMainMenuButton;
-
 #[derive(Component)]
 pub struct FinalScoreInfo;
@@ -2,48 +2,103 @@ use bevy::prelude::*;
 
 use crate::{
     events::GameOver,
-    game::ui::game_over_menu::{components::*, styles::*},
+    game::{
+        mode::GameMode,
+        player::components::PlayerId,
+        resources::RunSettings,
+        score::resources::HighScores,
+        ui::game_over_menu::{components::*, styles::*},
+        versus::resources::{RoundOutcome, VersusMatch},
+    },
+    loading::resources::GameAssets,
+    localization::components::LocalizedText,
+    network::resources::RollbackSession,
+    tween::{components::UiTween, despawn_with_tween, MENU_TRANSITION, MENU_TRANSITION_DURATION},
+    widgets::{
+        components::ButtonAction,
+        resources::{UiFont, UiTheme},
+        spawn_menu_button,
+        styles::{MENU_NODE, MENU_TITLE_NODE},
+        text_bundle,
+    },
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_game_over_menu(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
     game_over_event_reader: EventReader<GameOver>,
+    run_settings: Res<RunSettings>,
+    high_scores: Res<HighScores>,
+    rollback_session: Option<Res<RollbackSession>>,
 ) {
-    let final_score: u32 = get_final_score(game_over_event_reader);
+    let final_score = get_final_score(game_over_event_reader);
+    let summary = RunSummary {
+        mode: run_settings.mode,
+        final_score,
+        best_score: high_scores.best(run_settings.mode).unwrap_or_default(),
+        new_best: high_scores.is_new_best(run_settings.mode, final_score),
+        // Daily challenges only have a single attempt, rollback peers can't restart together
+        restartable: run_settings.mode != GameMode::DailyChallenge && rollback_session.is_none(),
+    };
     let _game_over_menu_entity: Entity =
-        build_game_over_menu(&mut commands, &asset_server, final_score);
+        build_game_over_menu(&mut commands, &game_assets, &ui_theme, &ui_font, &summary);
 }
 
-pub fn despawn_game_over_menu(mut commands: Commands, query: Query<Entity, With<GameOverMenu>>) {
-    if let Ok(game_over_menu_entity) = query.get_single() {
-        commands.entity(game_over_menu_entity).despawn_recursive();
+pub fn spawn_versus_results(
+    mut commands: Commands,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+    run_settings: Res<RunSettings>,
+    versus_match: Res<VersusMatch>,
+    rollback_session: Option<Res<RollbackSession>>,
+) {
+    let _versus_results_entity: Entity = build_versus_results(
+        &mut commands,
+        &ui_theme,
+        &ui_font,
+        &versus_match,
+        &run_settings,
+        rollback_session.is_none(),
+    );
+}
+
+pub fn despawn_game_over_menu(
+    mut commands: Commands,
+    query: Query<Entity, With<GameOverMenu>>,
+    mut tween_query: Query<&mut UiTween>,
+) {
+    for game_over_menu_entity in &query {
+        despawn_with_tween(&mut commands, &mut tween_query, game_over_menu_entity);
     }
 }
 
 fn build_game_over_menu(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    final_score: u32,
+    game_assets: &GameAssets,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    summary: &RunSummary,
 ) -> Entity {
     commands
-        .spawn((GAME_OVER_MENU_NODE, GameOverMenu))
+        .spawn((
+            MENU_NODE,
+            GameOverMenu,
+            UiTween::new(MENU_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
         .with_children(|parent| {
             // Game over menu title
-            parent.spawn(TITLE_NODE).with_child((
-                Text::new("Game Over!"),
-                TextFont {
-                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    font_size: 64.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
+            parent.spawn(MENU_TITLE_NODE).with_child((
+                text_bundle("", ui_font, ui_theme.title_font_size, ui_theme.text_color),
+                LocalizedText::new("game_over_menu.title"),
             ));
             // Final score info
             parent.spawn(INFO_ITEM_NODE).with_children(|parent| {
                 parent.spawn((
                     ImageNode {
-                        image: asset_server.load("sprites/star.png"),
+                        image: game_assets.star_sprite.clone(),
                         ..default()
                     },
                     Node {
@@ -54,62 +109,144 @@ fn build_game_over_menu(
                     },
                 ));
                 parent.spawn((
-                    Text::new(format! {"Final score: {:?}", final_score}),
-                    TextFont {
-                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 44.0,
-                        ..default()
-                    },
-                    TextColor(Color::WHITE),
+                    text_bundle("", ui_font, ui_theme.heading_font_size, ui_theme.text_color),
+                    LocalizedText::new(summary_key(summary.mode))
+                        .with_arg("score", summary.final_score),
                     FinalScoreInfo,
                 ));
             });
+            // Best score of the mode, which already includes this run
+            parent.spawn((
+                text_bundle(
+                    "",
+                    ui_font,
+                    ui_theme.info_font_size,
+                    ui_theme.muted_text_color,
+                ),
+                match summary.new_best {
+                    true => LocalizedText::new("game_over_menu.new_best"),
+                    false => LocalizedText::new("game_over_menu.best")
+                        .with_arg("score", summary.best_score),
+                },
+            ));
             // Restart button
-            parent
-                .spawn((
-                    NORMAL_BUTTON_NODE,
-                    Button,
-                    GameOverRestartButton,
-                    BorderRadius::all(Val::Px(10.0)),
-                ))
-                .with_child((
-                    Text::new("Restart (R)"),
-                    TextFont {
-                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 32.0,
-                        ..default()
-                    },
-                    TextColor(Color::WHITE),
-                    TextLayout {
-                        justify: JustifyText::Center,
-                        ..default()
-                    },
-                ));
+            if summary.restartable {
+                spawn_menu_button(
+                    parent,
+                    ui_theme,
+                    ui_font,
+                    LocalizedText::new("menu.restart"),
+                    ButtonAction::Restart,
+                );
+            }
             // Main menu button
-            parent
-                .spawn((
-                    NORMAL_BUTTON_NODE,
-                    Button,
-                    GameOverMainMenuButton,
-                    BorderRadius::all(Val::Px(10.0)),
-                ))
-                .with_child((
-                    Text::new("Main Menu (M)"),
-                    TextFont {
-                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 32.0,
-                        ..default()
-                    },
-                    TextColor(Color::WHITE),
-                    TextLayout {
-                        justify: JustifyText::Center,
-                        ..default()
-                    },
+            spawn_menu_button(
+                parent,
+                ui_theme,
+                ui_font,
+                LocalizedText::new("menu.main_menu"),
+                ButtonAction::MainMenu,
+            );
+        })
+        .id()
+}
+
+fn build_versus_results(
+    commands: &mut Commands,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    versus_match: &VersusMatch,
+    run_settings: &RunSettings,
+    restartable: bool,
+) -> Entity {
+    let best_of = run_settings.best_of;
+    let match_winner = versus_match.winner(best_of);
+
+    commands
+        .spawn((
+            MENU_NODE,
+            GameOverMenu,
+            UiTween::new(MENU_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
+        .with_children(|parent| {
+            // Winner of the match, or of the round while the match goes on
+            parent.spawn(MENU_TITLE_NODE).with_child((
+                text_bundle("", ui_font, ui_theme.title_font_size, ui_theme.text_color),
+                match (match_winner, versus_match.round_outcome) {
+                    (Some(player_id), _) => LocalizedText::new("versus.match_winner")
+                        .with_arg("number", player_id.number()),
+                    (None, Some(RoundOutcome::Winner(player_id))) => {
+                        LocalizedText::new("versus.round_winner")
+                            .with_arg("number", player_id.number())
+                            .with_arg("round", versus_match.rounds_played)
+                    }
+                    (None, _) => LocalizedText::new("versus.round_draw")
+                        .with_arg("round", versus_match.rounds_played),
+                },
+            ));
+            // Stars and round wins of each player
+            for index in 0..run_settings.player_count() {
+                parent.spawn((
+                    text_bundle("", ui_font, ui_theme.heading_font_size, ui_theme.text_color),
+                    LocalizedText::new("versus.player_result")
+                        .with_arg("number", PlayerId(index).number())
+                        .with_arg("stars", versus_match.round_stars[index])
+                        .with_arg("wins", versus_match.wins[index]),
                 ));
+            }
+            parent.spawn((
+                text_bundle(
+                    "",
+                    ui_font,
+                    ui_theme.info_font_size,
+                    ui_theme.muted_text_color,
+                ),
+                LocalizedText::new("versus.best_of").with_arg("rounds", best_of),
+            ));
+            // Next round or rematch button
+            if restartable {
+                spawn_menu_button(
+                    parent,
+                    ui_theme,
+                    ui_font,
+                    match match_winner {
+                        Some(_) => LocalizedText::new("versus.rematch"),
+                        None => LocalizedText::new("versus.next_round"),
+                    },
+                    ButtonAction::Restart,
+                );
+            }
+            // Main menu button
+            spawn_menu_button(
+                parent,
+                ui_theme,
+                ui_font,
+                LocalizedText::new("menu.main_menu"),
+                ButtonAction::MainMenu,
+            );
         })
         .id()
 }
 
+/// Result of the finished run shown in the game over menu.
+struct RunSummary {
+    mode: GameMode,
+    final_score: u32,
+    best_score: u32,
+    /// Whether the final score beat the best score instead of only tying it.
+    new_best: bool,
+    restartable: bool,
+}
+
+fn summary_key(mode: GameMode) -> &'static str {
+    match mode {
+        GameMode::Endless | GameMode::Versus => "game_over_menu.final_score",
+        GameMode::TimeAttack => "game_over_menu.time_attack_score",
+        GameMode::Zen => "game_over_menu.zen_score",
+        GameMode::DailyChallenge => "game_over_menu.daily_challenge_score",
+    }
+}
+
 fn get_final_score(mut game_over_event_reader: EventReader<GameOver>) -> u32 {
     if let Some(event) = game_over_event_reader.read().next() {
         return event.score;
@@ -1,2 +1 @@
-pub mod interactions;
 pub mod layout;
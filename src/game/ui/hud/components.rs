@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::game::player::components::PlayerId;
+
 #[derive(Component)]
 pub struct GameInfoHUD;
 
@@ -7,10 +9,20 @@ pub struct GameInfoHUD;
 pub struct ScoreInfo;
 
 #[derive(Component)]
-pub struct HealthInfo;
+pub struct MissedStarsInfo;
+
+#[derive(Component)]
+pub struct HealthInfo(pub PlayerId);
+
+/// Stars collected by a single player in co-op.
+#[derive(Component)]
+pub struct PlayerScoreInfo(pub PlayerId);
 
 #[derive(Component)]
 pub struct EnemyNumberInfo;
 
+#[derive(Component)]
+pub struct TimeLimitInfo;
+
 #[derive(Component)]
 pub struct ControlsHUD;
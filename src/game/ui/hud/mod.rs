@@ -4,7 +4,7 @@ mod systems;
 
 use bevy::prelude::*;
 
-use crate::game::SimulationState;
+use crate::game::{mode::resources::TimeLimit, SimulationState};
 use crate::AppState;
 use systems::layout::spawn_game_hud;
 use systems::updates::*;
@@ -20,6 +20,9 @@ impl Plugin for GameHUDPlugin {
                 update_enemy_number_info,
                 update_health_info,
                 update_score_info,
+                update_player_score_info,
+                update_missed_stars_info,
+                update_time_limit_info.run_if(resource_exists::<TimeLimit>),
             )
                 .run_if(in_state(AppState::Game))
                 .run_if(in_state(SimulationState::Running)),
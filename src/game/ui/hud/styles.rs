@@ -1,20 +1,5 @@
-use crate::Srgba;
 use bevy::prelude::*;
 
-pub const CONTROLS_INFO_COLORS: Srgba = Srgba {
-    red: 1.0,
-    green: 1.0,
-    blue: 1.0,
-    alpha: 0.5,
-};
-
-pub const INFO_BAR_COLOR: Srgba = Srgba {
-    red: 0.25,
-    green: 0.25,
-    blue: 0.25,
-    alpha: 0.7,
-}; // Color::DARK_GRAY with smaller alpha
-
 pub const INFO_HUD_NODE: Node = {
     let mut node = Node::DEFAULT;
     node.width = Val::Percent(100.0);
@@ -26,7 +11,7 @@ pub const INFO_HUD_NODE: Node = {
 pub const INFO_BAR_NODE: Node = {
     let mut node = Node::DEFAULT;
     node.width = Val::Percent(8.0);
-    node.height = Val::Percent(20.0);
+    node.height = Val::Percent(26.0);
     node.top = Val::Px(10.0);
     node.left = Val::Px(10.0);
     node.flex_direction = FlexDirection::Column;
@@ -41,3 +26,42 @@ pub const INFO_ITEM_NODE: Node = {
     node.column_gap = Val::Px(10.0);
     node
 };
+
+pub const PLAYER_PANELS_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.position_type = PositionType::Absolute;
+    node.top = Val::Px(10.0);
+    node.right = Val::Px(10.0);
+    node.flex_direction = FlexDirection::Column;
+    node.row_gap = Val::Px(10.0);
+    node
+};
+
+pub const PLAYER_PANEL_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.padding = UiRect::all(Val::Px(10.0));
+    node.flex_direction = FlexDirection::Column;
+    node.align_items = AlignItems::Center;
+    node.row_gap = Val::Px(5.0);
+    node
+};
+
+pub const TIME_LIMIT_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.position_type = PositionType::Absolute;
+    node.width = Val::Percent(100.0);
+    node.top = Val::Px(10.0);
+    node.justify_content = JustifyContent::Center;
+    node
+};
+
+pub const TIME_LIMIT_PANEL_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.padding = UiRect {
+        left: Val::Px(20.0),
+        right: Val::Px(20.0),
+        top: Val::Px(5.0),
+        bottom: Val::Px(5.0),
+    };
+    node
+};
@@ -1,34 +1,90 @@
 use bevy::prelude::*;
 
-use crate::game::{
-    enemy::{ENEMY_SPRITE, INITIAL_NUMBER_OF_ENEMIES},
-    player::INITIAL_PLAYER_HEALTH,
-    score::resources::Score,
-    ui::hud::{components::*, styles::*},
+use crate::{
+    game::{
+        enemy::INITIAL_NUMBER_OF_ENEMIES,
+        player::{components::PlayerId, Lives, INITIAL_PLAYER_HEALTH},
+        resources::RunSettings,
+        score::resources::Score,
+        ui::hud::{components::*, styles::*},
+    },
+    loading::resources::GameAssets,
+    localization::components::LocalizedText,
+    tween::{
+        components::{AnimatedCounter, UiTween},
+        HUD_TRANSITION, MENU_TRANSITION_DURATION,
+    },
+    widgets::{
+        resources::{UiFont, UiTheme},
+        text_bundle,
+    },
 };
 
-pub fn spawn_game_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let _hud_entity: Entity = build_info_hud(&mut commands, &asset_server);
-    let _controls_entity: Entity = build_controls_hud(&mut commands, &asset_server);
+pub fn spawn_game_hud(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+    run_settings: Res<RunSettings>,
+) {
+    // Players with separate lives show their health in their own panels
+    let separate_health =
+        run_settings.is_multiplayer() && run_settings.player_lives() == Lives::Separate;
+    let _hud_entity: Entity = build_info_hud(
+        &mut commands,
+        &game_assets,
+        &ui_theme,
+        &ui_font,
+        !separate_health,
+    );
+    if run_settings.is_multiplayer() {
+        let _player_panels_entity: Entity = build_player_panels_hud(
+            &mut commands,
+            &game_assets,
+            &ui_theme,
+            &ui_font,
+            &run_settings,
+        );
+    }
+    if let Some(time_limit) = run_settings.mode.time_limit() {
+        let _time_limit_entity: Entity =
+            build_time_limit_hud(&mut commands, &ui_theme, &ui_font, time_limit);
+    }
+    let _controls_entity: Entity = build_controls_hud(
+        &mut commands,
+        &ui_theme,
+        &ui_font,
+        run_settings.is_multiplayer(),
+    );
 }
 
-fn build_info_hud(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+fn build_info_hud(
+    commands: &mut Commands,
+    game_assets: &GameAssets,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    show_health: bool,
+) -> Entity {
     commands
-        .spawn((INFO_HUD_NODE, GameInfoHUD))
+        .spawn((
+            INFO_HUD_NODE,
+            GameInfoHUD,
+            UiTween::new(HUD_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
         // Info bar at the top-left of the screen
         .with_children(|parent| {
             parent
                 .spawn((
                     INFO_BAR_NODE,
                     BorderRadius::all(Val::Px(10.0)),
-                    BackgroundColor(INFO_BAR_COLOR.into()),
+                    BackgroundColor(ui_theme.panel_color),
                 ))
                 .with_children(|parent| {
                     // Score info at the top
                     parent.spawn(INFO_ITEM_NODE).with_children(|parent| {
                         parent.spawn((
                             ImageNode {
-                                image: asset_server.load("sprites/star.png"),
+                                image: game_assets.star_sprite.clone(),
                                 ..default()
                             },
                             Node {
@@ -39,46 +95,50 @@ fn build_info_hud(commands: &mut Commands, asset_server: &Res<AssetServer>) -> E
                             },
                         ));
                         parent.spawn((
-                            Text::new(format!("{:?}", Score::default().value)),
-                            TextFont {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                font_size: 32.0,
-                                ..default()
-                            },
-                            TextColor(Color::WHITE),
+                            text_bundle(
+                                format!("{:?}", Score::default().value),
+                                ui_font,
+                                ui_theme.info_font_size,
+                                ui_theme.text_color,
+                            ),
                             ScoreInfo,
+                            AnimatedCounter::default(),
                         ));
                     });
-                    // Player health info in the center
+                    // Missed stars info below the score
                     parent.spawn(INFO_ITEM_NODE).with_children(|parent| {
                         parent.spawn((
                             ImageNode {
-                                image: asset_server.load("sprites/info_heart.png"),
+                                image: game_assets.star_sprite.clone(),
+                                color: ui_theme.muted_text_color,
                                 ..default()
                             },
                             Node {
-                                top: Val::Px(5.0),
+                                top: Val::Px(3.0),
                                 width: Val::Px(30.0),
                                 height: Val::Px(30.0),
                                 ..default()
                             },
                         ));
                         parent.spawn((
-                            Text::new(format!("{:?}", INITIAL_PLAYER_HEALTH)),
-                            TextFont {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                font_size: 32.0,
-                                ..default()
-                            },
-                            TextColor(Color::WHITE),
-                            HealthInfo,
+                            text_bundle(
+                                format!("{:?}", Score::default().missed),
+                                ui_font,
+                                ui_theme.info_font_size,
+                                ui_theme.muted_text_color,
+                            ),
+                            MissedStarsInfo,
                         ));
                     });
+                    // Player health info in the center
+                    if show_health {
+                        spawn_health_item(parent, game_assets, ui_theme, ui_font, PlayerId::ONE);
+                    }
                     // Number of enemies info at the bottom
                     parent.spawn(INFO_ITEM_NODE).with_children(|parent| {
                         parent.spawn((
                             ImageNode {
-                                image: asset_server.load(ENEMY_SPRITE),
+                                image: game_assets.enemy_sprite.clone(),
                                 ..default()
                             },
                             Node {
@@ -89,23 +149,177 @@ fn build_info_hud(commands: &mut Commands, asset_server: &Res<AssetServer>) -> E
                             },
                         ));
                         parent.spawn((
-                            Text::new(format!("{:?}", INITIAL_NUMBER_OF_ENEMIES)),
-                            TextFont {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                font_size: 32.0,
-                                ..default()
-                            },
-                            TextColor(Color::WHITE),
+                            text_bundle(
+                                format!("{:?}", INITIAL_NUMBER_OF_ENEMIES),
+                                ui_font,
+                                ui_theme.info_font_size,
+                                ui_theme.text_color,
+                            ),
                             EnemyNumberInfo,
                         ));
                     });
                 })
-                .with_child((INFO_BAR_NODE, BackgroundColor(INFO_BAR_COLOR.into())));
+                .with_child((INFO_BAR_NODE, BackgroundColor(ui_theme.panel_color)));
+        })
+        .id()
+}
+
+fn build_player_panels_hud(
+    commands: &mut Commands,
+    game_assets: &GameAssets,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    run_settings: &RunSettings,
+) -> Entity {
+    commands
+        .spawn((
+            PLAYER_PANELS_NODE,
+            GameInfoHUD,
+            UiTween::new(HUD_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
+        // One panel per player at the top-right of the screen
+        .with_children(|parent| {
+            for index in 0..run_settings.player_count() {
+                let player_id = PlayerId(index);
+                parent
+                    .spawn((
+                        PLAYER_PANEL_NODE,
+                        BorderRadius::all(Val::Px(10.0)),
+                        BackgroundColor(ui_theme.panel_color),
+                    ))
+                    .with_children(|parent| {
+                        // Player name
+                        parent.spawn((
+                            text_bundle("", ui_font, ui_theme.info_font_size, ui_theme.text_color),
+                            LocalizedText::new("hud.player").with_arg("number", player_id.number()),
+                        ));
+                        // Stars collected by the player
+                        parent.spawn(INFO_ITEM_NODE).with_children(|parent| {
+                            parent.spawn((
+                                ImageNode {
+                                    image: game_assets.star_sprite.clone(),
+                                    ..default()
+                                },
+                                Node {
+                                    top: Val::Px(3.0),
+                                    width: Val::Px(30.0),
+                                    height: Val::Px(30.0),
+                                    ..default()
+                                },
+                            ));
+                            parent.spawn((
+                                text_bundle(
+                                    format!("{:?}", Score::default().by_player[index]),
+                                    ui_font,
+                                    ui_theme.info_font_size,
+                                    ui_theme.text_color,
+                                ),
+                                PlayerScoreInfo(player_id),
+                                AnimatedCounter::default(),
+                            ));
+                        });
+                        // Health of the player, unless it's shared
+                        if run_settings.player_lives() == Lives::Separate {
+                            spawn_health_item(parent, game_assets, ui_theme, ui_font, player_id);
+                        }
+                    });
+            }
+        })
+        .id()
+}
+
+fn spawn_health_item(
+    parent: &mut ChildBuilder,
+    game_assets: &GameAssets,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    player_id: PlayerId,
+) {
+    parent.spawn(INFO_ITEM_NODE).with_children(|parent| {
+        parent.spawn((
+            ImageNode {
+                image: game_assets.heart_sprite.clone(),
+                ..default()
+            },
+            Node {
+                top: Val::Px(5.0),
+                width: Val::Px(30.0),
+                height: Val::Px(30.0),
+                ..default()
+            },
+        ));
+        parent.spawn((
+            text_bundle(
+                format!("{:?}", INITIAL_PLAYER_HEALTH),
+                ui_font,
+                ui_theme.info_font_size,
+                ui_theme.text_color,
+            ),
+            HealthInfo(player_id),
+        ));
+    });
+}
+
+fn build_time_limit_hud(
+    commands: &mut Commands,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    time_limit: f32,
+) -> Entity {
+    commands
+        .spawn((
+            TIME_LIMIT_NODE,
+            GameInfoHUD,
+            UiTween::new(HUD_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
+        // Countdown at the top-center of the screen
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    TIME_LIMIT_PANEL_NODE,
+                    BorderRadius::all(Val::Px(10.0)),
+                    BackgroundColor(ui_theme.panel_color),
+                ))
+                .with_child((
+                    text_bundle(
+                        format_countdown(time_limit),
+                        ui_font,
+                        ui_theme.heading_font_size,
+                        ui_theme.text_color,
+                    ),
+                    TimeLimitInfo,
+                ));
         })
         .id()
 }
 
-fn build_controls_hud(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+/// Formats the remaining seconds as `m:ss`, rounding up so that `0:00` means the time is up.
+pub fn format_countdown(remaining_secs: f32) -> String {
+    let seconds = remaining_secs.ceil() as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn build_controls_hud(
+    commands: &mut Commands,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    multiplayer: bool,
+) -> Entity {
+    let controls: &[&str] = match multiplayer {
+        true => &[
+            "hud.controls.player_one",
+            "hud.controls.player_two",
+            "hud.controls.pause",
+        ],
+        false => &[
+            "hud.controls.up",
+            "hud.controls.left",
+            "hud.controls.down",
+            "hud.controls.right",
+            "hud.controls.pause",
+        ],
+    };
+
     commands
         .spawn((
             Node {
@@ -116,6 +330,7 @@ fn build_controls_hud(commands: &mut Commands, asset_server: &Res<AssetServer>)
                 ..default()
             },
             ControlsHUD,
+            UiTween::new(HUD_TRANSITION, MENU_TRANSITION_DURATION),
         ))
         .with_children(|parent| {
             parent
@@ -126,51 +341,17 @@ fn build_controls_hud(commands: &mut Commands, asset_server: &Res<AssetServer>)
                     ..default()
                 })
                 .with_children(|parent| {
-                    parent.spawn((
-                        Text::new("W: Move up"),
-                        TextFont {
-                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            font_size: 22.0,
-                            ..default()
-                        },
-                        TextColor(CONTROLS_INFO_COLORS.into()),
-                    ));
-                    parent.spawn((
-                        Text::new("A: Move left"),
-                        TextFont {
-                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            font_size: 22.0,
-                            ..default()
-                        },
-                        TextColor(CONTROLS_INFO_COLORS.into()),
-                    ));
-                    parent.spawn((
-                        Text::new("S: Move down"),
-                        TextFont {
-                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            font_size: 22.0,
-                            ..default()
-                        },
-                        TextColor(CONTROLS_INFO_COLORS.into()),
-                    ));
-                    parent.spawn((
-                        Text::new("D: Move right"),
-                        TextFont {
-                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            font_size: 22.0,
-                            ..default()
-                        },
-                        TextColor(CONTROLS_INFO_COLORS.into()),
-                    ));
-                    parent.spawn((
-                        Text::new("Space: Pause"),
-                        TextFont {
-                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            font_size: 22.0,
-                            ..default()
-                        },
-                        TextColor(CONTROLS_INFO_COLORS.into()),
-                    ));
+                    for &control in controls {
+                        parent.spawn((
+                            text_bundle(
+                                "",
+                                ui_font,
+                                ui_theme.small_font_size,
+                                ui_theme.muted_text_color,
+                            ),
+                            LocalizedText::new(control),
+                        ));
+                    }
                 });
         })
         .id()
@@ -2,34 +2,94 @@ use std::fmt::Write;
 
 use bevy::prelude::*;
 
+use crate::events::PlayerOut;
 use crate::game::{
     components::Health,
     enemy::components::Enemy,
-    player::components::Player,
+    mode::resources::TimeLimit,
+    player::components::{Player, PlayerId},
     score::resources::Score,
-    ui::hud::components::{ControlsHUD, EnemyNumberInfo, GameInfoHUD, HealthInfo, ScoreInfo},
+    ui::hud::components::{
+        ControlsHUD, EnemyNumberInfo, GameInfoHUD, HealthInfo, MissedStarsInfo, PlayerScoreInfo,
+        ScoreInfo, TimeLimitInfo,
+    },
+    ui::hud::systems::layout::format_countdown,
 };
+use crate::tween::{
+    components::{AnimatedCounter, UiTween},
+    despawn_with_tween,
+};
+
+pub fn update_time_limit_info(
+    mut time_limit_info_query: Query<&mut Text, With<TimeLimitInfo>>,
+    time_limit: Res<TimeLimit>,
+) {
+    let countdown = format_countdown(time_limit.timer.remaining_secs());
+    if let Ok(mut text) = time_limit_info_query.get_single_mut() {
+        if text.0 != countdown {
+            text.0 = countdown;
+        }
+    }
+}
 
 pub fn update_score_info(
-    mut score_info_query: Query<&mut Text, With<ScoreInfo>>,
+    mut score_info_query: Query<&mut AnimatedCounter, With<ScoreInfo>>,
+    score: Res<Score>,
+) {
+    if score.is_changed() {
+        if let Ok(mut counter) = score_info_query.get_single_mut() {
+            counter.set_target(score.value);
+        }
+    }
+}
+
+pub fn update_missed_stars_info(
+    mut missed_stars_info_query: Query<&mut Text, With<MissedStarsInfo>>,
     score: Res<Score>,
 ) {
     if score.is_changed() {
-        if let Ok(mut text) = score_info_query.get_single_mut() {
+        if let Ok(mut text) = missed_stars_info_query.get_single_mut() {
             text.clear();
-            let _ = text.write_str(&format!("{:?}", score.value));
+            let _ = text.write_str(&format!("{:?}", score.missed));
         }
     }
 }
 
+pub fn update_player_score_info(
+    mut player_score_info_query: Query<(&mut AnimatedCounter, &PlayerScoreInfo)>,
+    score: Res<Score>,
+) {
+    if score.is_changed() {
+        for (mut counter, player_score_info) in &mut player_score_info_query {
+            counter.set_target(score.by_player[player_score_info.0 .0]);
+        }
+    }
+}
+
+type ChangedPlayerHealth = (Changed<Health>, With<Player>);
+
 pub fn update_health_info(
-    mut health_info_query: Query<&mut Text, With<HealthInfo>>,
-    player_health_query: Query<&Health, (Changed<Health>, With<Player>)>,
+    mut health_info_query: Query<(&mut Text, &HealthInfo)>,
+    player_health_query: Query<(&Health, &PlayerId), ChangedPlayerHealth>,
+    mut player_out_event_reader: EventReader<PlayerOut>,
 ) {
-    if let Ok(player_health) = player_health_query.get_single() {
-        if let Ok(mut text) = health_info_query.get_single_mut() {
+    // Players that are out are already despawned, so their last health might not have been shown
+    let player_healths = player_health_query
+        .iter()
+        .map(|(player_health, player_id)| (*player_id, player_health.current))
+        .chain(
+            player_out_event_reader
+                .read()
+                .map(|player_out| (player_out.player_id, 0)),
+        );
+
+    for (player_id, health) in player_healths {
+        for (mut text, health_info) in &mut health_info_query {
+            if health_info.0 != player_id {
+                continue;
+            }
             text.clear();
-            let _ = text.write_str(&format!("{:?}", player_health.current));
+            let _ = text.write_str(&format!("{:?}", health));
         }
     }
 }
@@ -49,11 +109,12 @@ pub fn despawn_game_hud(
     mut commands: Commands,
     game_info_query: Query<Entity, With<GameInfoHUD>>,
     controls_query: Query<Entity, With<ControlsHUD>>,
+    mut tween_query: Query<&mut UiTween>,
 ) {
-    if let Ok(game_info_entity) = game_info_query.get_single() {
-        commands.entity(game_info_entity).despawn_recursive();
+    for game_info_entity in &game_info_query {
+        despawn_with_tween(&mut commands, &mut tween_query, game_info_entity);
     }
-    if let Ok(controls_entity) = controls_query.get_single() {
-        commands.entity(controls_entity).despawn_recursive();
+    for controls_entity in &controls_query {
+        despawn_with_tween(&mut commands, &mut tween_query, controls_entity);
     }
 }
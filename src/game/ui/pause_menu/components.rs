@@ -4,10 +4,4 @@ use bevy::prelude::*;
 pub struct PauseMenu;
 
 #[derive(Component)]
-pub struct ContinueButton;
-
-#[derive(Component)]
-pub struct RestartButton;
-
-#[derive(Component)]
-pub struct MainMenuButton;
+pub struct RestartConfirmation;
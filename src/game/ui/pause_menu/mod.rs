@@ -1,47 +1,49 @@
 mod components;
-mod styles;
 mod systems;
 
 use bevy::prelude::*;
 
 use systems::{
-    interactions::{
-        continue_game, interact_with_continue_button, interact_with_main_menu_button,
-        interact_with_restart_button, reset_game, restart_game, transition_to_main_menu_state,
+    interactions::{cancel_restart, confirm_restart, continue_game},
+    layout::{
+        despawn_pause_menu, despawn_restart_confirmation, spawn_pause_menu,
+        spawn_restart_confirmation,
     },
-    layout::{despawn_pause_menu, spawn_pause_menu},
 };
 
-use crate::{game::SimulationState, AppState};
+use crate::{game::PauseMenuView, AppState};
 
 pub struct PauseMenuPlugin;
 
 impl Plugin for PauseMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            OnEnter(SimulationState::Paused),
+            OnEnter(PauseMenuView::Menu),
             spawn_pause_menu.run_if(in_state(AppState::Game)),
         );
         app.add_systems(
             Update,
-            (
-                interact_with_continue_button,
-                interact_with_main_menu_button,
-                interact_with_restart_button,
-                transition_to_main_menu_state,
-                continue_game,
-                reset_game,
-                restart_game.after(reset_game),
-                run_state_transitions.after(reset_game), // make sure that restarting makes other entities despawn
-            )
+            continue_game
                 .run_if(in_state(AppState::Game))
-                .run_if(in_state(SimulationState::Paused)),
+                .run_if(in_state(PauseMenuView::Menu)),
         );
-        app.add_systems(OnExit(SimulationState::Paused), despawn_pause_menu);
+        app.add_systems(OnExit(PauseMenuView::Menu), despawn_pause_menu);
         app.add_systems(OnExit(AppState::Game), despawn_pause_menu);
+        // Restarting mid-run has to be confirmed
+        app.add_systems(
+            OnEnter(PauseMenuView::ConfirmRestart),
+            spawn_restart_confirmation.run_if(in_state(AppState::Game)),
+        );
+        app.add_systems(
+            Update,
+            (confirm_restart, cancel_restart)
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(PauseMenuView::ConfirmRestart)),
+        );
+        app.add_systems(
+            OnExit(PauseMenuView::ConfirmRestart),
+            despawn_restart_confirmation,
+        );
+        app.add_systems(OnExit(AppState::Game), despawn_restart_confirmation);
     }
 }
-
-fn run_state_transitions(world: &mut World) {
-    let _ = world.try_run_schedule(StateTransition);
-}
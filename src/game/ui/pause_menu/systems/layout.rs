@@ -1,24 +1,64 @@
 use bevy::prelude::*;
 
 use crate::{
-    game::ui::pause_menu::{
-        components::{ContinueButton, MainMenuButton, PauseMenu, RestartButton},
-        styles::{NORMAL_BUTTON_NODE, PAUSE_MENU_NODE, TITLE_NODE},
+    game::ui::pause_menu::components::{PauseMenu, RestartConfirmation},
+    game::{mode::GameMode, resources::RunSettings},
+    localization::components::LocalizedText,
+    tween::{components::UiTween, despawn_with_tween, MENU_TRANSITION, MENU_TRANSITION_DURATION},
+    widgets::{
+        components::ButtonAction,
+        resources::{UiFont, UiTheme},
+        spawn_menu_button,
+        styles::{MENU_NODE, MENU_TITLE_NODE},
+        text_bundle,
     },
-    Srgba,
 };
 
-pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let _game_over_menu_entity: Entity = build_pause_menu(&mut commands, &asset_server);
+pub fn spawn_pause_menu(
+    mut commands: Commands,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+    run_settings: Res<RunSettings>,
+) {
+    let _pause_menu_entity: Entity =
+        build_pause_menu(&mut commands, &ui_theme, &ui_font, &run_settings);
 }
 
-pub fn despawn_pause_menu(mut commands: Commands, query: Query<Entity, With<PauseMenu>>) {
-    if let Ok(pause_menu_entity) = query.get_single() {
-        commands.entity(pause_menu_entity).despawn_recursive();
+pub fn despawn_pause_menu(
+    mut commands: Commands,
+    query: Query<Entity, With<PauseMenu>>,
+    mut tween_query: Query<&mut UiTween>,
+) {
+    for pause_menu_entity in &query {
+        despawn_with_tween(&mut commands, &mut tween_query, pause_menu_entity);
     }
 }
 
-fn build_pause_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+pub fn spawn_restart_confirmation(
+    mut commands: Commands,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+) {
+    let _restart_confirmation_entity: Entity =
+        build_restart_confirmation(&mut commands, &ui_theme, &ui_font);
+}
+
+pub fn despawn_restart_confirmation(
+    mut commands: Commands,
+    query: Query<Entity, With<RestartConfirmation>>,
+    mut tween_query: Query<&mut UiTween>,
+) {
+    for restart_confirmation_entity in &query {
+        despawn_with_tween(&mut commands, &mut tween_query, restart_confirmation_entity);
+    }
+}
+
+fn build_pause_menu(
+    commands: &mut Commands,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    run_settings: &RunSettings,
+) -> Entity {
     commands
         // Blur gameplay
         .spawn((
@@ -28,94 +68,109 @@ fn build_pause_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) ->
 
                 ..default()
             },
-            BackgroundColor(
-                Srgba {
-                    red: 0.0,
-                    green: 0.0,
-                    blue: 0.0,
-                    alpha: 0.8,
-                }
-                .into(),
-            ),
+            BackgroundColor(ui_theme.overlay_color),
             PauseMenu,
+            UiTween::new(MENU_TRANSITION, MENU_TRANSITION_DURATION),
         ))
         .with_children(|parent| {
-            parent.spawn(PAUSE_MENU_NODE).with_children(|parent| {
+            parent.spawn(MENU_NODE).with_children(|parent| {
                 // Pause menu title
-                parent.spawn(TITLE_NODE).with_child((
-                    Text::new("Game Paused!"),
-                    TextFont {
-                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 64.0,
-                        ..default()
-                    },
-                    TextColor(Color::WHITE),
+                parent.spawn(MENU_TITLE_NODE).with_child((
+                    text_bundle("", ui_font, ui_theme.title_font_size, ui_theme.text_color),
+                    LocalizedText::new("pause_menu.title"),
                 ));
                 // Continue button
-                parent
-                    .spawn((
-                        NORMAL_BUTTON_NODE,
-                        Button,
-                        ContinueButton,
-                        BorderRadius::all(Val::Px(10.0)),
-                    ))
-                    .with_child((
-                        Text::new("Continue (C)"),
-                        TextFont {
-                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            font_size: 32.0,
-                            ..default()
-                        },
-                        TextColor(Color::WHITE),
-                        TextLayout {
-                            justify: JustifyText::Center,
-                            ..default()
-                        },
-                    ));
-                // Restart button
-                parent
-                    .spawn((
-                        NORMAL_BUTTON_NODE,
-                        Button,
-                        RestartButton,
-                        BorderRadius::all(Val::Px(10.0)),
-                    ))
-                    .with_child((
-                        Text::new("Restart (R)"),
-                        TextFont {
-                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            font_size: 32.0,
-                            ..default()
-                        },
-                        TextColor(Color::WHITE),
-                        TextLayout {
-                            justify: JustifyText::Center,
-                            ..default()
-                        },
-                    ));
+                spawn_menu_button(
+                    parent,
+                    ui_theme,
+                    ui_font,
+                    LocalizedText::new("pause_menu.continue"),
+                    ButtonAction::Continue,
+                );
+                // Finish run button
+                if run_settings.mode.is_finished_by_player() {
+                    spawn_menu_button(
+                        parent,
+                        ui_theme,
+                        ui_font,
+                        LocalizedText::new("pause_menu.finish"),
+                        ButtonAction::FinishRun,
+                    );
+                }
+                // Restart button, daily challenges only have a single attempt
+                if run_settings.mode != GameMode::DailyChallenge {
+                    spawn_menu_button(
+                        parent,
+                        ui_theme,
+                        ui_font,
+                        LocalizedText::new("menu.restart"),
+                        ButtonAction::Restart,
+                    );
+                }
                 // Main menu button
-                parent
-                    .spawn((
-                        NORMAL_BUTTON_NODE,
-                        Button,
-                        MainMenuButton,
-                        BorderRadius::all(Val::Px(10.0)),
-                    ))
-                    .with_child((
-                        Text::new("Main Menu (M)"),
-                        TextFont {
-                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            font_size: 32.0,
-                            ..default()
-                        },
-                        TextColor(Color::WHITE),
-                        TextLayout {
-                            justify: JustifyText::Center,
-                            ..default()
-                        },
-                    ));
+                spawn_menu_button(
+                    parent,
+                    ui_theme,
+                    ui_font,
+                    LocalizedText::new("menu.main_menu"),
+                    ButtonAction::MainMenu,
+                );
+            });
+        })
+        .id()
+}
+
+fn build_restart_confirmation(
+    commands: &mut Commands,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+) -> Entity {
+    commands
+        .spawn((
+            Node {
+                height: Val::Percent(100.0),
+                width: Val::Percent(100.0),
+
+                ..default()
+            },
+            BackgroundColor(ui_theme.overlay_color),
+            RestartConfirmation,
+            UiTween::new(MENU_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
+        .with_children(|parent| {
+            parent.spawn(MENU_NODE).with_children(|parent| {
+                // Confirmation title
+                parent.spawn(MENU_TITLE_NODE).with_child((
+                    text_bundle("", ui_font, ui_theme.title_font_size, ui_theme.text_color),
+                    LocalizedText::new("pause_menu.confirm_restart.title"),
+                ));
+                // Warning about losing the current run
+                parent.spawn((
+                    text_bundle(
+                        "",
+                        ui_font,
+                        ui_theme.info_font_size,
+                        ui_theme.muted_text_color,
+                    ),
+                    LocalizedText::new("pause_menu.confirm_restart.warning"),
+                ));
+                // Confirm button
+                spawn_menu_button(
+                    parent,
+                    ui_theme,
+                    ui_font,
+                    LocalizedText::new("pause_menu.confirm_restart.confirm"),
+                    ButtonAction::ConfirmRestart,
+                );
+                // Cancel button
+                spawn_menu_button(
+                    parent,
+                    ui_theme,
+                    ui_font,
+                    LocalizedText::new("pause_menu.confirm_restart.cancel"),
+                    ButtonAction::CancelRestart,
+                );
             });
         })
-        // TODO: Interactions with buttons
         .id()
 }
@@ -0,0 +1,56 @@
+pub mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::VersusMatch;
+use systems::*;
+
+use super::mode::GameMode;
+use super::player::{confine_player_movement, player_hit_star};
+use super::resources::RunSettings;
+use super::run::{RunEnd, RunStart};
+use super::score::remove_score;
+use super::simulation::SimulationStep;
+use super::SimulationState;
+use crate::AppState;
+
+/// Seconds a versus round lasts when no player is eliminated.
+pub const VERSUS_ROUND_DURATION: f32 = 90.0;
+/// Default number of rounds a versus match is played over.
+pub const VERSUS_BEST_OF: u32 = 3;
+/// Distance the players are pushed apart when they collide.
+const PLAYER_BOUNCE_STRENGTH: f32 = 50.0;
+
+/// Rounds, winners and player collisions of the versus mode.
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VersusMatch>()
+            .add_systems(OnEnter(AppState::MainMenu), reset_versus_match)
+            .add_systems(RunStart, start_versus_round.run_if(is_versus))
+            .add_systems(
+                SimulationStep,
+                player_hit_player
+                    .after(confine_player_movement)
+                    .before(player_hit_star)
+                    .run_if(is_versus),
+            )
+            .add_systems(
+                Update,
+                end_round_on_elimination
+                    .run_if(is_versus)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+            )
+            .add_systems(
+                RunEnd,
+                finish_versus_round.before(remove_score).run_if(is_versus),
+            );
+    }
+}
+
+fn is_versus(run_settings: Res<RunSettings>) -> bool {
+    run_settings.mode == GameMode::Versus
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+use crate::game::player::{components::PlayerId, MAX_PLAYERS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundOutcome {
+    Winner(PlayerId),
+    Draw,
+}
+
+impl RoundOutcome {
+    /// The player with the most stars wins, a shared lead is a draw.
+    pub fn from_stars(stars: &[u32; MAX_PLAYERS]) -> RoundOutcome {
+        let most_stars = stars.iter().copied().max().unwrap_or_default();
+        let mut leaders = (0..MAX_PLAYERS).filter(|&index| stars[index] == most_stars);
+        match (leaders.next(), leaders.next()) {
+            (Some(index), None) => RoundOutcome::Winner(PlayerId(index)),
+            _ => RoundOutcome::Draw,
+        }
+    }
+}
+
+/// Rounds of the current versus match, kept across restarts until a player has won it.
+#[derive(Resource, Debug, Default)]
+pub struct VersusMatch {
+    /// Rounds won by each player.
+    pub wins: [u32; MAX_PLAYERS],
+    /// Finished rounds, including draws.
+    pub rounds_played: u32,
+    /// Outcome of the current round, known early when a player gets eliminated.
+    pub round_outcome: Option<RoundOutcome>,
+    /// Stars collected by each player in the last finished round.
+    pub round_stars: [u32; MAX_PLAYERS],
+}
+
+impl VersusMatch {
+    pub fn record(&mut self, outcome: RoundOutcome, stars: [u32; MAX_PLAYERS]) {
+        if let RoundOutcome::Winner(player_id) = outcome {
+            self.wins[player_id.0] += 1;
+        }
+        self.rounds_played += 1;
+        self.round_outcome = Some(outcome);
+        self.round_stars = stars;
+    }
+
+    /// Player that won the majority of the rounds of a best-of-`best_of` match.
+    pub fn winner(&self, best_of: u32) -> Option<PlayerId> {
+        let wins_needed = best_of / 2 + 1;
+        self.wins
+            .iter()
+            .position(|&wins| wins >= wins_needed)
+            .map(PlayerId)
+    }
+}
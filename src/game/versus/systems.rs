@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use super::resources::{RoundOutcome, VersusMatch};
+use super::PLAYER_BOUNCE_STRENGTH;
+use crate::events::{GameOver, PlayerOut};
+use crate::game::components::Health;
+use crate::game::player::components::{Player, PlayerId};
+use crate::game::player::PLAYER_SIZE;
+use crate::game::resources::RunSettings;
+use crate::game::score::resources::Score;
+
+pub fn reset_versus_match(mut versus_match: ResMut<VersusMatch>) {
+    *versus_match = VersusMatch::default();
+}
+
+pub fn start_versus_round(mut versus_match: ResMut<VersusMatch>, run_settings: Res<RunSettings>) {
+    // A decided match is followed by a rematch
+    if versus_match.winner(run_settings.best_of).is_some() {
+        *versus_match = VersusMatch::default();
+    }
+    versus_match.round_outcome = None;
+    info!(target: "versus", "Round {} begins!", versus_match.rounds_played + 1);
+}
+
+pub fn player_hit_player(mut player_query: Query<(&mut Transform, &PlayerId), With<Player>>) {
+    // Pairs are pushed apart in the same order on every machine
+    let mut player_transforms: Vec<Mut<Transform>> = player_query
+        .iter_mut()
+        .sort::<&PlayerId>()
+        .map(|(player_transform, _)| player_transform)
+        .collect();
+    for second_index in 1..player_transforms.len() {
+        let (first_transforms, second_transforms) = player_transforms.split_at_mut(second_index);
+        let second_transform = &mut second_transforms[0];
+        for first_transform in first_transforms.iter_mut() {
+            push_apart(first_transform, second_transform);
+        }
+    }
+}
+
+fn push_apart(first_transform: &mut Transform, second_transform: &mut Transform) {
+    let relative_vector_in_plane = Vec3 {
+        x: first_transform.translation.x - second_transform.translation.x,
+        y: first_transform.translation.y - second_transform.translation.y,
+        z: 0.0,
+    };
+
+    if relative_vector_in_plane.length() > PLAYER_SIZE {
+        return;
+    }
+
+    let bounce = PLAYER_BOUNCE_STRENGTH * relative_vector_in_plane.normalize_or_zero();
+    first_transform.translation += bounce;
+    second_transform.translation -= bounce;
+}
+
+/// Ends the round as soon as a player is out, the remaining player wins it.
+pub fn end_round_on_elimination(
+    mut player_out_event_reader: EventReader<PlayerOut>,
+    mut game_over_event_writer: EventWriter<GameOver>,
+    player_query: Query<(&PlayerId, &Health), With<Player>>,
+    mut versus_match: ResMut<VersusMatch>,
+    score: Res<Score>,
+) {
+    for player_out in player_out_event_reader.read() {
+        if versus_match.round_outcome.is_some() {
+            continue;
+        }
+
+        let survivor = player_query
+            .iter()
+            .find(|(player_id, player_health)| {
+                **player_id != player_out.player_id && player_health.current > 0
+            })
+            .map(|(player_id, _)| *player_id);
+        versus_match.round_outcome = Some(match survivor {
+            Some(player_id) => RoundOutcome::Winner(player_id),
+            None => RoundOutcome::Draw,
+        });
+        info!(target: "versus", "{} was eliminated!", player_out.player_id);
+        game_over_event_writer.send(GameOver { score: score.value });
+    }
+}
+
+/// Records the finished round, rounds that were abandoned or restarted don't count.
+pub fn finish_versus_round(
+    mut game_over_event_reader: EventReader<GameOver>,
+    mut versus_match: ResMut<VersusMatch>,
+    run_settings: Res<RunSettings>,
+    score: Res<Score>,
+) {
+    if game_over_event_reader.read().count() == 0 {
+        return;
+    }
+
+    // Rounds that ran out of time go to the player with the most stars
+    let outcome = versus_match
+        .round_outcome
+        .unwrap_or_else(|| RoundOutcome::from_stars(&score.by_player));
+    versus_match.record(outcome, score.by_player);
+
+    match outcome {
+        RoundOutcome::Winner(player_id) => {
+            info!(target: "versus", "{player_id} wins round {}!", versus_match.rounds_played)
+        }
+        RoundOutcome::Draw => {
+            info!(target: "versus", "Round {} is a draw!", versus_match.rounds_played)
+        }
+    }
+    if let Some(player_id) = versus_match.winner(run_settings.best_of) {
+        info!(target: "versus", "{player_id} wins the match {:?}!", versus_match.wins);
+    }
+}
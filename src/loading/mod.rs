@@ -0,0 +1,39 @@
+pub mod components;
+pub mod resources;
+mod styles;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::*;
+use systems::{layout::*, updates::*};
+
+use crate::{cli::StartIn, systems::quit_game, AppState};
+
+/// Preloads all assets before the main menu is shown and reports missing ones.
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameAssets>()
+            .init_resource::<LoadingProgress>()
+            .init_resource::<StartIn>()
+            .add_systems(OnEnter(AppState::Loading), spawn_loading_screen)
+            .add_systems(
+                Update,
+                (
+                    track_loading_progress,
+                    update_loading_screen,
+                    finish_loading.run_if(|progress: Res<LoadingProgress>| progress.is_finished()),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Loading)),
+            )
+            .add_systems(OnExit(AppState::Loading), despawn_loading_screen)
+            .add_systems(
+                OnEnter(AppState::LoadingFailed),
+                spawn_loading_failed_screen,
+            )
+            .add_systems(Update, quit_game.run_if(in_state(AppState::LoadingFailed)));
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::{enemy::ENEMY_SPRITE, player::PLAYER_SPRITE},
+    music::{GAME_INTENSITY_STEM, GAME_OVER_TRACK, GAME_TRACK, MAIN_MENU_TRACK},
+    widgets::FONT,
+};
+
+/// Handles to every asset the game uses, loaded up front while in [`AppState::Loading`](crate::AppState::Loading).
+#[derive(Resource, Debug)]
+pub struct GameAssets {
+    pub font: Handle<Font>,
+    pub player_sprite: Handle<Image>,
+    pub enemy_sprite: Handle<Image>,
+    pub star_sprite: Handle<Image>,
+    pub heart_sprite: Handle<Image>,
+    pub bounce_sound: Handle<AudioSource>,
+    pub star_collected_sound: Handle<AudioSource>,
+    pub explosion_sound: Handle<AudioSource>,
+    pub main_menu_music: Handle<AudioSource>,
+    pub game_music: Handle<AudioSource>,
+    pub game_intensity_music: Handle<AudioSource>,
+    pub game_over_music: Handle<AudioSource>,
+}
+
+impl FromWorld for GameAssets {
+    fn from_world(world: &mut World) -> GameAssets {
+        let asset_server = world.resource::<AssetServer>();
+        GameAssets {
+            font: asset_server.load(FONT),
+            player_sprite: asset_server.load(PLAYER_SPRITE),
+            enemy_sprite: asset_server.load(ENEMY_SPRITE),
+            star_sprite: asset_server.load("sprites/star.png"),
+            heart_sprite: asset_server.load("sprites/info_heart.png"),
+            bounce_sound: asset_server.load("audio/pluck_001.ogg"),
+            star_collected_sound: asset_server.load("audio/laserLarge_000.ogg"),
+            explosion_sound: asset_server.load("audio/explosionCrunch_000.ogg"),
+            main_menu_music: asset_server.load(MAIN_MENU_TRACK),
+            game_music: asset_server.load(GAME_TRACK),
+            game_intensity_music: asset_server.load(GAME_INTENSITY_STEM),
+            game_over_music: asset_server.load(GAME_OVER_TRACK),
+        }
+    }
+}
+
+impl GameAssets {
+    pub fn handles(&self) -> [UntypedHandle; 12] {
+        [
+            self.font.clone().untyped(),
+            self.player_sprite.clone().untyped(),
+            self.enemy_sprite.clone().untyped(),
+            self.star_sprite.clone().untyped(),
+            self.heart_sprite.clone().untyped(),
+            self.bounce_sound.clone().untyped(),
+            self.star_collected_sound.clone().untyped(),
+            self.explosion_sound.clone().untyped(),
+            self.main_menu_music.clone().untyped(),
+            self.game_music.clone().untyped(),
+            self.game_intensity_music.clone().untyped(),
+            self.game_over_music.clone().untyped(),
+        ]
+    }
+}
+
+#[derive(Resource, Debug, Default, PartialEq)]
+pub struct LoadingProgress {
+    pub loaded: usize,
+    pub total: usize,
+}
+
+impl LoadingProgress {
+    pub fn fraction(&self) -> f32 {
+        match self.total {
+            0 => 1.0,
+            total => self.loaded as f32 / total as f32,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.total > 0 && self.loaded == self.total
+    }
+}
+
+/// Paths of the assets that could not be loaded.
+#[derive(Resource, Debug)]
+pub struct LoadingFailure {
+    pub paths: Vec<String>,
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+pub const PROGRESS_BAR_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.width = Val::Px(500.0);
+    node.height = Val::Px(30.0);
+    node.padding = UiRect::all(Val::Px(4.0));
+    node
+};
+
+pub const PROGRESS_BAR_FILL_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.width = Val::Percent(0.0);
+    node.height = Val::Percent(100.0);
+    node
+};
+
+pub const FAILED_PATHS_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.flex_direction = FlexDirection::Column;
+    node.align_items = AlignItems::Center;
+    node.row_gap = Val::Px(4.0);
+    node
+};
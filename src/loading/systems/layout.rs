@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+
+use crate::{
+    loading::{components::*, resources::LoadingFailure, styles::*},
+    localization::components::LocalizedText,
+    tween::{components::UiTween, despawn_with_tween, MENU_TRANSITION, MENU_TRANSITION_DURATION},
+    widgets::{
+        components::{ButtonAction, MenuButton},
+        resources::{UiFont, UiTheme},
+        styles::{MENU_BUTTON_NODE, MENU_NODE, MENU_TITLE_NODE},
+        text_bundle,
+    },
+};
+
+pub fn spawn_loading_screen(mut commands: Commands, ui_theme: Res<UiTheme>, ui_font: Res<UiFont>) {
+    let _loading_screen_entity: Entity = build_loading_screen(&mut commands, &ui_theme, &ui_font);
+}
+
+pub fn despawn_loading_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<LoadingScreen>>,
+    mut tween_query: Query<&mut UiTween>,
+) {
+    for loading_screen_entity in &query {
+        despawn_with_tween(&mut commands, &mut tween_query, loading_screen_entity);
+    }
+}
+
+pub fn spawn_loading_failed_screen(
+    mut commands: Commands,
+    ui_theme: Res<UiTheme>,
+    loading_failure: Res<LoadingFailure>,
+) {
+    let _loading_failed_screen_entity: Entity =
+        build_loading_failed_screen(&mut commands, &ui_theme, &loading_failure);
+}
+
+fn build_loading_screen(commands: &mut Commands, ui_theme: &UiTheme, ui_font: &UiFont) -> Entity {
+    commands
+        .spawn((MENU_NODE, LoadingScreen))
+        .with_children(|parent| {
+            // Loading title
+            parent.spawn(MENU_TITLE_NODE).with_child((
+                text_bundle("", ui_font, ui_theme.title_font_size, ui_theme.text_color),
+                LocalizedText::new("loading.title"),
+            ));
+            // Progress bar
+            parent
+                .spawn((
+                    PROGRESS_BAR_NODE,
+                    BackgroundColor(ui_theme.panel_color),
+                    BorderRadius::all(Val::Px(10.0)),
+                ))
+                .with_child((
+                    PROGRESS_BAR_FILL_NODE,
+                    BackgroundColor(ui_theme.hovered_button_color),
+                    BorderRadius::all(Val::Px(6.0)),
+                    LoadingProgressBar,
+                ));
+            // Progress info
+            parent.spawn((
+                text_bundle(
+                    "",
+                    ui_font,
+                    ui_theme.info_font_size,
+                    ui_theme.muted_text_color,
+                ),
+                LocalizedText::new("loading.progress").with_arg("percent", 0),
+                LoadingProgressInfo,
+            ));
+        })
+        .id()
+}
+
+fn build_loading_failed_screen(
+    commands: &mut Commands,
+    ui_theme: &UiTheme,
+    loading_failure: &LoadingFailure,
+) -> Entity {
+    commands
+        .spawn((
+            MENU_NODE,
+            LoadingFailedScreen,
+            UiTween::new(MENU_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
+        .with_children(|parent| {
+            // Failure title
+            parent.spawn(MENU_TITLE_NODE).with_child((
+                fallback_text("", ui_theme.heading_font_size, ui_theme.text_color),
+                LocalizedText::new("loading.failed.title"),
+            ));
+            // Missing assets
+            parent.spawn((
+                fallback_text("", ui_theme.info_font_size, ui_theme.muted_text_color),
+                LocalizedText::new("loading.failed.hint"),
+            ));
+            parent.spawn(FAILED_PATHS_NODE).with_children(|parent| {
+                for path in &loading_failure.paths {
+                    parent.spawn(fallback_text(
+                        path.clone(),
+                        ui_theme.small_font_size,
+                        ui_theme.text_color,
+                    ));
+                }
+            });
+            // Quit button
+            parent
+                .spawn((
+                    MENU_BUTTON_NODE,
+                    Button,
+                    MenuButton {
+                        action: ButtonAction::Quit,
+                    },
+                    BorderRadius::all(Val::Px(10.0)),
+                    BackgroundColor(ui_theme.normal_button_color),
+                    Outline::new(Val::Px(3.0), Val::ZERO, Color::NONE),
+                ))
+                .with_child((
+                    fallback_text("", ui_theme.button_font_size, ui_theme.text_color),
+                    TextLayout {
+                        justify: JustifyText::Center,
+                        ..default()
+                    },
+                    LocalizedText::new("main_menu.quit"),
+                ));
+        })
+        .id()
+}
+
+/// Text in Bevy's built-in font, since the game font might be one of the missing assets.
+fn fallback_text(value: impl Into<String>, font_size: f32, color: Color) -> impl Bundle {
+    (
+        Text::new(value),
+        TextFont {
+            font_size,
+            ..default()
+        },
+        TextColor(color),
+    )
+}
@@ -0,0 +1,101 @@
+use bevy::{asset::LoadState, prelude::*};
+
+use crate::{
+    cli::StartIn,
+    game::{run::RunLock, SimulationState},
+    loading::{
+        components::{LoadingProgressBar, LoadingProgressInfo},
+        resources::{GameAssets, LoadingFailure, LoadingProgress},
+    },
+    localization::{components::LocalizedText, resources::Localization},
+    network::resources::{ClientSession, HostSession, RollbackSession},
+    AppState,
+};
+
+/// Counts the loaded assets and switches to the failure screen as soon as one of them fails.
+pub fn track_loading_progress(
+    mut commands: Commands,
+    mut loading_progress: ResMut<LoadingProgress>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    localization: Res<Localization>,
+) {
+    let handles: Vec<UntypedHandle> = game_assets
+        .handles()
+        .into_iter()
+        .chain(
+            localization
+                .string_tables
+                .values()
+                .map(|handle| handle.clone().untyped()),
+        )
+        .collect();
+
+    let mut loaded = 0;
+    let mut failed_paths = Vec::new();
+    for handle in &handles {
+        if asset_server.is_loaded_with_dependencies(handle) {
+            loaded += 1;
+        } else if let LoadState::Failed(error) = asset_server.load_state(handle) {
+            error!(target: "loading", "Could not load asset: {error}");
+            let path = handle
+                .path()
+                .map(|path| path.to_string())
+                .unwrap_or_else(|| format!("{:?}", handle.id()));
+            failed_paths.push(path);
+        }
+    }
+
+    loading_progress.set_if_neq(LoadingProgress {
+        loaded,
+        total: handles.len(),
+    });
+
+    if !failed_paths.is_empty() {
+        commands.insert_resource(LoadingFailure {
+            paths: failed_paths,
+        });
+        next_app_state.set(AppState::LoadingFailed);
+    }
+}
+
+pub fn update_loading_screen(
+    mut progress_bar_query: Query<&mut Node, With<LoadingProgressBar>>,
+    mut progress_info_query: Query<&mut LocalizedText, With<LoadingProgressInfo>>,
+    loading_progress: Res<LoadingProgress>,
+) {
+    let percent = (loading_progress.fraction() * 100.0).round();
+
+    for mut node in &mut progress_bar_query {
+        node.width = Val::Percent(percent);
+    }
+    for mut localized_text in &mut progress_info_query {
+        localized_text
+            .set_if_neq(LocalizedText::new("loading.progress").with_arg("percent", percent));
+    }
+}
+
+pub fn finish_loading(
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_simulation_state: ResMut<NextState<SimulationState>>,
+    start_in: Res<StartIn>,
+    run_lock: RunLock,
+    host_session: Option<Res<HostSession>>,
+    client_session: Option<Res<ClientSession>>,
+    rollback_session: Option<Res<RollbackSession>>,
+) {
+    info!(target: "loading", "All assets loaded.");
+    // Online sessions opened from the command line wait for the other players first
+    if host_session.is_some() || client_session.is_some() || rollback_session.is_some() {
+        next_app_state.set(AppState::Lobby);
+        return;
+    }
+    match *start_in {
+        StartIn::Game if !run_lock.is_locked() => {
+            next_app_state.set(AppState::Game);
+            next_simulation_state.set(SimulationState::Running);
+        }
+        StartIn::MainMenu | StartIn::Game => next_app_state.set(AppState::MainMenu),
+    }
+}
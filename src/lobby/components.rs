@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct Lobby;
+
+/// Address of the session and whether the host accepted this client yet.
+#[derive(Component)]
+pub struct LobbyStatusInfo;
+
+#[derive(Component)]
+pub struct LobbyPlayersInfo;
@@ -0,0 +1,37 @@
+mod components;
+mod styles;
+mod systems;
+
+use bevy::prelude::*;
+use systems::{
+    interactions::{
+        auto_start_online_run, handle_lobby_buttons, start_rollback_run, update_lobby_info,
+    },
+    layout::{despawn_lobby, spawn_lobby},
+};
+
+use crate::game::run::handle_run_events;
+use crate::network::resources::{HostSession, RollbackSession};
+use crate::AppState;
+
+/// Screen where the players of an online session gather before the host starts the run.
+pub struct LobbyPlugin;
+
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Lobby), spawn_lobby);
+        app.add_systems(
+            Update,
+            (
+                handle_lobby_buttons,
+                auto_start_online_run.run_if(resource_exists::<HostSession>),
+                start_rollback_run.run_if(resource_exists::<RollbackSession>),
+                update_lobby_info,
+            )
+                .chain()
+                .before(handle_run_events)
+                .run_if(in_state(AppState::Lobby)),
+        );
+        app.add_systems(OnExit(AppState::Lobby), despawn_lobby);
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+pub const LOBBY_INFO_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.flex_direction = FlexDirection::Column;
+    node.align_items = AlignItems::Center;
+    node.row_gap = Val::Px(10.0);
+    node
+};
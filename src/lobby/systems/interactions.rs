@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+
+use crate::{
+    cli::StartIn,
+    events::{MenuButtonPressed, StartRun},
+    game::{player::MAX_PLAYERS, resources::RunSettings},
+    lobby::components::{LobbyPlayersInfo, LobbyStatusInfo},
+    localization::components::LocalizedText,
+    network::resources::{ClientSession, HostSession, RollbackSession},
+    widgets::components::ButtonAction,
+    AppState,
+};
+
+pub fn handle_lobby_buttons(
+    mut menu_button_event_reader: EventReader<MenuButtonPressed>,
+    mut start_run_event_writer: EventWriter<StartRun>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut run_settings: ResMut<RunSettings>,
+    host_session: Option<Res<HostSession>>,
+) {
+    for event in menu_button_event_reader.read() {
+        match event.action {
+            ButtonAction::StartOnlineGame => {
+                if let Some(host_session) = &host_session {
+                    start_online_run(host_session, &mut run_settings, &mut start_run_event_writer);
+                }
+            }
+            // Closing the session happens when entering the main menu
+            ButtonAction::LeaveLobby => next_app_state.set(AppState::MainMenu),
+            _ => {}
+        }
+    }
+}
+
+/// Starts the run as soon as someone joined when launched with `--start-in game`.
+pub fn auto_start_online_run(
+    mut start_run_event_writer: EventWriter<StartRun>,
+    mut run_settings: ResMut<RunSettings>,
+    host_session: Res<HostSession>,
+    start_in: Res<StartIn>,
+) {
+    if *start_in == StartIn::Game && !host_session.peers.is_empty() {
+        start_online_run(
+            &host_session,
+            &mut run_settings,
+            &mut start_run_event_writer,
+        );
+    }
+}
+
+/// Starts the run in the lobby as soon as both peers agreed on it.
+pub fn start_rollback_run(
+    mut start_run_event_writer: EventWriter<StartRun>,
+    mut rollback_session: ResMut<RollbackSession>,
+    mut run_settings: ResMut<RunSettings>,
+) {
+    if rollback_session.local_player.is_none() {
+        return;
+    }
+    if rollback_session.local_run_settings.is_none() {
+        rollback_session.local_run_settings = Some(run_settings.clone());
+    }
+    run_settings.mode = rollback_session.mode;
+    run_settings.players = 2;
+    run_settings.seed = Some(rollback_session.seed);
+    // A bot would override the inputs both machines agreed on
+    run_settings.bots = false;
+    start_run_event_writer.send(StartRun);
+}
+
+fn start_online_run(
+    host_session: &HostSession,
+    run_settings: &mut RunSettings,
+    start_run_event_writer: &mut EventWriter<StartRun>,
+) {
+    if host_session.peers.is_empty() {
+        info!(target: "network", "Waiting for at least one more player.");
+        return;
+    }
+    run_settings.players = host_session.player_count();
+    start_run_event_writer.send(StartRun);
+}
+
+pub fn update_lobby_info(
+    mut status_info_query: Query<&mut LocalizedText, With<LobbyStatusInfo>>,
+    mut players_info_query: Query<
+        &mut LocalizedText,
+        (With<LobbyPlayersInfo>, Without<LobbyStatusInfo>),
+    >,
+    host_session: Option<Res<HostSession>>,
+    client_session: Option<Res<ClientSession>>,
+    rollback_session: Option<Res<RollbackSession>>,
+) {
+    let (status, players) = match (&host_session, &client_session, &rollback_session) {
+        (Some(host_session), _, _) => (
+            LocalizedText::new("lobby.hosting").with_arg("port", host_session.port()),
+            host_session.player_count(),
+        ),
+        (None, Some(client_session), _) => match client_session.player_id {
+            Some(player_id) => (
+                LocalizedText::new("lobby.joined").with_arg("number", player_id.number()),
+                client_session.lobby_players.into(),
+            ),
+            None => (
+                LocalizedText::new("lobby.connecting").with_arg("address", client_session.host),
+                0,
+            ),
+        },
+        (None, None, Some(rollback_session)) => (
+            LocalizedText::new("lobby.connecting").with_arg("address", rollback_session.peer),
+            1,
+        ),
+        (None, None, None) => return,
+    };
+
+    for mut localized_text in &mut status_info_query {
+        localized_text.set_if_neq(status.clone());
+    }
+    for mut localized_text in &mut players_info_query {
+        localized_text.set_if_neq(
+            LocalizedText::new("lobby.players")
+                .with_arg("players", players)
+                .with_arg("max", MAX_PLAYERS),
+        );
+    }
+}
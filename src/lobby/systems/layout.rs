@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::{
+    lobby::{
+        components::{Lobby, LobbyPlayersInfo, LobbyStatusInfo},
+        styles::*,
+    },
+    localization::components::LocalizedText,
+    network::resources::HostSession,
+    tween::{components::UiTween, despawn_with_tween, MENU_TRANSITION, MENU_TRANSITION_DURATION},
+    widgets::{
+        components::ButtonAction,
+        resources::{UiFont, UiTheme},
+        spawn_menu_button,
+        styles::MENU_NODE,
+        text_bundle,
+    },
+};
+
+pub fn spawn_lobby(
+    mut commands: Commands,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+    host_session: Option<Res<HostSession>>,
+) {
+    let _lobby_entity: Entity =
+        build_lobby(&mut commands, &ui_theme, &ui_font, host_session.is_some());
+}
+
+pub fn despawn_lobby(
+    mut commands: Commands,
+    query: Query<Entity, With<Lobby>>,
+    mut tween_query: Query<&mut UiTween>,
+) {
+    for lobby_entity in &query {
+        despawn_with_tween(&mut commands, &mut tween_query, lobby_entity);
+    }
+}
+
+pub fn build_lobby(
+    commands: &mut Commands,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    is_host: bool,
+) -> Entity {
+    commands
+        .spawn((
+            MENU_NODE,
+            Lobby,
+            UiTween::new(MENU_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                text_bundle("", ui_font, ui_theme.title_font_size, ui_theme.text_color),
+                LocalizedText::new("lobby.title"),
+            ));
+            // Session info, filled by `update_lobby_info`
+            parent.spawn(LOBBY_INFO_NODE).with_children(|parent| {
+                parent.spawn((
+                    text_bundle("", ui_font, ui_theme.info_font_size, ui_theme.text_color),
+                    LobbyStatusInfo,
+                ));
+                parent.spawn((
+                    text_bundle(
+                        "",
+                        ui_font,
+                        ui_theme.info_font_size,
+                        ui_theme.muted_text_color,
+                    ),
+                    LobbyPlayersInfo,
+                ));
+            });
+            // Start button, only the host decides when the run begins
+            if is_host {
+                spawn_menu_button(
+                    parent,
+                    ui_theme,
+                    ui_font,
+                    LocalizedText::new("lobby.start"),
+                    ButtonAction::StartOnlineGame,
+                );
+            }
+            // Leave button
+            spawn_menu_button(
+                parent,
+                ui_theme,
+                ui_font,
+                LocalizedText::new("lobby.leave"),
+                ButtonAction::LeaveLobby,
+            );
+        })
+        .id()
+}
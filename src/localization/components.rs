@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+/// Text whose content is looked up in the string table of the current language.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct LocalizedText {
+    pub key: &'static str,
+    /// Values substituted for `{name}` placeholders in the translated string.
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl LocalizedText {
+    pub fn new(key: &'static str) -> LocalizedText {
+        LocalizedText { key, args: vec![] }
+    }
+
+    pub fn with_arg(mut self, name: &'static str, value: impl ToString) -> LocalizedText {
+        self.args.push((name, value.to_string()));
+        self
+    }
+}
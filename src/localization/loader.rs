@@ -0,0 +1,45 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+    utils::HashMap,
+};
+use thiserror::Error;
+
+/// Translations of all UI strings of one language, keyed by string id.
+#[derive(Asset, TypePath, Debug)]
+pub struct StringTable {
+    pub strings: HashMap<String, String>,
+}
+
+#[derive(Default)]
+pub struct StringTableLoader;
+
+#[derive(Debug, Error)]
+pub enum StringTableLoaderError {
+    #[error("Could not read string table: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse string table: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for StringTableLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = StringTableLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<StringTable, StringTableLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let strings = ron::de::from_bytes::<HashMap<String, String>>(&bytes)?;
+        Ok(StringTable { strings })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["strings.ron"]
+    }
+}
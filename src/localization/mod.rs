@@ -0,0 +1,61 @@
+pub mod components;
+mod loader;
+pub mod resources;
+mod systems;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use loader::{StringTable, StringTableLoader};
+use resources::*;
+use systems::*;
+
+pub const FALLBACK_LANGUAGE: Language = Language::English;
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<StringTable>()
+            .init_asset_loader::<StringTableLoader>()
+            .init_resource::<Localization>()
+            .add_systems(PostUpdate, update_localized_texts);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    French,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::German, Language::French];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+        }
+    }
+
+    /// Name of the language in the language itself.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::French => "Français",
+        }
+    }
+
+    pub fn next(self) -> Language {
+        let index = Language::ALL
+            .iter()
+            .position(|language| *language == self)
+            .unwrap_or_default();
+        Language::ALL[(index + 1) % Language::ALL.len()]
+    }
+}
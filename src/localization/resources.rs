@@ -0,0 +1,63 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::components::LocalizedText;
+use super::loader::StringTable;
+use super::{Language, FALLBACK_LANGUAGE};
+
+#[derive(Resource, Debug)]
+pub struct Localization {
+    pub string_tables: HashMap<Language, Handle<StringTable>>,
+}
+
+impl FromWorld for Localization {
+    fn from_world(world: &mut World) -> Localization {
+        let asset_server = world.resource::<AssetServer>();
+        let string_tables = Language::ALL
+            .into_iter()
+            .map(|language| {
+                let path = format!("locales/{}.strings.ron", language.code());
+                (language, asset_server.load(path))
+            })
+            .collect();
+
+        Localization { string_tables }
+    }
+}
+
+impl Localization {
+    fn lookup<'a>(
+        &self,
+        string_tables: &'a Assets<StringTable>,
+        language: Language,
+        key: &str,
+    ) -> Option<&'a String> {
+        let handle = self.string_tables.get(&language)?;
+        string_tables.get(handle)?.strings.get(key)
+    }
+
+    /// Translates the text into the given language, falling back to English and finally to the
+    /// key itself if no translation exists.
+    pub fn translate(
+        &self,
+        string_tables: &Assets<StringTable>,
+        language: Language,
+        localized_text: &LocalizedText,
+    ) -> String {
+        let key = localized_text.key;
+        let mut translation = match self.lookup(string_tables, language, key) {
+            Some(translation) => translation.clone(),
+            None => match self.lookup(string_tables, FALLBACK_LANGUAGE, key) {
+                Some(translation) => {
+                    warn!("Missing {:?} translation for '{}'.", language, key);
+                    translation.clone()
+                }
+                None => key.to_string(),
+            },
+        };
+
+        for (name, value) in &localized_text.args {
+            translation = translation.replace(&format!("{{{}}}", name), value);
+        }
+        translation
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use super::components::LocalizedText;
+use super::loader::StringTable;
+use super::resources::Localization;
+use crate::settings::resources::Settings;
+
+pub fn update_localized_texts(
+    mut text_query: Query<(Ref<LocalizedText>, &mut Text)>,
+    mut string_table_event_reader: EventReader<AssetEvent<StringTable>>,
+    localization: Res<Localization>,
+    string_tables: Res<Assets<StringTable>>,
+    settings: Res<Settings>,
+) {
+    // Re-render every text when the language changes or a string table is (re)loaded.
+    let refresh_all = settings.is_changed() || string_table_event_reader.read().count() > 0;
+
+    for (localized_text, mut text) in &mut text_query {
+        if !refresh_all && !localized_text.is_changed() {
+            continue;
+        }
+        text.0 = localization.translate(&string_tables, settings.language, &localized_text);
+    }
+}
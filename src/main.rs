@@ -1,33 +1,93 @@
+mod arena;
+mod cli;
+mod console;
+#[cfg(feature = "debug_overlay")]
+mod debug;
 mod events;
 mod game;
+mod loading;
+mod lobby;
+mod localization;
 mod main_menu;
+mod music;
+mod network;
+mod replay;
+mod settings;
 mod systems;
-mod utils;
+mod tween;
+mod widgets;
 
 use bevy::prelude::*;
+use clap::Parser;
 
+use arena::ArenaPlugin;
+use cli::LaunchOptions;
+use console::ConsolePlugin;
 use game::GamePlugin;
+use loading::LoadingPlugin;
+use lobby::LobbyPlugin;
+use localization::LocalizationPlugin;
 use main_menu::MainMenuPlugin;
+use music::MusicPlugin;
+use network::NetworkPlugin;
+use replay::ReplayPlugin;
+use settings::SettingsPlugin;
 use systems::*;
+use tween::TweenPlugin;
+use widgets::WidgetsPlugin;
 
-fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins)
+fn main() -> AppExit {
+    let launch_options = LaunchOptions::parse();
+    let mut app = game_app(&launch_options);
+    if let Err(error) = launch_options.apply(&mut app) {
+        eprintln!("{error}");
+        return AppExit::error();
+    }
+
+    app.run()
+}
+
+/// The game with all of its plugins, before the launch options are applied.
+fn game_app(launch_options: &LaunchOptions) -> App {
+    let mut app = App::new();
+    app.add_plugins(launch_options.default_plugins())
         .init_state::<AppState>()
-        .add_plugins((MainMenuPlugin, GamePlugin))
+        .add_plugins((
+            LoadingPlugin,
+            MainMenuPlugin,
+            GamePlugin,
+            MusicPlugin,
+            LocalizationPlugin,
+            SettingsPlugin,
+            TweenPlugin,
+            WidgetsPlugin,
+            ReplayPlugin,
+            ArenaPlugin,
+            ConsolePlugin,
+            NetworkPlugin,
+            LobbyPlugin,
+        ))
         .add_systems(Startup, spawn_camera)
         .add_systems(
             Update,
             (transition_to_game_state, quit_game).run_if(in_state(AppState::MainMenu)),
-        )
-        .run();
+        );
+    #[cfg(feature = "debug_overlay")]
+    app.add_plugins(debug::DebugPlugin);
+    app
 }
 
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub enum AppState {
     #[default]
+    Loading,
+    LoadingFailed,
     MainMenu,
+    Settings,
     Game,
     GameOver,
-    Restarting,
+    /// Waiting for the players of an online session.
+    Lobby,
+    /// Playing on another machine's online session.
+    OnlineGame,
 }
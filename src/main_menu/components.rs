@@ -1,10 +1,21 @@
 use bevy::prelude::*;
 
+use crate::game::run::components::RunScoped;
+
 #[derive(Component)]
 pub struct MainMenu;
 
 #[derive(Component)]
-pub struct PlayButton;
+pub struct GameModeLabel;
+
+#[derive(Component)]
+pub struct PlayersLabel;
+
+/// Date, modifiers and attempt of the daily challenge, only filled while it's selected.
+#[derive(Component)]
+pub struct DailyChallengeInfo;
 
+/// Hint shown during a demo run that any input leads back to the main menu.
 #[derive(Component)]
-pub struct QuitButton;
+#[require(RunScoped)]
+pub struct DemoBanner;
@@ -1,25 +1,65 @@
 mod components;
+mod resources;
 mod styles;
 mod systems;
 
 use bevy::prelude::*;
+use resources::{AttractTimer, DemoRun};
 use systems::{
-    interactions::{interact_with_play_button, interact_with_quit_button},
+    attract::{
+        end_demo_run_on_input, reset_attract_timer, restore_run_settings, spawn_demo_banner,
+        start_demo_run,
+    },
+    interactions::{
+        cycle_game_mode, cycle_players, update_daily_challenge_info, update_game_mode_label,
+        update_players_label,
+    },
     layout::{despawn_main_menu, spawn_main_menu},
 };
 
+use crate::game::run::RunStart;
 use crate::AppState;
 
+/// Seconds the main menu has to be left alone before bots play a demo run.
+pub const ATTRACT_MODE_DELAY: f32 = 30.0;
+
 pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::MainMenu), spawn_main_menu);
+        app.init_resource::<AttractTimer>();
+        app.add_systems(
+            OnEnter(AppState::MainMenu),
+            (
+                restore_run_settings.run_if(resource_exists::<DemoRun>),
+                reset_attract_timer,
+                spawn_main_menu,
+            )
+                .chain(),
+        );
         app.add_systems(
             Update,
-            (interact_with_play_button, interact_with_quit_button)
+            (
+                (cycle_game_mode, cycle_players),
+                (update_game_mode_label, update_players_label),
+                update_daily_challenge_info,
+                start_demo_run,
+            )
+                .chain()
                 .run_if(in_state(AppState::MainMenu)),
         );
         app.add_systems(OnExit(AppState::MainMenu), despawn_main_menu);
+
+        // Attract mode
+        app.add_systems(
+            RunStart,
+            spawn_demo_banner.run_if(resource_exists::<DemoRun>),
+        );
+        app.add_systems(
+            Update,
+            end_demo_run_on_input
+                .run_if(in_state(AppState::Game).or(in_state(AppState::GameOver)))
+                .run_if(resource_exists::<DemoRun>),
+        );
     }
 }
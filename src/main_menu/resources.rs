@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use super::ATTRACT_MODE_DELAY;
+use crate::game::resources::RunSettings;
+
+/// Time nobody touched the main menu, a demo run starts once it finishes.
+#[derive(Resource, Debug)]
+pub struct AttractTimer {
+    pub timer: Timer,
+}
+
+impl Default for AttractTimer {
+    fn default() -> AttractTimer {
+        AttractTimer {
+            timer: Timer::from_seconds(ATTRACT_MODE_DELAY, TimerMode::Once),
+        }
+    }
+}
+
+/// Marks a run played by bots to attract players, holding the run settings to return to.
+#[derive(Resource, Debug)]
+pub struct DemoRun {
+    pub run_settings: RunSettings,
+}
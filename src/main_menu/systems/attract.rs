@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+
+use crate::{
+    events::{EndRun, StartRun},
+    game::resources::RunSettings,
+    localization::components::LocalizedText,
+    main_menu::{
+        components::DemoBanner,
+        resources::{AttractTimer, DemoRun},
+        styles::DEMO_BANNER_NODE,
+    },
+    widgets::{
+        resources::{UiFont, UiTheme},
+        text_bundle,
+    },
+};
+
+pub fn reset_attract_timer(mut attract_timer: ResMut<AttractTimer>) {
+    attract_timer.timer.reset();
+}
+
+/// Lets bots play a demo run once nobody touched the main menu for a while.
+#[allow(clippy::too_many_arguments)]
+pub fn start_demo_run(
+    mut commands: Commands,
+    mut attract_timer: ResMut<AttractTimer>,
+    mut run_settings: ResMut<RunSettings>,
+    mut start_run_event_writer: EventWriter<StartRun>,
+    mut cursor_moved_event_reader: EventReader<CursorMoved>,
+    gamepad_query: Query<&Gamepad>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    time: Res<Time>,
+) {
+    let cursor_moved = cursor_moved_event_reader.read().count() > 0;
+    if cursor_moved || any_input(&gamepad_query, &keyboard_input, &mouse_input) {
+        attract_timer.timer.reset();
+        return;
+    }
+    if !attract_timer.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    commands.insert_resource(DemoRun {
+        run_settings: run_settings.clone(),
+    });
+    *run_settings = RunSettings {
+        max_stars: run_settings.max_stars,
+        bots: true,
+        ..default()
+    };
+    start_run_event_writer.send(StartRun);
+    info!(target: "state", "Starting a demo run.");
+}
+
+pub fn spawn_demo_banner(mut commands: Commands, ui_theme: Res<UiTheme>, ui_font: Res<UiFont>) {
+    commands.spawn((DEMO_BANNER_NODE, DemoBanner)).with_child((
+        text_bundle("", &ui_font, ui_theme.info_font_size, ui_theme.text_color),
+        LocalizedText::new("main_menu.demo"),
+    ));
+}
+
+/// Returns to the main menu as soon as someone wants to play.
+pub fn end_demo_run_on_input(
+    mut end_run_event_writer: EventWriter<EndRun>,
+    gamepad_query: Query<&Gamepad>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+) {
+    if any_input(&gamepad_query, &keyboard_input, &mouse_input) {
+        end_run_event_writer.send(EndRun);
+    }
+}
+
+/// Puts back the run settings the player chose before the demo run.
+pub fn restore_run_settings(
+    mut commands: Commands,
+    demo_run: Res<DemoRun>,
+    mut run_settings: ResMut<RunSettings>,
+) {
+    *run_settings = demo_run.run_settings.clone();
+    commands.remove_resource::<DemoRun>();
+}
+
+fn any_input(
+    gamepad_query: &Query<&Gamepad>,
+    keyboard_input: &ButtonInput<KeyCode>,
+    mouse_input: &ButtonInput<MouseButton>,
+) -> bool {
+    keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || gamepad_query
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+}
@@ -1,56 +1,117 @@
-use bevy::{app::AppExit, prelude::*};
+use bevy::prelude::*;
 
 use crate::{
-    game::SimulationState,
-    main_menu::{
-        components::{PlayButton, QuitButton},
-        styles::{HOVERED_BUTTON_COLOR, NORMAL_BUTTON_COLOR, PRESSED_BUTTON_COLOR},
+    events::MenuButtonPressed,
+    game::{
+        daily::resources::{DailyChallenge, DailyRecords},
+        mode::GameMode,
+        resources::RunSettings,
+    },
+    localization::components::LocalizedText,
+    main_menu::components::{DailyChallengeInfo, GameModeLabel, PlayersLabel},
+    widgets::{
+        components::ButtonAction,
+        resources::{UiFont, UiTheme},
+        text_bundle,
     },
-    AppState,
 };
 
-type PlayButtonInteraction = (Changed<Interaction>, With<PlayButton>);
+pub fn cycle_game_mode(
+    mut menu_button_event_reader: EventReader<MenuButtonPressed>,
+    mut run_settings: ResMut<RunSettings>,
+) {
+    for event in menu_button_event_reader.read() {
+        if event.action == ButtonAction::CycleGameMode {
+            run_settings.mode = run_settings.mode.next();
+        }
+    }
+}
 
-pub fn interact_with_play_button(
-    mut button_query: Query<(&Interaction, &mut BackgroundColor), PlayButtonInteraction>,
-    mut next_app_state: ResMut<NextState<AppState>>,
-    mut next_simulation_state: ResMut<NextState<SimulationState>>,
+pub fn cycle_players(
+    mut menu_button_event_reader: EventReader<MenuButtonPressed>,
+    mut run_settings: ResMut<RunSettings>,
 ) {
-    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                *background_color = PRESSED_BUTTON_COLOR.into();
-                next_app_state.set(AppState::Game);
-                next_simulation_state.set(SimulationState::Running);
-            }
-            Interaction::Hovered => {
-                *background_color = HOVERED_BUTTON_COLOR.into();
-            }
-            Interaction::None => {
-                *background_color = NORMAL_BUTTON_COLOR.into();
-            }
+    for event in menu_button_event_reader.read() {
+        if event.action == ButtonAction::CyclePlayers {
+            run_settings.cycle_players();
         }
     }
 }
 
-type QuitButtonInteraction = (Changed<Interaction>, With<QuitButton>);
+pub fn update_game_mode_label(
+    mut game_mode_label_query: Query<&mut LocalizedText, With<GameModeLabel>>,
+    run_settings: Res<RunSettings>,
+) {
+    if !run_settings.is_changed() {
+        return;
+    }
+
+    for mut localized_text in &mut game_mode_label_query {
+        *localized_text = LocalizedText::new(run_settings.mode.key());
+    }
+}
+
+pub fn update_players_label(
+    mut players_label_query: Query<&mut LocalizedText, With<PlayersLabel>>,
+    run_settings: Res<RunSettings>,
+) {
+    if !run_settings.is_changed() {
+        return;
+    }
 
-pub fn interact_with_quit_button(
-    mut app_exit_event_writer: EventWriter<AppExit>,
-    mut button_query: Query<(&Interaction, &mut BackgroundColor), QuitButtonInteraction>,
+    for mut localized_text in &mut players_label_query {
+        *localized_text = LocalizedText::new(run_settings.players_key());
+    }
+}
+
+pub fn update_daily_challenge_info(
+    mut commands: Commands,
+    daily_challenge_info_query: Query<(Entity, Ref<DailyChallengeInfo>)>,
+    run_settings: Res<RunSettings>,
+    daily_challenge: Res<DailyChallenge>,
+    daily_records: Res<DailyRecords>,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
 ) {
-    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                *background_color = PRESSED_BUTTON_COLOR.into();
-                app_exit_event_writer.send(AppExit::Success);
-            }
-            Interaction::Hovered => {
-                *background_color = HOVERED_BUTTON_COLOR.into();
-            }
-            Interaction::None => {
-                *background_color = NORMAL_BUTTON_COLOR.into();
-            }
+    for (daily_challenge_info_entity, daily_challenge_info) in &daily_challenge_info_query {
+        if !daily_challenge_info.is_added() && !run_settings.is_changed() {
+            continue;
+        }
+
+        let mut entity_commands = commands.entity(daily_challenge_info_entity);
+        entity_commands.despawn_descendants();
+        if run_settings.mode != GameMode::DailyChallenge {
+            continue;
         }
+
+        entity_commands.with_children(|parent| {
+            // Date of the challenge
+            parent.spawn((
+                text_bundle("", &ui_font, ui_theme.info_font_size, ui_theme.text_color),
+                LocalizedText::new("main_menu.daily.date").with_arg("date", daily_challenge.date),
+            ));
+            // Modifiers of the day
+            for modifier in &daily_challenge.modifiers {
+                parent.spawn((
+                    text_bundle(
+                        "",
+                        &ui_font,
+                        ui_theme.small_font_size,
+                        ui_theme.muted_text_color,
+                    ),
+                    LocalizedText::new(modifier.key()),
+                ));
+            }
+            // Today's attempt
+            parent.spawn((
+                text_bundle("", &ui_font, ui_theme.small_font_size, ui_theme.text_color),
+                match daily_records.score(&daily_challenge.date) {
+                    Some(score) => {
+                        LocalizedText::new("main_menu.daily.played").with_arg("score", score)
+                    }
+                    None => LocalizedText::new("main_menu.daily.available"),
+                },
+            ));
+        });
     }
 }
@@ -1,89 +1,142 @@
 use bevy::prelude::*;
 
 use crate::{
-    game::player::PLAYER_SPRITE,
+    game::{resources::RunSettings, save::resources::SaveSlot},
+    loading::resources::GameAssets,
+    localization::components::LocalizedText,
     main_menu::{
-        components::{MainMenu, PlayButton, QuitButton},
+        components::{DailyChallengeInfo, GameModeLabel, MainMenu, PlayersLabel},
         styles::*,
     },
+    tween::{components::UiTween, despawn_with_tween, MENU_TRANSITION, MENU_TRANSITION_DURATION},
+    widgets::{
+        components::ButtonAction,
+        resources::{UiFont, UiTheme},
+        spawn_button, spawn_menu_button,
+        styles::{MENU_BUTTON_NODE, MENU_NODE},
+        text_bundle,
+    },
 };
 
-pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let _main_menu_entity: Entity = build_main_menu(&mut commands, &asset_server);
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+    run_settings: Res<RunSettings>,
+    save_slot: Res<SaveSlot>,
+) {
+    let _main_menu_entity: Entity = build_main_menu(
+        &mut commands,
+        &game_assets,
+        &ui_theme,
+        &ui_font,
+        &run_settings,
+        &save_slot,
+    );
 }
 
-pub fn despawn_main_menu(mut commands: Commands, query: Query<Entity, With<MainMenu>>) {
-    if let Ok(main_menu_entity) = query.get_single() {
-        commands.entity(main_menu_entity).despawn_recursive();
+pub fn despawn_main_menu(
+    mut commands: Commands,
+    query: Query<Entity, With<MainMenu>>,
+    mut tween_query: Query<&mut UiTween>,
+) {
+    for main_menu_entity in &query {
+        despawn_with_tween(&mut commands, &mut tween_query, main_menu_entity);
     }
 }
 
-pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+pub fn build_main_menu(
+    commands: &mut Commands,
+    game_assets: &GameAssets,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    run_settings: &RunSettings,
+    save_slot: &SaveSlot,
+) -> Entity {
     commands
-        .spawn((MAIN_MENU_NODE, MainMenu))
+        .spawn((
+            MENU_NODE,
+            MainMenu,
+            UiTween::new(MENU_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
         .with_children(|parent| {
             // Title
             parent.spawn(TITLE_NODE).with_children(|parent| {
                 parent.spawn(ImageNode {
-                    image: asset_server.load(PLAYER_SPRITE),
+                    image: game_assets.player_sprite.clone(),
                     ..default()
                 });
                 parent.spawn((
-                    Text::new("Shoot For The Stars"),
-                    TextFont {
-                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 64.0,
-                        ..default()
-                    },
-                    TextColor(Color::WHITE),
+                    text_bundle("", ui_font, ui_theme.title_font_size, ui_theme.text_color),
+                    LocalizedText::new("main_menu.title"),
                 ));
                 parent.spawn(ImageNode {
-                    image: asset_server.load(PLAYER_SPRITE),
+                    image: game_assets.player_sprite.clone(),
                     ..default()
                 });
             });
+            // Continue button
+            if save_slot.exists() {
+                spawn_menu_button(
+                    parent,
+                    ui_theme,
+                    ui_font,
+                    LocalizedText::new("main_menu.continue"),
+                    ButtonAction::ResumeSavedGame,
+                );
+            }
             // Play button
-            parent
-                .spawn((
-                    NORMAL_BUTTON_NODE,
-                    Button,
-                    PlayButton,
-                    BorderRadius::all(Val::Px(10.0)),
-                ))
-                .with_child((
-                    Text::new("Play (P)"),
-                    TextFont {
-                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 32.0,
-                        ..default()
-                    },
-                    TextColor(Color::WHITE),
-                    TextLayout {
-                        justify: JustifyText::Center,
-                        ..default()
-                    },
-                ));
+            spawn_menu_button(
+                parent,
+                ui_theme,
+                ui_font,
+                LocalizedText::new("main_menu.play"),
+                ButtonAction::Play,
+            );
+            // Game mode button
+            spawn_button(
+                parent,
+                MENU_BUTTON_NODE,
+                ui_theme,
+                ui_font,
+                (LocalizedText::new(run_settings.mode.key()), GameModeLabel),
+                ButtonAction::CycleGameMode,
+            );
+            parent.spawn((DAILY_CHALLENGE_INFO_NODE, DailyChallengeInfo));
+            // Players button
+            spawn_button(
+                parent,
+                MENU_BUTTON_NODE,
+                ui_theme,
+                ui_font,
+                (LocalizedText::new(run_settings.players_key()), PlayersLabel),
+                ButtonAction::CyclePlayers,
+            );
+            // Host online game button
+            spawn_menu_button(
+                parent,
+                ui_theme,
+                ui_font,
+                LocalizedText::new("main_menu.host"),
+                ButtonAction::HostOnlineGame,
+            );
+            // Settings button
+            spawn_menu_button(
+                parent,
+                ui_theme,
+                ui_font,
+                LocalizedText::new("main_menu.settings"),
+                ButtonAction::Settings,
+            );
             // Quit button
-            parent
-                .spawn((
-                    NORMAL_BUTTON_NODE,
-                    Button,
-                    QuitButton,
-                    BorderRadius::all(Val::Px(10.0)),
-                ))
-                .with_child((
-                    Text::new("Quit (Q)"),
-                    TextFont {
-                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 32.0,
-                        ..default()
-                    },
-                    TextColor(Color::WHITE),
-                    TextLayout {
-                        justify: JustifyText::Center,
-                        ..default()
-                    },
-                ));
+            spawn_menu_button(
+                parent,
+                ui_theme,
+                ui_font,
+                LocalizedText::new("main_menu.quit"),
+                ButtonAction::Quit,
+            );
         })
         .id()
 }
@@ -1,2 +1,3 @@
+pub mod attract;
 pub mod interactions;
 pub mod layout;
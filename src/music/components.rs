@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct MusicTrack {
+    pub track: &'static str,
+    pub volume: f32,
+    pub fading_out: bool,
+}
+
+/// Layer of the game music whose volume follows the [`MusicIntensity`](super::resources::MusicIntensity).
+#[derive(Component)]
+pub struct IntensityStem;
@@ -0,0 +1,40 @@
+mod components;
+mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::*;
+use systems::*;
+
+use crate::{game::run::RunStart, AppState};
+
+pub const MAIN_MENU_TRACK: &str = "audio/music/main_menu.wav";
+pub const GAME_TRACK: &str = "audio/music/game.wav";
+pub const GAME_INTENSITY_STEM: &str = "audio/music/game_intensity.wav";
+pub const GAME_OVER_TRACK: &str = "audio/music/game_over.wav";
+
+/// Volume of a fully faded-in track.
+const MUSIC_VOLUME: f32 = 0.5;
+/// Volume change per second while crossfading.
+const CROSSFADE_SPEED: f32 = 0.5;
+/// Factor applied to the music volume while the simulation is paused.
+const PAUSED_VOLUME_FACTOR: f32 = 0.3;
+/// Number of enemies on top of the initial ones at which the intensity stem plays at full volume.
+const MAX_INTENSITY_EXTRA_ENEMIES: usize = 12;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicIntensity>()
+            .add_systems(OnEnter(AppState::MainMenu), play_main_menu_music)
+            .add_systems(RunStart, play_game_music)
+            .add_systems(OnEnter(AppState::GameOver), play_game_over_music)
+            .add_systems(
+                Update,
+                update_music_intensity.run_if(in_state(AppState::Game)),
+            )
+            .add_systems(Update, fade_music.after(update_music_intensity));
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// Intensity of the game music between 0.0 (calm) and 1.0 (hectic).
+#[derive(Resource, Default, Debug)]
+pub struct MusicIntensity {
+    pub value: f32,
+}
@@ -0,0 +1,139 @@
+use bevy::{audio::Volume, prelude::*};
+
+use super::components::{IntensityStem, MusicTrack};
+use super::resources::MusicIntensity;
+use super::{
+    CROSSFADE_SPEED, GAME_INTENSITY_STEM, GAME_OVER_TRACK, GAME_TRACK, MAIN_MENU_TRACK,
+    MAX_INTENSITY_EXTRA_ENEMIES, MUSIC_VOLUME, PAUSED_VOLUME_FACTOR,
+};
+use crate::game::enemy::{components::Enemy, INITIAL_NUMBER_OF_ENEMIES};
+use crate::game::SimulationState;
+use crate::loading::resources::GameAssets;
+use crate::AppState;
+
+fn create_music_bundle(music: &Handle<AudioSource>, track: &'static str) -> impl Bundle {
+    (
+        AudioPlayer(music.clone()),
+        PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+        MusicTrack {
+            track,
+            volume: 0.0,
+            fading_out: false,
+        },
+    )
+}
+
+/// Fades out all music except the given track and returns whether the track is already playing.
+fn fade_out_music(
+    music_query: &mut Query<&mut MusicTrack>,
+    keep_track: Option<&'static str>,
+) -> bool {
+    let mut is_playing = false;
+    for mut music_track in music_query {
+        if Some(music_track.track) == keep_track && !music_track.fading_out {
+            is_playing = true;
+        } else {
+            music_track.fading_out = true;
+        }
+    }
+    is_playing
+}
+
+pub fn play_main_menu_music(
+    mut commands: Commands,
+    mut music_query: Query<&mut MusicTrack>,
+    game_assets: Res<GameAssets>,
+) {
+    if !fade_out_music(&mut music_query, Some(MAIN_MENU_TRACK)) {
+        commands.spawn(create_music_bundle(
+            &game_assets.main_menu_music,
+            MAIN_MENU_TRACK,
+        ));
+    }
+}
+
+pub fn play_game_music(
+    mut commands: Commands,
+    mut music_query: Query<&mut MusicTrack>,
+    game_assets: Res<GameAssets>,
+    mut music_intensity: ResMut<MusicIntensity>,
+) {
+    // Restarting a run also restarts the game music from the beginning.
+    fade_out_music(&mut music_query, None);
+    music_intensity.value = 0.0;
+    // Both layers have the same length, so they stay in sync while looping.
+    commands.spawn(create_music_bundle(&game_assets.game_music, GAME_TRACK));
+    commands.spawn((
+        create_music_bundle(&game_assets.game_intensity_music, GAME_INTENSITY_STEM),
+        IntensityStem,
+    ));
+}
+
+pub fn play_game_over_music(
+    mut commands: Commands,
+    mut music_query: Query<&mut MusicTrack>,
+    game_assets: Res<GameAssets>,
+) {
+    if !fade_out_music(&mut music_query, Some(GAME_OVER_TRACK)) {
+        commands.spawn(create_music_bundle(
+            &game_assets.game_over_music,
+            GAME_OVER_TRACK,
+        ));
+    }
+}
+
+pub fn update_music_intensity(
+    mut music_intensity: ResMut<MusicIntensity>,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    let extra_enemies = enemy_query
+        .iter()
+        .count()
+        .saturating_sub(INITIAL_NUMBER_OF_ENEMIES);
+    let intensity = (extra_enemies as f32 / MAX_INTENSITY_EXTRA_ENEMIES as f32).min(1.0);
+
+    if music_intensity.value != intensity {
+        music_intensity.value = intensity;
+    }
+}
+
+pub fn fade_music(
+    mut commands: Commands,
+    mut music_query: Query<(
+        Entity,
+        &mut MusicTrack,
+        Option<&AudioSink>,
+        Has<IntensityStem>,
+    )>,
+    music_intensity: Res<MusicIntensity>,
+    app_state: Res<State<AppState>>,
+    simulation_state: Res<State<SimulationState>>,
+    time: Res<Time>,
+) {
+    let mut volume_factor = 1.0;
+    if *app_state.get() == AppState::Game && *simulation_state.get() == SimulationState::Paused {
+        volume_factor = PAUSED_VOLUME_FACTOR;
+    }
+    let max_step = CROSSFADE_SPEED * time.delta_secs();
+
+    for (music_entity, mut music_track, audio_sink, is_intensity_stem) in &mut music_query {
+        let target_volume = if music_track.fading_out {
+            0.0
+        } else if is_intensity_stem {
+            MUSIC_VOLUME * volume_factor * music_intensity.value
+        } else {
+            MUSIC_VOLUME * volume_factor
+        };
+
+        let volume_difference = target_volume - music_track.volume;
+        music_track.volume += volume_difference.clamp(-max_step, max_step);
+
+        if let Some(audio_sink) = audio_sink {
+            audio_sink.set_volume(music_track.volume);
+        }
+
+        if music_track.fading_out && music_track.volume <= 0.0 {
+            commands.entity(music_entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::game::player::components::PlayerId;
+
+/// Entity drawn on a client from the host's snapshots.
+#[derive(Component)]
+pub struct NetworkEntity {
+    pub id: u64,
+}
+
+/// Moves an entity from its last position to the one of the newest snapshot.
+#[derive(Component, Debug)]
+pub struct Interpolation {
+    pub from: Vec2,
+    pub to: Vec2,
+    pub elapsed: f32,
+}
+
+#[derive(Component)]
+pub struct OnlineHUD;
+
+#[derive(Component)]
+pub struct OnlineScoreInfo;
+
+#[derive(Component)]
+pub struct OnlinePlayerInfo(pub PlayerId);
+
+#[derive(Component)]
+pub struct RoundOverInfo;
@@ -0,0 +1,133 @@
+pub mod components;
+pub mod protocol;
+pub mod resources;
+mod styles;
+mod systems;
+
+use bevy::prelude::*;
+use systems::{client::*, host::*, rollback::*, view::*};
+
+use resources::{ClientSession, HostSession, HostSnapshot, RollbackSession};
+
+use crate::game::{player::read_local_input, run::RunStart, SimulationState};
+use crate::AppState;
+
+/// Identifies the game's datagrams, so that stray traffic on the port is ignored.
+pub const PROTOCOL_MAGIC: [u8; 4] = *b"SFTS";
+/// Bumped whenever the message format changes, hosts reject clients of other versions.
+pub const PROTOCOL_VERSION: u16 = 2;
+pub const DEFAULT_PORT: u16 = 7777;
+/// Seconds between two snapshots of the host.
+pub const SNAPSHOT_INTERVAL: f32 = 1.0 / 30.0;
+/// Seconds between two join requests of a client that got no answer yet.
+pub const JOIN_RETRY_INTERVAL: f32 = 0.5;
+/// Seconds without a datagram after which the other side counts as gone.
+pub const CONNECTION_TIMEOUT: f32 = 5.0;
+pub const MAX_DATAGRAM_SIZE: usize = 8192;
+/// Frames a rollback peer simulates ahead of the peer's last received input before waiting.
+pub const MAX_PREDICTION_FRAMES: u32 = 8;
+pub const MAX_INPUTS_PER_DATAGRAM: usize = 128;
+/// Frames between two checksums compared in desync checks.
+pub const CHECKSUM_INTERVAL: u32 = 30;
+
+/// Online sessions, either host-authoritative where the host runs the game and sends snapshots
+/// to clients, or peer-to-peer where both peers simulate the run and roll back on late inputs.
+pub struct NetworkPlugin;
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut App) {
+        // Host
+        app.add_systems(
+            Update,
+            host_online_game.run_if(in_state(AppState::MainMenu)),
+        )
+        .add_systems(
+            PreUpdate,
+            receive_client_messages.run_if(resource_exists::<HostSession>),
+        )
+        .add_systems(
+            Update,
+            (
+                (mark_remote_players, apply_remote_inputs)
+                    .chain()
+                    .before(read_local_input)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+                announce_round_over,
+                // Snapshots keep the clients connected outside of runs as well
+                send_snapshots,
+            )
+                .run_if(resource_exists::<HostSession>),
+        )
+        .add_systems(
+            RunStart,
+            announce_run_start.run_if(resource_exists::<HostSession>),
+        )
+        .add_systems(
+            OnEnter(AppState::MainMenu),
+            close_host_session.run_if(resource_exists::<HostSession>),
+        );
+
+        // Client
+        app.add_systems(
+            PreUpdate,
+            receive_host_messages.run_if(resource_exists::<ClientSession>),
+        )
+        .add_systems(
+            Update,
+            (join_host, send_client_input).run_if(resource_exists::<ClientSession>),
+        )
+        .add_systems(OnEnter(AppState::OnlineGame), spawn_online_hud)
+        .add_systems(
+            Update,
+            (
+                apply_host_snapshot.run_if(resource_exists_and_changed::<HostSnapshot>),
+                interpolate_network_entities,
+                update_online_hud.run_if(resource_exists::<HostSnapshot>),
+                update_round_over_info,
+                leave_online_game_on_key,
+            )
+                .chain()
+                .run_if(in_state(AppState::OnlineGame))
+                .run_if(resource_exists::<ClientSession>),
+        )
+        .add_systems(OnExit(AppState::OnlineGame), despawn_online_game)
+        .add_systems(
+            OnEnter(AppState::MainMenu),
+            close_client_session.run_if(resource_exists::<ClientSession>),
+        );
+
+        // Rollback
+        app.add_systems(
+            PreUpdate,
+            receive_peer_messages.run_if(resource_exists::<RollbackSession>),
+        )
+        .add_systems(
+            Update,
+            (
+                sync_with_peer,
+                send_local_inputs.run_if(in_state(AppState::Game).or(in_state(AppState::GameOver))),
+            )
+                .run_if(resource_exists::<RollbackSession>),
+        )
+        .add_systems(
+            RunStart,
+            reset_rollback_timeline.run_if(resource_exists::<RollbackSession>),
+        )
+        .add_systems(
+            FixedUpdate,
+            advance_rollback
+                .run_if(resource_exists::<RollbackSession>)
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(SimulationState::Running)),
+        )
+        .add_systems(
+            PostUpdate,
+            flush_delayed_datagrams.run_if(resource_exists::<RollbackSession>),
+        )
+        .add_systems(
+            OnEnter(AppState::MainMenu),
+            close_rollback_session.run_if(resource_exists::<RollbackSession>),
+        );
+    }
+}
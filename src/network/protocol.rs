@@ -0,0 +1,687 @@
+use bevy::prelude::*;
+use thiserror::Error;
+
+use super::{PROTOCOL_MAGIC, PROTOCOL_VERSION};
+use crate::game::{
+    mode::GameMode,
+    player::{components::PlayerId, MAX_PLAYERS},
+};
+
+/// Datagrams exchanged between the host and its clients.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// Asks the host for a place in the session, repeated until it's answered.
+    Join,
+    /// Movement direction of the client's player.
+    Input {
+        sequence: u32,
+        direction: Vec2,
+    },
+    /// The client leaves the session.
+    Leave,
+    /// The host accepted the client, which controls the given player.
+    Welcome {
+        player_id: PlayerId,
+    },
+    Rejected {
+        reason: RejectReason,
+    },
+    /// Number of players in the lobby.
+    Lobby {
+        players: u8,
+    },
+    /// A new run begins in an arena of the given size.
+    Start {
+        mode: GameMode,
+        players: u8,
+        arena: Vec2,
+    },
+    Snapshot(WorldSnapshot),
+    /// The run is over, the host decides what follows.
+    RoundOver {
+        score: u32,
+    },
+    /// The host closed the session, or a rollback peer left.
+    Disconnect,
+    /// Introduces a rollback peer, repeated until the other peer answered.
+    RollbackSync {
+        /// Random number that decides which peer plays the first player.
+        nonce: u64,
+        seed: u64,
+        mode: GameMode,
+    },
+    /// Inputs of the sender's player from the frame `start` on.
+    RollbackInput {
+        /// Counts the runs of the session, inputs of other runs are dropped.
+        run: u32,
+        /// First frame whose input the sender is still missing from the receiver.
+        ack: u32,
+        start: u32,
+        directions: Vec<Vec2>,
+        /// Checksum of the sender's confirmed state at a frame.
+        checksum: Option<(u32, u64)>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    SessionFull,
+    InProgress,
+    VersionMismatch,
+}
+
+/// State of the host's run as far as clients need it to draw the game.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorldSnapshot {
+    /// Increases with every snapshot the host sends, so that late datagrams can be dropped.
+    pub tick: u32,
+    pub score: u32,
+    pub players: Vec<PlayerState>,
+    pub entities: Vec<EntityState>,
+}
+
+impl WorldSnapshot {
+    /// Bytes of an encoded snapshot message without any players or entities.
+    const ENCODED_BASE_SIZE: usize = PROTOCOL_MAGIC.len() + 2 + 1 + 4 + 4 + 2 + 2;
+    const ENCODED_PLAYER_SIZE: usize = 1 + 4 + 4;
+
+    pub fn encoded_size(&self) -> usize {
+        Self::ENCODED_BASE_SIZE
+            + self.players.len() * Self::ENCODED_PLAYER_SIZE
+            + self
+                .entities
+                .iter()
+                .map(EntityState::encoded_size)
+                .sum::<usize>()
+    }
+
+    /// Leaves out the last entities until the encoded snapshot fits into the given number of bytes,
+    /// returns how many were left out.
+    pub fn fit_to(&mut self, max_size: usize) -> usize {
+        let mut size = self.encoded_size();
+        let mut kept = self.entities.len();
+        while size > max_size && kept > 0 {
+            kept -= 1;
+            size -= self.entities[kept].encoded_size();
+        }
+        let dropped = self.entities.len() - kept;
+        self.entities.truncate(kept);
+        dropped
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerState {
+    pub player_id: PlayerId,
+    pub health: u32,
+    pub stars: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityState {
+    /// Identifies the entity across snapshots.
+    pub id: u64,
+    pub kind: EntityKind,
+    pub position: Vec2,
+}
+
+impl EntityState {
+    fn encoded_size(&self) -> usize {
+        let kind_size = match self.kind {
+            EntityKind::Player(_) => 2,
+            EntityKind::Enemy | EntityKind::Star => 1,
+        };
+        8 + kind_size + 8
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityKind {
+    Player(PlayerId),
+    Enemy,
+    Star,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ProtocolError {
+    #[error("datagram is not part of the game protocol")]
+    NotGameProtocol,
+    #[error("protocol version {0} is not supported, expected {PROTOCOL_VERSION}")]
+    UnsupportedVersion(u16),
+    #[error("unknown {kind} tag {tag}")]
+    UnknownTag { kind: &'static str, tag: u8 },
+    #[error("datagram ended unexpectedly")]
+    Truncated,
+}
+
+impl Message {
+    /// Encodes the message behind a header of the protocol magic, version and message tag.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.bytes(&PROTOCOL_MAGIC);
+        writer.u16(PROTOCOL_VERSION);
+
+        match self {
+            Message::Join => writer.u8(0),
+            Message::Input {
+                sequence,
+                direction,
+            } => {
+                writer.u8(1);
+                writer.u32(*sequence);
+                writer.vec2(*direction);
+            }
+            Message::Leave => writer.u8(2),
+            Message::Welcome { player_id } => {
+                writer.u8(3);
+                writer.u8(player_id.0 as u8);
+            }
+            Message::Rejected { reason } => {
+                writer.u8(4);
+                writer.u8(match reason {
+                    RejectReason::SessionFull => 0,
+                    RejectReason::InProgress => 1,
+                    RejectReason::VersionMismatch => 2,
+                });
+            }
+            Message::Lobby { players } => {
+                writer.u8(5);
+                writer.u8(*players);
+            }
+            Message::Start {
+                mode,
+                players,
+                arena,
+            } => {
+                writer.u8(6);
+                writer.u8(encode_mode(*mode));
+                writer.u8(*players);
+                writer.vec2(*arena);
+            }
+            Message::Snapshot(snapshot) => {
+                writer.u8(7);
+                writer.u32(snapshot.tick);
+                writer.u32(snapshot.score);
+                writer.u16(snapshot.players.len() as u16);
+                for player in &snapshot.players {
+                    writer.u8(player.player_id.0 as u8);
+                    writer.u32(player.health);
+                    writer.u32(player.stars);
+                }
+                writer.u16(snapshot.entities.len() as u16);
+                for entity in &snapshot.entities {
+                    writer.u64(entity.id);
+                    match entity.kind {
+                        EntityKind::Player(player_id) => {
+                            writer.u8(0);
+                            writer.u8(player_id.0 as u8);
+                        }
+                        EntityKind::Enemy => writer.u8(1),
+                        EntityKind::Star => writer.u8(2),
+                    }
+                    writer.vec2(entity.position);
+                }
+            }
+            Message::RoundOver { score } => {
+                writer.u8(8);
+                writer.u32(*score);
+            }
+            Message::Disconnect => writer.u8(9),
+            Message::RollbackSync { nonce, seed, mode } => {
+                writer.u8(10);
+                writer.u64(*nonce);
+                writer.u64(*seed);
+                writer.u8(encode_mode(*mode));
+            }
+            Message::RollbackInput {
+                run,
+                ack,
+                start,
+                directions,
+                checksum,
+            } => {
+                writer.u8(11);
+                writer.u32(*run);
+                writer.u32(*ack);
+                writer.u32(*start);
+                writer.u16(directions.len() as u16);
+                for direction in directions {
+                    writer.vec2(*direction);
+                }
+                match checksum {
+                    Some((frame, checksum)) => {
+                        writer.u8(1);
+                        writer.u32(*frame);
+                        writer.u64(*checksum);
+                    }
+                    None => writer.u8(0),
+                }
+            }
+        }
+
+        writer.0
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Message, ProtocolError> {
+        let mut reader = Reader { bytes };
+        if reader.bytes(PROTOCOL_MAGIC.len())? != PROTOCOL_MAGIC {
+            return Err(ProtocolError::NotGameProtocol);
+        }
+        let version = reader.u16()?;
+        if version != PROTOCOL_VERSION {
+            return Err(ProtocolError::UnsupportedVersion(version));
+        }
+
+        let message = match reader.u8()? {
+            0 => Message::Join,
+            1 => Message::Input {
+                sequence: reader.u32()?,
+                direction: reader.vec2()?,
+            },
+            2 => Message::Leave,
+            3 => Message::Welcome {
+                player_id: reader.player_id()?,
+            },
+            4 => Message::Rejected {
+                reason: match reader.u8()? {
+                    0 => RejectReason::SessionFull,
+                    1 => RejectReason::InProgress,
+                    2 => RejectReason::VersionMismatch,
+                    tag => {
+                        return Err(ProtocolError::UnknownTag {
+                            kind: "reason",
+                            tag,
+                        })
+                    }
+                },
+            },
+            5 => Message::Lobby {
+                players: reader.u8()?,
+            },
+            6 => Message::Start {
+                mode: decode_mode(reader.u8()?)?,
+                players: reader.u8()?,
+                arena: reader.vec2()?,
+            },
+            7 => {
+                let tick = reader.u32()?;
+                let score = reader.u32()?;
+                let players = (0..reader.u16()?)
+                    .map(|_| {
+                        Ok(PlayerState {
+                            player_id: reader.player_id()?,
+                            health: reader.u32()?,
+                            stars: reader.u32()?,
+                        })
+                    })
+                    .collect::<Result<_, ProtocolError>>()?;
+                let entities = (0..reader.u16()?)
+                    .map(|_| {
+                        Ok(EntityState {
+                            id: reader.u64()?,
+                            kind: match reader.u8()? {
+                                0 => EntityKind::Player(reader.player_id()?),
+                                1 => EntityKind::Enemy,
+                                2 => EntityKind::Star,
+                                tag => {
+                                    return Err(ProtocolError::UnknownTag {
+                                        kind: "entity",
+                                        tag,
+                                    })
+                                }
+                            },
+                            position: reader.vec2()?,
+                        })
+                    })
+                    .collect::<Result<_, ProtocolError>>()?;
+                Message::Snapshot(WorldSnapshot {
+                    tick,
+                    score,
+                    players,
+                    entities,
+                })
+            }
+            8 => Message::RoundOver {
+                score: reader.u32()?,
+            },
+            9 => Message::Disconnect,
+            10 => Message::RollbackSync {
+                nonce: reader.u64()?,
+                seed: reader.u64()?,
+                mode: decode_mode(reader.u8()?)?,
+            },
+            11 => Message::RollbackInput {
+                run: reader.u32()?,
+                ack: reader.u32()?,
+                start: reader.u32()?,
+                directions: (0..reader.u16()?)
+                    .map(|_| reader.vec2())
+                    .collect::<Result<_, ProtocolError>>()?,
+                checksum: match reader.u8()? {
+                    0 => None,
+                    _ => Some((reader.u32()?, reader.u64()?)),
+                },
+            },
+            tag => {
+                return Err(ProtocolError::UnknownTag {
+                    kind: "message",
+                    tag,
+                })
+            }
+        };
+        Ok(message)
+    }
+}
+
+fn encode_mode(mode: GameMode) -> u8 {
+    match mode {
+        GameMode::Endless => 0,
+        GameMode::TimeAttack => 1,
+        GameMode::Zen => 2,
+        GameMode::DailyChallenge => 3,
+        GameMode::Versus => 4,
+    }
+}
+
+fn decode_mode(tag: u8) -> Result<GameMode, ProtocolError> {
+    match tag {
+        0 => Ok(GameMode::Endless),
+        1 => Ok(GameMode::TimeAttack),
+        2 => Ok(GameMode::Zen),
+        3 => Ok(GameMode::DailyChallenge),
+        4 => Ok(GameMode::Versus),
+        tag => Err(ProtocolError::UnknownTag { kind: "mode", tag }),
+    }
+}
+
+/// Little-endian encoding of the message fields.
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn vec2(&mut self, value: Vec2) {
+        self.bytes(&value.x.to_le_bytes());
+        self.bytes(&value.y.to_le_bytes());
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], ProtocolError> {
+        if self.bytes.len() < count {
+            return Err(ProtocolError::Truncated);
+        }
+        let (bytes, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ProtocolError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, ProtocolError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ProtocolError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, ProtocolError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, ProtocolError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, ProtocolError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    fn vec2(&mut self) -> Result<Vec2, ProtocolError> {
+        Ok(Vec2::new(self.f32()?, self.f32()?))
+    }
+
+    fn player_id(&mut self) -> Result<PlayerId, ProtocolError> {
+        let index = self.u8()? as usize;
+        if index >= MAX_PLAYERS {
+            return Err(ProtocolError::UnknownTag {
+                kind: "player",
+                tag: index as u8,
+            });
+        }
+        Ok(PlayerId(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::MAX_DATAGRAM_SIZE;
+
+    fn snapshot(entities: usize) -> WorldSnapshot {
+        WorldSnapshot {
+            tick: 12,
+            score: 34,
+            players: vec![
+                PlayerState {
+                    player_id: PlayerId(0),
+                    health: 3,
+                    stars: 20,
+                },
+                PlayerState {
+                    player_id: PlayerId(1),
+                    health: 1,
+                    stars: 14,
+                },
+            ],
+            entities: (0..entities)
+                .map(|index| EntityState {
+                    id: index as u64 | 1 << 40,
+                    kind: match index % 3 {
+                        0 => EntityKind::Player(PlayerId(1)),
+                        1 => EntityKind::Enemy,
+                        _ => EntityKind::Star,
+                    },
+                    position: Vec2::new(index as f32, -1.5),
+                })
+                .collect(),
+        }
+    }
+
+    fn all_messages() -> Vec<Message> {
+        vec![
+            Message::Join,
+            Message::Input {
+                sequence: 7,
+                direction: Vec2::new(-0.5, 1.0),
+            },
+            Message::Leave,
+            Message::Welcome {
+                player_id: PlayerId(2),
+            },
+            Message::Rejected {
+                reason: RejectReason::SessionFull,
+            },
+            Message::Rejected {
+                reason: RejectReason::InProgress,
+            },
+            Message::Rejected {
+                reason: RejectReason::VersionMismatch,
+            },
+            Message::Lobby { players: 3 },
+            Message::Start {
+                mode: GameMode::TimeAttack,
+                players: 2,
+                arena: Vec2::new(1280.0, 720.0),
+            },
+            Message::Snapshot(WorldSnapshot::default()),
+            Message::Snapshot(snapshot(9)),
+            Message::RoundOver { score: 99 },
+            Message::Disconnect,
+            Message::RollbackSync {
+                nonce: u64::MAX,
+                seed: 42,
+                mode: GameMode::Zen,
+            },
+            Message::RollbackInput {
+                run: 2,
+                ack: 30,
+                start: 28,
+                directions: vec![Vec2::X, Vec2::ZERO, Vec2::NEG_Y],
+                checksum: Some((30, 0xdead_beef)),
+            },
+            Message::RollbackInput {
+                run: 0,
+                ack: 0,
+                start: 0,
+                directions: Vec::new(),
+                checksum: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn round_trips_every_message() {
+        for message in all_messages() {
+            assert_eq!(Message::decode(&message.encode()), Ok(message));
+        }
+    }
+
+    #[test]
+    fn round_trips_every_mode() {
+        for mode in [
+            GameMode::Endless,
+            GameMode::TimeAttack,
+            GameMode::Zen,
+            GameMode::DailyChallenge,
+            GameMode::Versus,
+        ] {
+            assert_eq!(decode_mode(encode_mode(mode)), Ok(mode));
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_datagrams() {
+        for message in all_messages() {
+            let datagram = message.encode();
+            for length in 0..datagram.len() {
+                assert_eq!(
+                    Message::decode(&datagram[..length]),
+                    Err(ProtocolError::Truncated),
+                    "{message:?} cut to {length} bytes"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_other_protocols() {
+        let mut datagram = Message::Join.encode();
+        datagram[0] ^= 0xff;
+        assert_eq!(
+            Message::decode(&datagram),
+            Err(ProtocolError::NotGameProtocol)
+        );
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut datagram = Message::Join.encode();
+        let version = PROTOCOL_VERSION + 1;
+        datagram[PROTOCOL_MAGIC.len()..PROTOCOL_MAGIC.len() + 2]
+            .copy_from_slice(&version.to_le_bytes());
+        assert_eq!(
+            Message::decode(&datagram),
+            Err(ProtocolError::UnsupportedVersion(version))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_tags() {
+        let mut datagram = Message::Join.encode();
+        *datagram.last_mut().unwrap() = 200;
+        assert_eq!(
+            Message::decode(&datagram),
+            Err(ProtocolError::UnknownTag {
+                kind: "message",
+                tag: 200
+            })
+        );
+
+        let mut datagram = Message::Welcome {
+            player_id: PlayerId(0),
+        }
+        .encode();
+        *datagram.last_mut().unwrap() = MAX_PLAYERS as u8;
+        assert_eq!(
+            Message::decode(&datagram),
+            Err(ProtocolError::UnknownTag {
+                kind: "player",
+                tag: MAX_PLAYERS as u8
+            })
+        );
+    }
+
+    #[test]
+    fn computes_the_encoded_size_of_snapshots() {
+        for entities in [0, 1, 2, 3, 50] {
+            let snapshot = snapshot(entities);
+            assert_eq!(
+                snapshot.encoded_size(),
+                Message::Snapshot(snapshot.clone()).encode().len()
+            );
+        }
+    }
+
+    #[test]
+    fn fits_snapshots_into_a_datagram() {
+        let mut small = snapshot(10);
+        assert_eq!(small.fit_to(MAX_DATAGRAM_SIZE), 0);
+        assert_eq!(small.entities.len(), 10);
+
+        let mut large = snapshot(1000);
+        let dropped = large.fit_to(MAX_DATAGRAM_SIZE);
+        assert!(dropped > 0);
+        assert_eq!(large.entities.len(), 1000 - dropped);
+        assert!(Message::Snapshot(large.clone()).encode().len() <= MAX_DATAGRAM_SIZE);
+        // Only as many entities as necessary are left out
+        let mut one_more = large.clone();
+        one_more
+            .entities
+            .push(snapshot(1000).entities[large.entities.len()].clone());
+        assert!(one_more.encoded_size() > MAX_DATAGRAM_SIZE);
+        // Entities are kept in order
+        assert_eq!(
+            large.entities[..],
+            snapshot(1000).entities[..large.entities.len()]
+        );
+    }
+}
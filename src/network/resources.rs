@@ -0,0 +1,455 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+
+use super::protocol::{Message, ProtocolError, WorldSnapshot};
+use super::{JOIN_RETRY_INTERVAL, MAX_DATAGRAM_SIZE, SNAPSHOT_INTERVAL};
+use crate::{
+    events::{AsteroidImpact, EnemyBounced, GameOver, PlayerOut, StarCollected},
+    game::{
+        mode::GameMode,
+        player::{components::PlayerId, MAX_PLAYERS},
+        resources::RunSettings,
+        simulation::snapshot::SimulationSnapshot,
+    },
+};
+
+/// Session of the machine that runs the game for everyone.
+#[derive(Resource, Debug)]
+pub struct HostSession {
+    socket: UdpSocket,
+    pub peers: Vec<Peer>,
+    pub snapshot_timer: Timer,
+    pub tick: u32,
+}
+
+/// Client connected to the host.
+#[derive(Debug)]
+pub struct Peer {
+    pub address: SocketAddr,
+    pub player_id: PlayerId,
+    pub direction: Vec2,
+    pub last_sequence: u32,
+    /// Seconds since the last datagram of the client.
+    pub silence: f32,
+}
+
+/// Session of a machine that plays on a host.
+#[derive(Resource, Debug)]
+pub struct ClientSession {
+    socket: UdpSocket,
+    pub host: SocketAddr,
+    /// Player controlled by this client, once the host accepted it.
+    pub player_id: Option<PlayerId>,
+    pub lobby_players: u8,
+    /// Players and arena size of the host's current run.
+    pub run_players: u8,
+    pub host_arena: Vec2,
+    pub sequence: u32,
+    pub last_tick: Option<u32>,
+    /// Final score of the host's run once it's over.
+    pub round_over: Option<u32>,
+    /// Seconds since the last datagram of the host.
+    pub silence: f32,
+    pub join_timer: Timer,
+}
+
+/// Latest snapshot received from the host.
+#[derive(Resource, Debug)]
+pub struct HostSnapshot {
+    pub snapshot: WorldSnapshot,
+}
+
+/// Peer-to-peer session in which both machines simulate the run and roll back on late inputs.
+#[derive(Resource, Debug)]
+pub struct RollbackSession {
+    socket: UdpSocket,
+    pub peer: SocketAddr,
+    /// Random number that decides which peer plays the first player.
+    pub nonce: u64,
+    /// Seed and mode proposed to the peer, the first player's proposal is played.
+    pub seed: u64,
+    pub mode: GameMode,
+    /// Player controlled by this machine, once the peer answered.
+    pub local_player: Option<PlayerId>,
+    /// Overrides the input delay of the settings.
+    pub input_delay: Option<u32>,
+    /// Artificial delay of outgoing datagrams, to try out rollbacks on a single machine.
+    pub latency: Duration,
+    /// Exchange checksums of confirmed frames and report diverging simulations.
+    pub desync_check: bool,
+    outgoing: VecDeque<(Duration, Vec<u8>)>,
+    /// Settings of local runs, restored when the session ends.
+    pub local_run_settings: Option<RunSettings>,
+    /// Counts the runs played in this session.
+    pub run: u32,
+    pub timeline: RollbackTimeline,
+    pub checked_frames: u32,
+    pub desyncs: u32,
+    /// Seconds since the last datagram of the peer.
+    pub silence: f32,
+    pub sync_timer: Timer,
+}
+
+/// Inputs and snapshots of the current run, indexed by simulation frame.
+#[derive(Debug, Default)]
+pub struct RollbackTimeline {
+    /// Next frame to simulate.
+    pub frame: u32,
+    /// First frame whose input of the peer is still missing.
+    pub received_frame: u32,
+    /// First local input the peer hasn't received yet.
+    pub peer_ack: u32,
+    pub input_delay: u32,
+    /// Earliest frame that was simulated with a wrong prediction.
+    pub rollback_frame: Option<u32>,
+    pub local_inputs: BTreeMap<u32, Vec2>,
+    pub peer_inputs: BTreeMap<u32, Vec2>,
+    /// Peer inputs the frames without a received input were simulated with.
+    pub predicted_inputs: BTreeMap<u32, Vec2>,
+    /// State before each frame that could still be rolled back.
+    pub snapshots: BTreeMap<u32, SimulationSnapshot>,
+    pub checksums: BTreeMap<u32, u64>,
+    pub peer_checksums: BTreeMap<u32, u64>,
+    /// Latest frame whose checksums were compared.
+    pub checked_frame: Option<u32>,
+    /// Events of predicted frames, held back until their frame is confirmed.
+    pub held_events: Vec<(u32, HeldEvent)>,
+}
+
+#[derive(Debug)]
+pub enum HeldEvent {
+    GameOver(GameOver),
+    PlayerOut(PlayerOut),
+    StarCollected(StarCollected),
+    AsteroidImpact(AsteroidImpact),
+    EnemyBounced(EnemyBounced),
+}
+
+impl HostSession {
+    pub fn bind(port: u16) -> io::Result<HostSession> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        socket.set_nonblocking(true)?;
+        Ok(HostSession {
+            socket,
+            peers: Vec::new(),
+            snapshot_timer: Timer::from_seconds(SNAPSHOT_INTERVAL, TimerMode::Repeating),
+            tick: 0,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.socket.local_addr().map_or(0, |address| address.port())
+    }
+
+    /// The host plays as well, so it counts towards the players.
+    pub fn player_count(&self) -> usize {
+        self.peers.len() + 1
+    }
+
+    /// Lowest player that isn't taken yet, the host is always the first player.
+    pub fn free_player_id(&self) -> Option<PlayerId> {
+        (1..MAX_PLAYERS)
+            .map(PlayerId)
+            .find(|player_id| self.peers.iter().all(|peer| peer.player_id != *player_id))
+    }
+
+    pub fn peer_mut(&mut self, address: SocketAddr) -> Option<&mut Peer> {
+        self.peers.iter_mut().find(|peer| peer.address == address)
+    }
+
+    pub fn receive(&self) -> Option<(SocketAddr, Result<Message, ProtocolError>)> {
+        receive(&self.socket)
+    }
+
+    pub fn send(&self, address: SocketAddr, message: &Message) {
+        send(&self.socket, address, message);
+    }
+
+    pub fn broadcast(&self, message: &Message) {
+        let datagram = message.encode();
+        for peer in &self.peers {
+            if let Err(error) = self.socket.send_to(&datagram, peer.address) {
+                warn!(target: "network", "Could not send to {}: {error}", peer.address);
+            }
+        }
+    }
+}
+
+impl ClientSession {
+    pub fn connect(host: SocketAddr) -> io::Result<ClientSession> {
+        let socket = match host {
+            SocketAddr::V4(_) => UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?,
+            SocketAddr::V6(_) => UdpSocket::bind((Ipv6Addr::UNSPECIFIED, 0))?,
+        };
+        socket.set_nonblocking(true)?;
+        let mut join_timer = Timer::from_seconds(JOIN_RETRY_INTERVAL, TimerMode::Repeating);
+        // The first join request goes out right away
+        join_timer.set_elapsed(join_timer.duration());
+        Ok(ClientSession {
+            socket,
+            host,
+            player_id: None,
+            lobby_players: 0,
+            run_players: 0,
+            host_arena: Vec2::ZERO,
+            sequence: 0,
+            last_tick: None,
+            round_over: None,
+            silence: 0.0,
+            join_timer,
+        })
+    }
+
+    pub fn receive(&self) -> Option<(SocketAddr, Result<Message, ProtocolError>)> {
+        receive(&self.socket)
+    }
+
+    pub fn send(&self, message: &Message) {
+        send(&self.socket, self.host, message);
+    }
+}
+
+impl RollbackSession {
+    pub fn bind(
+        port: u16,
+        peer: SocketAddr,
+        seed: u64,
+        mode: GameMode,
+    ) -> io::Result<RollbackSession> {
+        let socket = match peer {
+            SocketAddr::V4(_) => UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?,
+            SocketAddr::V6(_) => UdpSocket::bind((Ipv6Addr::UNSPECIFIED, port))?,
+        };
+        socket.set_nonblocking(true)?;
+        let mut sync_timer = Timer::from_seconds(JOIN_RETRY_INTERVAL, TimerMode::Repeating);
+        sync_timer.set_elapsed(sync_timer.duration());
+        Ok(RollbackSession {
+            socket,
+            peer,
+            nonce: rand::random(),
+            seed,
+            mode,
+            local_player: None,
+            input_delay: None,
+            latency: Duration::ZERO,
+            desync_check: false,
+            outgoing: VecDeque::new(),
+            local_run_settings: None,
+            run: 0,
+            timeline: RollbackTimeline::default(),
+            checked_frames: 0,
+            desyncs: 0,
+            silence: 0.0,
+            sync_timer,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.socket.local_addr().map_or(0, |address| address.port())
+    }
+
+    pub fn peer_player(&self) -> Option<PlayerId> {
+        self.local_player.map(|player_id| match player_id {
+            PlayerId::ONE => PlayerId(1),
+            _ => PlayerId::ONE,
+        })
+    }
+
+    pub fn sync_message(&self) -> Message {
+        Message::RollbackSync {
+            nonce: self.nonce,
+            seed: self.seed,
+            mode: self.mode,
+        }
+    }
+
+    pub fn receive(&self) -> Option<(SocketAddr, Result<Message, ProtocolError>)> {
+        receive(&self.socket)
+    }
+
+    /// Sends the message to the peer once the artificial latency passed.
+    pub fn send(&mut self, message: &Message, now: Duration) {
+        if self.latency.is_zero() {
+            send(&self.socket, self.peer, message);
+        } else {
+            self.outgoing
+                .push_back((now + self.latency, message.encode()));
+        }
+    }
+
+    /// Sends the message right away, regardless of the artificial latency.
+    pub fn send_now(&self, message: &Message) {
+        send(&self.socket, self.peer, message);
+    }
+
+    /// Sends the delayed datagrams whose latency passed.
+    pub fn flush(&mut self, now: Duration) {
+        while let Some((due, datagram)) = self.outgoing.front() {
+            if *due > now {
+                break;
+            }
+            if let Err(error) = self.socket.send_to(datagram, self.peer) {
+                warn!(target: "network", "Could not send to {}: {error}", self.peer);
+            }
+            self.outgoing.pop_front();
+        }
+    }
+}
+
+impl RollbackTimeline {
+    pub fn new(input_delay: u32) -> RollbackTimeline {
+        RollbackTimeline {
+            input_delay,
+            // Nobody has moved before the first delayed input arrives
+            local_inputs: (0..input_delay).map(|frame| (frame, Vec2::ZERO)).collect(),
+            ..default()
+        }
+    }
+
+    /// Input of the peer for the frame, repeating its last received input until the real one arrives.
+    pub fn peer_input(&mut self, frame: u32) -> Vec2 {
+        if let Some(direction) = self.peer_inputs.get(&frame) {
+            return *direction;
+        }
+        let direction = self
+            .peer_inputs
+            .range(..frame)
+            .next_back()
+            .map_or(Vec2::ZERO, |(_, direction)| *direction);
+        self.predicted_inputs.insert(frame, direction);
+        direction
+    }
+
+    /// Records inputs of the peer starting at the frame and notes wrong predictions.
+    pub fn receive_peer_inputs(&mut self, start: u32, directions: &[Vec2]) {
+        for (frame, direction) in (start..).zip(directions) {
+            if frame < self.received_frame || self.peer_inputs.contains_key(&frame) {
+                continue;
+            }
+            // Kept bit for bit, the movement clamps the direction the same way on both machines
+            let direction = *direction;
+            self.peer_inputs.insert(frame, direction);
+            if self
+                .predicted_inputs
+                .remove(&frame)
+                .is_some_and(|predicted| predicted != direction)
+            {
+                let rollback_frame = self.rollback_frame.get_or_insert(frame);
+                *rollback_frame = (*rollback_frame).min(frame);
+            }
+        }
+        while self.peer_inputs.contains_key(&self.received_frame) {
+            self.received_frame += 1;
+        }
+    }
+}
+
+fn receive(socket: &UdpSocket) -> Option<(SocketAddr, Result<Message, ProtocolError>)> {
+    let mut buffer = [0; MAX_DATAGRAM_SIZE];
+    loop {
+        match socket.recv_from(&mut buffer) {
+            Ok((length, address)) => return Some((address, Message::decode(&buffer[..length]))),
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => return None,
+            // Unreachable peers are reported on the next receive on some platforms
+            Err(error) => debug!(target: "network", "Could not receive: {error}"),
+        }
+    }
+}
+
+fn send(socket: &UdpSocket, address: SocketAddr, message: &Message) {
+    if let Err(error) = socket.send_to(&message.encode(), address) {
+        warn!(target: "network", "Could not send to {address}: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predicts_the_last_received_input() {
+        let mut timeline = RollbackTimeline::new(0);
+        assert_eq!(timeline.peer_input(0), Vec2::ZERO);
+
+        timeline.receive_peer_inputs(0, &[Vec2::X, Vec2::Y]);
+        assert_eq!(timeline.peer_input(1), Vec2::Y);
+        assert_eq!(timeline.peer_input(5), Vec2::Y);
+        assert_eq!(
+            timeline
+                .predicted_inputs
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            [5]
+        );
+    }
+
+    #[test]
+    fn advances_over_received_inputs_without_gaps() {
+        let mut timeline = RollbackTimeline::new(0);
+        timeline.receive_peer_inputs(0, &[Vec2::X, Vec2::X]);
+        assert_eq!(timeline.received_frame, 2);
+
+        // A late datagram leaves a gap until the missing frame arrives
+        timeline.receive_peer_inputs(3, &[Vec2::Y]);
+        assert_eq!(timeline.received_frame, 2);
+        timeline.receive_peer_inputs(2, &[Vec2::Y, Vec2::NEG_Y]);
+        assert_eq!(timeline.received_frame, 4);
+        // The first received input of a frame counts, resent ones are ignored
+        assert_eq!(timeline.peer_inputs[&3], Vec2::Y);
+    }
+
+    #[test]
+    fn rolls_back_to_the_earliest_wrong_prediction() {
+        let mut timeline = RollbackTimeline::new(0);
+        timeline.receive_peer_inputs(0, &[Vec2::X]);
+        for frame in 1..6 {
+            assert_eq!(timeline.peer_input(frame), Vec2::X);
+        }
+
+        // Frames 1 and 2 were predicted right, 3 was the first to be wrong
+        timeline.receive_peer_inputs(1, &[Vec2::X, Vec2::X, Vec2::Y, Vec2::X]);
+        assert_eq!(timeline.rollback_frame, Some(3));
+        assert_eq!(timeline.received_frame, 5);
+        assert!(!timeline.predicted_inputs.contains_key(&3));
+
+        // An earlier wrong prediction moves the rollback further back, a later one doesn't
+        let mut timeline = RollbackTimeline::new(0);
+        for frame in 0..6 {
+            timeline.peer_input(frame);
+        }
+        timeline.receive_peer_inputs(4, &[Vec2::Y]);
+        assert_eq!(timeline.rollback_frame, Some(4));
+        timeline.receive_peer_inputs(1, &[Vec2::ZERO, Vec2::NEG_X]);
+        assert_eq!(timeline.rollback_frame, Some(2));
+        timeline.receive_peer_inputs(5, &[Vec2::Y]);
+        assert_eq!(timeline.rollback_frame, Some(2));
+    }
+
+    #[test]
+    fn right_predictions_dont_roll_back() {
+        let mut timeline = RollbackTimeline::new(2);
+        for frame in 0..4 {
+            assert_eq!(timeline.peer_input(frame), Vec2::ZERO);
+        }
+        timeline.receive_peer_inputs(0, &[Vec2::ZERO; 4]);
+        assert_eq!(timeline.rollback_frame, None);
+        assert!(timeline.predicted_inputs.is_empty());
+    }
+
+    #[test]
+    fn ignores_inputs_of_confirmed_frames() {
+        let mut timeline = RollbackTimeline::new(0);
+        timeline.receive_peer_inputs(0, &[Vec2::X; 3]);
+        timeline.peer_inputs.clear();
+        timeline.receive_peer_inputs(0, &[Vec2::Y; 3]);
+        assert!(timeline.peer_inputs.is_empty());
+        assert_eq!(timeline.rollback_frame, None);
+    }
+}
@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+pub const ONLINE_HUD_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.position_type = PositionType::Absolute;
+    node.top = Val::Px(10.0);
+    node.left = Val::Px(10.0);
+    node.padding = UiRect::all(Val::Px(10.0));
+    node.flex_direction = FlexDirection::Column;
+    node.row_gap = Val::Px(5.0);
+    node
+};
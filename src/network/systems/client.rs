@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::player::resources::PlayerBindings,
+    network::{
+        protocol::{Message, ProtocolError},
+        resources::{ClientSession, HostSnapshot},
+        CONNECTION_TIMEOUT,
+    },
+    AppState,
+};
+
+pub fn join_host(mut client_session: ResMut<ClientSession>, time: Res<Time<Real>>) {
+    if client_session.player_id.is_some() {
+        return;
+    }
+    if client_session.join_timer.tick(time.delta()).just_finished() {
+        client_session.send(&Message::Join);
+    }
+}
+
+pub fn receive_host_messages(
+    mut commands: Commands,
+    mut client_session: ResMut<ClientSession>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    time: Res<Time<Real>>,
+) {
+    client_session.silence += time.delta_secs();
+
+    while let Some((address, message)) = client_session.receive() {
+        if address != client_session.host {
+            continue;
+        }
+        client_session.silence = 0.0;
+
+        match message {
+            Ok(Message::Welcome { player_id }) => {
+                if client_session.player_id.is_none() {
+                    info!(target: "network", "Joined {} as {player_id}", client_session.host);
+                }
+                client_session.player_id = Some(player_id);
+            }
+            Ok(Message::Rejected { reason }) => {
+                error!(target: "network", "The host rejected the connection: {reason:?}");
+                leave_session(&mut commands, &mut next_app_state);
+                return;
+            }
+            Ok(Message::Lobby { players }) => client_session.lobby_players = players,
+            Ok(Message::Start {
+                mode,
+                players,
+                arena,
+            }) => {
+                info!(target: "network", "The host started a {mode:?} run with {players} players");
+                client_session.run_players = players;
+                client_session.host_arena = arena;
+                client_session.round_over = None;
+                next_app_state.set(AppState::OnlineGame);
+            }
+            Ok(Message::Snapshot(snapshot)) => {
+                // Snapshots that arrive after a newer one are outdated
+                if client_session
+                    .last_tick
+                    .is_some_and(|last_tick| snapshot.tick <= last_tick)
+                {
+                    continue;
+                }
+                client_session.last_tick = Some(snapshot.tick);
+                commands.insert_resource(HostSnapshot { snapshot });
+            }
+            Ok(Message::RoundOver { score }) => client_session.round_over = Some(score),
+            Ok(Message::Disconnect) => {
+                info!(target: "network", "The host closed the session");
+                leave_session(&mut commands, &mut next_app_state);
+                return;
+            }
+            Ok(message) => debug!(target: "network", "Ignoring {message:?} from the host"),
+            Err(ProtocolError::UnsupportedVersion(version)) => {
+                error!(target: "network", "The host uses protocol version {version}");
+                leave_session(&mut commands, &mut next_app_state);
+                return;
+            }
+            Err(error) => warn!(target: "network", "Invalid datagram from the host: {error}"),
+        }
+    }
+
+    if client_session.silence > CONNECTION_TIMEOUT {
+        error!(target: "network", "Lost the connection to {}", client_session.host);
+        leave_session(&mut commands, &mut next_app_state);
+    }
+}
+
+fn leave_session(commands: &mut Commands, next_app_state: &mut NextState<AppState>) {
+    commands.remove_resource::<ClientSession>();
+    commands.remove_resource::<HostSnapshot>();
+    next_app_state.set(AppState::MainMenu);
+}
+
+pub fn send_client_input(
+    mut client_session: ResMut<ClientSession>,
+    gamepad_query: Query<&Gamepad>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    player_bindings: Res<PlayerBindings>,
+) {
+    // Inputs also keep the connection alive while waiting in the lobby
+    if client_session.player_id.is_none() {
+        return;
+    }
+    let direction = player_bindings.combined_direction(&keyboard_input, &gamepad_query);
+    client_session.sequence += 1;
+    let sequence = client_session.sequence;
+    client_session.send(&Message::Input {
+        sequence,
+        direction,
+    });
+}
+
+pub fn leave_online_game_on_key(
+    mut next_app_state: ResMut<NextState<AppState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyM) {
+        next_app_state.set(AppState::MainMenu);
+    }
+}
+
+pub fn close_client_session(mut commands: Commands, client_session: Res<ClientSession>) {
+    client_session.send(&Message::Leave);
+    commands.remove_resource::<ClientSession>();
+    commands.remove_resource::<HostSnapshot>();
+    info!(target: "network", "Left the session");
+}
@@ -0,0 +1,391 @@
+use std::net::SocketAddr;
+
+use bevy::prelude::*;
+
+use crate::{
+    arena::resources::Arena,
+    events::{GameOver, MenuButtonPressed},
+    game::components::Health,
+    game::{
+        enemy::components::Enemy,
+        player::{
+            components::{Player, PlayerId, PlayerInput, RemoteControlled},
+            MAX_LOCAL_PLAYERS, MAX_PLAYERS,
+        },
+        resources::RunSettings,
+        score::resources::Score,
+        star::components::Star,
+    },
+    network::{
+        protocol::{
+            EntityKind, EntityState, Message, PlayerState, ProtocolError, RejectReason,
+            WorldSnapshot,
+        },
+        resources::{HostSession, Peer},
+        CONNECTION_TIMEOUT, DEFAULT_PORT, MAX_DATAGRAM_SIZE,
+    },
+    widgets::components::ButtonAction,
+    AppState,
+};
+
+pub fn host_online_game(
+    mut commands: Commands,
+    mut menu_button_event_reader: EventReader<MenuButtonPressed>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    for event in menu_button_event_reader.read() {
+        if event.action != ButtonAction::HostOnlineGame {
+            continue;
+        }
+
+        match HostSession::bind(DEFAULT_PORT) {
+            Ok(host_session) => {
+                info!(target: "network", "Hosting on port {}", host_session.port());
+                commands.insert_resource(host_session);
+                next_app_state.set(AppState::Lobby);
+            }
+            Err(error) => error!(target: "network", "Could not host a game: {error}"),
+        }
+    }
+}
+
+pub fn receive_client_messages(
+    mut host_session: ResMut<HostSession>,
+    app_state: Res<State<AppState>>,
+    time: Res<Time<Real>>,
+) {
+    for peer in &mut host_session.peers {
+        peer.silence += time.delta_secs();
+    }
+
+    let mut lobby_changed = false;
+    while let Some((address, message)) = host_session.receive() {
+        if let Some(peer) = host_session.peer_mut(address) {
+            peer.silence = 0.0;
+        }
+
+        match message {
+            Ok(Message::Join) => {
+                lobby_changed |= accept_client(&mut host_session, address, *app_state.get());
+            }
+            Ok(Message::Input {
+                sequence,
+                direction,
+            }) => {
+                // Datagrams can arrive out of order, older inputs are outdated
+                if let Some(peer) = host_session.peer_mut(address) {
+                    if sequence > peer.last_sequence {
+                        peer.last_sequence = sequence;
+                        peer.direction = direction.clamp_length_max(1.0);
+                    }
+                }
+            }
+            Ok(Message::Leave) => {
+                lobby_changed |= remove_client(&mut host_session, address, "left");
+            }
+            Ok(message) => {
+                debug!(target: "network", "Ignoring {message:?} from {address}");
+            }
+            Err(ProtocolError::UnsupportedVersion(version)) => {
+                warn!(target: "network", "{address} uses protocol version {version}");
+                host_session.send(
+                    address,
+                    &Message::Rejected {
+                        reason: RejectReason::VersionMismatch,
+                    },
+                );
+            }
+            Err(error) => warn!(target: "network", "Invalid datagram from {address}: {error}"),
+        }
+    }
+
+    let timed_out: Vec<SocketAddr> = host_session
+        .peers
+        .iter()
+        .filter(|peer| peer.silence > CONNECTION_TIMEOUT)
+        .map(|peer| peer.address)
+        .collect();
+    for address in timed_out {
+        lobby_changed |= remove_client(&mut host_session, address, "timed out");
+    }
+
+    if lobby_changed {
+        host_session.broadcast(&Message::Lobby {
+            players: host_session.player_count() as u8,
+        });
+    }
+}
+
+/// Returns whether the client is new to the lobby.
+fn accept_client(host_session: &mut HostSession, address: SocketAddr, app_state: AppState) -> bool {
+    // Join requests are repeated until the client got an answer
+    if let Some(peer) = host_session.peer_mut(address) {
+        let player_id = peer.player_id;
+        host_session.send(address, &Message::Welcome { player_id });
+        return false;
+    }
+
+    let rejection = match (app_state, host_session.free_player_id()) {
+        (AppState::Lobby, Some(player_id)) => {
+            host_session.peers.push(Peer {
+                address,
+                player_id,
+                direction: Vec2::ZERO,
+                last_sequence: 0,
+                silence: 0.0,
+            });
+            host_session.send(address, &Message::Welcome { player_id });
+            info!(target: "network", "{address} joined as {player_id}");
+            return true;
+        }
+        (AppState::Lobby, None) => RejectReason::SessionFull,
+        _ => RejectReason::InProgress,
+    };
+    host_session.send(address, &Message::Rejected { reason: rejection });
+    false
+}
+
+fn remove_client(host_session: &mut HostSession, address: SocketAddr, reason: &str) -> bool {
+    let Some(index) = host_session
+        .peers
+        .iter()
+        .position(|peer| peer.address == address)
+    else {
+        return false;
+    };
+    let peer = host_session.peers.remove(index);
+    info!(target: "network", "{} ({address}) {reason}", peer.player_id);
+    true
+}
+
+type UnmarkedPlayer = (With<Player>, Without<RemoteControlled>);
+
+pub fn mark_remote_players(
+    mut commands: Commands,
+    player_query: Query<(Entity, &PlayerId), UnmarkedPlayer>,
+) {
+    // The host always plays the first player
+    for (player_entity, player_id) in &player_query {
+        if *player_id != PlayerId::ONE {
+            commands.entity(player_entity).insert(RemoteControlled);
+        }
+    }
+}
+
+pub fn apply_remote_inputs(
+    mut player_query: Query<(&mut PlayerInput, &PlayerId), With<RemoteControlled>>,
+    host_session: Res<HostSession>,
+) {
+    for (mut player_input, player_id) in &mut player_query {
+        // Players whose client left stop moving
+        player_input.direction = host_session
+            .peers
+            .iter()
+            .find(|peer| peer.player_id == *player_id)
+            .map_or(Vec2::ZERO, |peer| peer.direction);
+    }
+}
+
+pub fn announce_run_start(
+    host_session: Res<HostSession>,
+    run_settings: Res<RunSettings>,
+    arena: Res<Arena>,
+) {
+    host_session.broadcast(&Message::Start {
+        mode: run_settings.mode,
+        players: run_settings.player_count() as u8,
+        arena: Vec2::new(arena.width, arena.height),
+    });
+}
+
+pub fn announce_round_over(
+    mut game_over_event_reader: EventReader<GameOver>,
+    host_session: Res<HostSession>,
+) {
+    for event in game_over_event_reader.read() {
+        host_session.broadcast(&Message::RoundOver { score: event.score });
+    }
+}
+
+pub fn send_snapshots(
+    mut host_session: ResMut<HostSession>,
+    player_query: Query<(Entity, &Transform, &PlayerId, &Health), With<Player>>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    star_query: Query<(Entity, &Transform), With<Star>>,
+    score: Option<Res<Score>>,
+    time: Res<Time<Real>>,
+) {
+    if !host_session
+        .snapshot_timer
+        .tick(time.delta())
+        .just_finished()
+    {
+        return;
+    }
+    host_session.tick += 1;
+
+    let (total_score, player_scores) = score.map_or((0, [0; MAX_PLAYERS]), |score| {
+        (score.value, score.by_player)
+    });
+    let players = player_query
+        .iter()
+        .map(|(_, _, player_id, player_health)| PlayerState {
+            player_id: *player_id,
+            health: player_health.current,
+            stars: player_scores[player_id.0],
+        })
+        .collect();
+    let entities = player_query
+        .iter()
+        .map(|(entity, transform, player_id, _)| {
+            (entity, transform, EntityKind::Player(*player_id))
+        })
+        .chain(
+            enemy_query
+                .iter()
+                .map(|(entity, transform)| (entity, transform, EntityKind::Enemy)),
+        )
+        .chain(
+            star_query
+                .iter()
+                .map(|(entity, transform)| (entity, transform, EntityKind::Star)),
+        )
+        .map(|(entity, transform, kind)| EntityState {
+            id: entity.to_bits(),
+            kind,
+            position: transform.translation.truncate(),
+        })
+        .collect();
+
+    let mut snapshot = WorldSnapshot {
+        tick: host_session.tick,
+        score: total_score,
+        players,
+        entities,
+    };
+    // Stars come last, so they're the first to be left out of crowded arenas
+    let dropped = snapshot.fit_to(MAX_DATAGRAM_SIZE);
+    if dropped > 0 {
+        warn_once!(target: "network", "Left {dropped} entities out of a snapshot that was too large");
+    }
+    host_session.broadcast(&Message::Snapshot(snapshot));
+}
+
+pub fn close_host_session(
+    mut commands: Commands,
+    host_session: Res<HostSession>,
+    mut run_settings: ResMut<RunSettings>,
+) {
+    host_session.broadcast(&Message::Disconnect);
+    commands.remove_resource::<HostSession>();
+    // Remote players don't carry over into local runs
+    run_settings.players = run_settings.players.min(MAX_LOCAL_PLAYERS);
+    info!(target: "network", "Closed the hosted session");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{Ipv4Addr, SocketAddrV4},
+        thread,
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{game::mode::GameMode, network::resources::ClientSession};
+
+    /// Waits for the next datagram, loopback delivery is fast but not instant.
+    fn wait_for<T>(mut receive: impl FnMut() -> Option<T>) -> T {
+        for _ in 0..200 {
+            if let Some(received) = receive() {
+                return received;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("no datagram arrived over loopback");
+    }
+
+    #[test]
+    fn client_joins_and_receives_the_run_over_loopback() {
+        let mut host_session = HostSession::bind(0).unwrap();
+        let host_address = SocketAddrV4::new(Ipv4Addr::LOCALHOST, host_session.port());
+        let client_session = ClientSession::connect(host_address.into()).unwrap();
+
+        client_session.send(&Message::Join);
+        let (client_address, join) = wait_for(|| host_session.receive());
+        assert_eq!(join, Ok(Message::Join));
+        assert!(accept_client(
+            &mut host_session,
+            client_address,
+            AppState::Lobby
+        ));
+        assert_eq!(
+            wait_for(|| client_session.receive()).1,
+            Ok(Message::Welcome {
+                player_id: PlayerId(1)
+            })
+        );
+        // A repeated join request is answered with the same player
+        assert!(!accept_client(
+            &mut host_session,
+            client_address,
+            AppState::Lobby
+        ));
+        assert_eq!(
+            wait_for(|| client_session.receive()).1,
+            Ok(Message::Welcome {
+                player_id: PlayerId(1)
+            })
+        );
+
+        let start = Message::Start {
+            mode: GameMode::Endless,
+            players: host_session.player_count() as u8,
+            arena: Vec2::new(1280.0, 720.0),
+        };
+        host_session.broadcast(&start);
+        assert_eq!(wait_for(|| client_session.receive()).1, Ok(start));
+
+        // Even a crowded arena arrives in a single datagram
+        let mut snapshot = WorldSnapshot {
+            tick: 1,
+            score: 0,
+            players: Vec::new(),
+            entities: (0..2000)
+                .map(|id| EntityState {
+                    id,
+                    kind: EntityKind::Star,
+                    position: Vec2::ZERO,
+                })
+                .collect(),
+        };
+        assert!(snapshot.fit_to(MAX_DATAGRAM_SIZE) > 0);
+        host_session.broadcast(&Message::Snapshot(snapshot.clone()));
+        assert_eq!(
+            wait_for(|| client_session.receive()).1,
+            Ok(Message::Snapshot(snapshot))
+        );
+    }
+
+    #[test]
+    fn rejects_clients_outside_of_the_lobby() {
+        let mut host_session = HostSession::bind(0).unwrap();
+        let host_address = SocketAddrV4::new(Ipv4Addr::LOCALHOST, host_session.port());
+        let client_session = ClientSession::connect(host_address.into()).unwrap();
+
+        client_session.send(&Message::Join);
+        let (client_address, _) = wait_for(|| host_session.receive());
+        assert!(!accept_client(
+            &mut host_session,
+            client_address,
+            AppState::Game
+        ));
+        assert_eq!(
+            wait_for(|| client_session.receive()).1,
+            Ok(Message::Rejected {
+                reason: RejectReason::InProgress
+            })
+        );
+        assert!(host_session.peers.is_empty());
+    }
+}
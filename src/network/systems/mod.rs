@@ -0,0 +1,4 @@
+pub mod client;
+pub mod host;
+pub mod rollback;
+pub mod view;
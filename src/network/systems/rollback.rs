@@ -0,0 +1,514 @@
+use bevy::prelude::*;
+
+use crate::{
+    events::{AsteroidImpact, EndRun, EnemyBounced, GameOver, PlayerOut, StarCollected},
+    game::{
+        mode::GameMode,
+        player::{
+            components::{PlayerId, PlayerInput},
+            resources::PlayerBindings,
+        },
+        resources::RunSettings,
+        simulation::{resources::ExternalStepping, snapshot::SimulationSnapshot, step_simulation},
+    },
+    network::{
+        protocol::{Message, ProtocolError},
+        resources::{HeldEvent, RollbackSession, RollbackTimeline},
+        CHECKSUM_INTERVAL, CONNECTION_TIMEOUT, MAX_INPUTS_PER_DATAGRAM, MAX_PREDICTION_FRAMES,
+    },
+    settings::resources::Settings,
+};
+
+pub fn receive_peer_messages(
+    mut end_run_event_writer: EventWriter<EndRun>,
+    mut rollback_session: ResMut<RollbackSession>,
+    time: Res<Time<Real>>,
+) {
+    rollback_session.silence += time.delta_secs();
+
+    while let Some((address, message)) = rollback_session.receive() {
+        if address != rollback_session.peer {
+            continue;
+        }
+        rollback_session.silence = 0.0;
+
+        match message {
+            Ok(Message::RollbackSync { nonce, seed, mode }) => {
+                if rollback_session.local_player.is_none() {
+                    accept_peer(&mut rollback_session, nonce, seed, mode);
+                }
+                // Syncs are repeated until the peer got an answer
+                let sync_message = rollback_session.sync_message();
+                rollback_session.send(&sync_message, time.elapsed());
+            }
+            Ok(Message::RollbackInput {
+                run,
+                ack,
+                start,
+                directions,
+                checksum,
+            }) => {
+                if run != rollback_session.run {
+                    continue;
+                }
+                let desync_check = rollback_session.desync_check;
+                let timeline = &mut rollback_session.timeline;
+                timeline.peer_ack = timeline.peer_ack.max(ack);
+                timeline.receive_peer_inputs(start, &directions);
+                if let Some((frame, checksum)) = checksum.filter(|_| desync_check) {
+                    timeline.peer_checksums.insert(frame, checksum);
+                }
+            }
+            Ok(Message::Disconnect) => {
+                info!(target: "network", "The peer left the session");
+                end_run_event_writer.send(EndRun);
+                return;
+            }
+            Ok(message) => debug!(target: "network", "Ignoring {message:?} from the peer"),
+            Err(ProtocolError::UnsupportedVersion(version)) => {
+                error!(target: "network", "The peer uses protocol version {version}");
+                end_run_event_writer.send(EndRun);
+                return;
+            }
+            Err(error) => warn!(target: "network", "Invalid datagram from the peer: {error}"),
+        }
+    }
+
+    // Peers that haven't started yet are waited for
+    if rollback_session.local_player.is_some() && rollback_session.silence > CONNECTION_TIMEOUT {
+        error!(target: "network", "Lost the connection to {}", rollback_session.peer);
+        rollback_session.silence = 0.0;
+        // The session is closed when entering the main menu
+        end_run_event_writer.send(EndRun);
+    }
+}
+
+fn accept_peer(rollback_session: &mut RollbackSession, nonce: u64, seed: u64, mode: GameMode) {
+    if nonce == rollback_session.nonce {
+        warn!(target: "network", "The peer rolled the same nonce, waiting for its next sync");
+        return;
+    }
+    // The lower nonce plays the first player, the other peer plays its seed and mode
+    let local_player = match nonce > rollback_session.nonce {
+        true => PlayerId::ONE,
+        false => {
+            rollback_session.seed = seed;
+            rollback_session.mode = mode;
+            PlayerId(1)
+        }
+    };
+    rollback_session.local_player = Some(local_player);
+    info!(
+        target: "network",
+        "Synced with {} as {local_player}, playing {:?} with seed {}",
+        rollback_session.peer,
+        rollback_session.mode,
+        rollback_session.seed
+    );
+}
+
+pub fn sync_with_peer(mut rollback_session: ResMut<RollbackSession>, time: Res<Time<Real>>) {
+    if rollback_session.local_player.is_some() {
+        return;
+    }
+    if rollback_session
+        .sync_timer
+        .tick(time.delta())
+        .just_finished()
+    {
+        let sync_message = rollback_session.sync_message();
+        rollback_session.send(&sync_message, time.elapsed());
+    }
+}
+
+pub fn reset_rollback_timeline(
+    mut rollback_session: ResMut<RollbackSession>,
+    settings: Res<Settings>,
+) {
+    let input_delay = rollback_session.input_delay.unwrap_or(settings.input_delay);
+    rollback_session.run += 1;
+    rollback_session.timeline = RollbackTimeline::new(input_delay);
+}
+
+/// Simulates the next frame with the peer's input predicted, rolling back first if an earlier prediction was wrong.
+pub fn advance_rollback(world: &mut World) {
+    let local_direction = match world.run_system_cached(read_rollback_direction) {
+        Ok(direction) => direction,
+        Err(error) => {
+            error!(target: "network", "Could not read the local input: {error}");
+            Vec2::ZERO
+        }
+    };
+
+    world.resource_scope(|world, mut rollback_session: Mut<RollbackSession>| {
+        let rollback_session = &mut *rollback_session;
+        let (Some(local_player), Some(peer_player)) = (
+            rollback_session.local_player,
+            rollback_session.peer_player(),
+        ) else {
+            return;
+        };
+        let players = (local_player, peer_player);
+
+        if let Some(rollback_frame) = rollback_session.timeline.rollback_frame.take() {
+            roll_back(
+                world,
+                &mut rollback_session.timeline,
+                players,
+                rollback_frame,
+            );
+        }
+
+        let timeline = &mut rollback_session.timeline;
+        // Predicting too far ahead would make rollbacks long and visible
+        if timeline.frame < timeline.received_frame + MAX_PREDICTION_FRAMES {
+            let frame = timeline.frame;
+            timeline
+                .local_inputs
+                .insert(frame + timeline.input_delay, local_direction);
+            simulate_frame(world, timeline, players, frame);
+            timeline.frame += 1;
+        }
+
+        confirm_frames(world, rollback_session);
+    });
+}
+
+fn read_rollback_direction(
+    gamepad_query: Query<&Gamepad>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    player_bindings: Res<PlayerBindings>,
+) -> Vec2 {
+    player_bindings
+        .combined_direction(&keyboard_input, &gamepad_query)
+        .normalize_or_zero()
+}
+
+fn roll_back(
+    world: &mut World,
+    timeline: &mut RollbackTimeline,
+    players: (PlayerId, PlayerId),
+    rollback_frame: u32,
+) {
+    let Some(snapshot) = timeline.snapshots.get(&rollback_frame) else {
+        error!(target: "network", "No snapshot to roll back to frame {rollback_frame}");
+        return;
+    };
+    snapshot.restore(world);
+    // Resimulating emits the events of these frames again
+    timeline
+        .held_events
+        .retain(|(frame, _)| *frame < rollback_frame);
+
+    let frame = timeline.frame;
+    for resimulated_frame in rollback_frame..frame {
+        simulate_frame(world, timeline, players, resimulated_frame);
+    }
+    debug!(
+        target: "network",
+        "Rolled back {} frames to frame {rollback_frame}",
+        frame - rollback_frame
+    );
+}
+
+fn simulate_frame(
+    world: &mut World,
+    timeline: &mut RollbackTimeline,
+    (local_player, peer_player): (PlayerId, PlayerId),
+    frame: u32,
+) {
+    timeline
+        .snapshots
+        .insert(frame, SimulationSnapshot::capture(world));
+
+    let local_direction = timeline
+        .local_inputs
+        .get(&frame)
+        .copied()
+        .unwrap_or_default();
+    let peer_direction = timeline.peer_input(frame);
+    let mut player_query = world.query::<(&PlayerId, &mut PlayerInput)>();
+    for (player_id, mut player_input) in player_query.iter_mut(world) {
+        if *player_id == local_player {
+            player_input.direction = local_direction;
+        } else if *player_id == peer_player {
+            player_input.direction = peer_direction;
+        }
+    }
+
+    if frame < timeline.received_frame {
+        step_simulation(world);
+        return;
+    }
+
+    // Predicted frames collect their run events and effects separately, so that readers only see
+    // confirmed ones and resimulating doesn't play sounds and particles twice
+    let game_over_events = swap_events::<GameOver>(world);
+    let player_out_events = swap_events::<PlayerOut>(world);
+    let star_collected_events = swap_events::<StarCollected>(world);
+    let asteroid_impact_events = swap_events::<AsteroidImpact>(world);
+    let enemy_bounced_events = swap_events::<EnemyBounced>(world);
+    step_simulation(world);
+    let held_events: Vec<HeldEvent> = restore_events(world, game_over_events)
+        .into_iter()
+        .map(HeldEvent::GameOver)
+        .chain(
+            restore_events(world, player_out_events)
+                .into_iter()
+                .map(HeldEvent::PlayerOut),
+        )
+        .chain(
+            restore_events(world, star_collected_events)
+                .into_iter()
+                .map(HeldEvent::StarCollected),
+        )
+        .chain(
+            restore_events(world, asteroid_impact_events)
+                .into_iter()
+                .map(HeldEvent::AsteroidImpact),
+        )
+        .chain(
+            restore_events(world, enemy_bounced_events)
+                .into_iter()
+                .map(HeldEvent::EnemyBounced),
+        )
+        .collect();
+    timeline
+        .held_events
+        .extend(held_events.into_iter().map(|event| (frame, event)));
+}
+
+fn swap_events<E: Event>(world: &mut World) -> Events<E> {
+    let events = world.remove_resource::<Events<E>>().unwrap_or_default();
+    world.init_resource::<Events<E>>();
+    events
+}
+
+/// Puts the original events back and returns the ones sent in between.
+fn restore_events<E: Event>(world: &mut World, events: Events<E>) -> Vec<E> {
+    let sent_events = world
+        .remove_resource::<Events<E>>()
+        .map_or(Vec::new(), |mut sent_events| sent_events.drain().collect());
+    world.insert_resource(events);
+    sent_events
+}
+
+/// Releases the events of frames that can't be rolled back anymore and compares checksums.
+fn confirm_frames(world: &mut World, rollback_session: &mut RollbackSession) {
+    let timeline = &mut rollback_session.timeline;
+    let confirmed_frame = timeline.received_frame.min(timeline.frame);
+
+    let (released_events, held_events) = std::mem::take(&mut timeline.held_events)
+        .into_iter()
+        .partition(|(frame, _)| *frame < confirmed_frame);
+    timeline.held_events = held_events;
+    for (_, held_event) in released_events {
+        match held_event {
+            HeldEvent::GameOver(game_over) => {
+                world.send_event(game_over);
+            }
+            HeldEvent::PlayerOut(player_out) => {
+                world.send_event(player_out);
+            }
+            HeldEvent::StarCollected(star_collected) => {
+                world.send_event(star_collected);
+            }
+            HeldEvent::AsteroidImpact(asteroid_impact) => {
+                world.send_event(asteroid_impact);
+            }
+            HeldEvent::EnemyBounced(enemy_bounced) => {
+                world.send_event(enemy_bounced);
+            }
+        }
+    }
+
+    if rollback_session.desync_check {
+        let checked_frame = timeline.checked_frame;
+        for (frame, snapshot) in timeline.snapshots.range(..=confirmed_frame) {
+            if frame % CHECKSUM_INTERVAL == 0
+                && checked_frame.is_none_or(|checked_frame| *frame > checked_frame)
+            {
+                timeline
+                    .checksums
+                    .entry(*frame)
+                    .or_insert_with(|| snapshot.checksum());
+            }
+        }
+        for (frame, peer_checksum) in &timeline.peer_checksums {
+            let Some(checksum) = timeline.checksums.get(frame) else {
+                continue;
+            };
+            rollback_session.checked_frames += 1;
+            if checksum != peer_checksum {
+                rollback_session.desyncs += 1;
+                error!(
+                    target: "network",
+                    "Desync at frame {frame} of run {}: {checksum:x} here, {peer_checksum:x} on the peer",
+                    rollback_session.run
+                );
+            }
+            timeline.checked_frame = Some(*frame);
+        }
+        if let Some(checked_frame) = timeline.checked_frame {
+            // Older checksums won't be compared anymore
+            timeline.checksums.retain(|frame, _| *frame > checked_frame);
+            timeline
+                .peer_checksums
+                .retain(|frame, _| *frame > checked_frame);
+        }
+    }
+
+    // Only frames after the last confirmed one can be rolled back
+    timeline
+        .snapshots
+        .retain(|frame, _| *frame >= confirmed_frame);
+    timeline
+        .predicted_inputs
+        .retain(|frame, _| *frame >= confirmed_frame);
+    // Peer inputs are simulated until confirmed, the last received one predicts the next ones
+    let kept_peer_frame = confirmed_frame.min(timeline.received_frame.saturating_sub(1));
+    timeline
+        .peer_inputs
+        .retain(|frame, _| *frame >= kept_peer_frame);
+    // Local inputs are resent until acknowledged and resimulated until confirmed
+    let kept_local_frame = timeline.peer_ack.min(confirmed_frame);
+    timeline
+        .local_inputs
+        .retain(|frame, _| *frame >= kept_local_frame);
+}
+
+/// Sends the local inputs the peer hasn't acknowledged yet, along with the latest checksum.
+pub fn send_local_inputs(mut rollback_session: ResMut<RollbackSession>, time: Res<Time<Real>>) {
+    let timeline = &rollback_session.timeline;
+    let message = Message::RollbackInput {
+        run: rollback_session.run,
+        ack: timeline.received_frame,
+        start: timeline.peer_ack,
+        directions: timeline
+            .local_inputs
+            .range(timeline.peer_ack..)
+            .take(MAX_INPUTS_PER_DATAGRAM)
+            .map(|(_, direction)| *direction)
+            .collect(),
+        checksum: timeline
+            .checksums
+            .last_key_value()
+            .map(|(frame, checksum)| (*frame, *checksum)),
+    };
+    rollback_session.send(&message, time.elapsed());
+}
+
+pub fn flush_delayed_datagrams(
+    mut rollback_session: ResMut<RollbackSession>,
+    time: Res<Time<Real>>,
+) {
+    rollback_session.flush(time.elapsed());
+}
+
+pub fn close_rollback_session(
+    mut commands: Commands,
+    rollback_session: Res<RollbackSession>,
+    mut run_settings: ResMut<RunSettings>,
+) {
+    rollback_session.send_now(&Message::Disconnect);
+    if let Some(local_run_settings) = &rollback_session.local_run_settings {
+        *run_settings = local_run_settings.clone();
+    }
+    if rollback_session.desync_check {
+        info!(
+            target: "network",
+            "Compared {} checksums, {} desyncs",
+            rollback_session.checked_frames,
+            rollback_session.desyncs
+        );
+    }
+    commands.remove_resource::<RollbackSession>();
+    commands.remove_resource::<ExternalStepping>();
+    info!(target: "network", "Closed the rollback session");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+        thread,
+        time::Duration,
+    };
+
+    use clap::Parser;
+
+    use super::*;
+    use crate::{cli::LaunchOptions, game_app, AppState};
+
+    /// Keys each peer cycles through, so that the predicted inputs are often wrong.
+    const DIRECTION_KEYS: [KeyCode; 4] =
+        [KeyCode::KeyD, KeyCode::KeyW, KeyCode::KeyA, KeyCode::KeyS];
+    /// Frames simulated by both peers once the run started.
+    const SIMULATED_FRAMES: u32 = 600;
+
+    fn localhost(port: u16) -> SocketAddr {
+        SocketAddrV4::new(Ipv4Addr::LOCALHOST, port).into()
+    }
+
+    fn peer_app(rollback_session: RollbackSession) -> App {
+        let launch_options =
+            LaunchOptions::parse_from(["bevy_ball_game", "--headless", "--mute", "--mode", "zen"]);
+        let mut app = game_app(&launch_options);
+        launch_options.apply(&mut app).unwrap();
+        app.insert_resource(rollback_session)
+            .insert_resource(ExternalStepping);
+        app.finish();
+        app.cleanup();
+        app
+    }
+
+    fn in_game(app: &App) -> bool {
+        app.world()
+            .get_resource::<State<AppState>>()
+            .is_some_and(|app_state| *app_state.get() == AppState::Game)
+    }
+
+    #[test]
+    fn peers_with_latency_stay_in_sync_over_loopback() {
+        let mut first = RollbackSession::bind(0, localhost(0), 1, GameMode::Zen).unwrap();
+        let mut second =
+            RollbackSession::bind(0, localhost(first.port()), 2, GameMode::Zen).unwrap();
+        first.peer = localhost(second.port());
+        first.latency = Duration::from_millis(120);
+        second.latency = Duration::from_millis(30);
+        for rollback_session in [&mut first, &mut second] {
+            rollback_session.input_delay = Some(0);
+            rollback_session.desync_check = true;
+        }
+        let mut apps = [peer_app(first), peer_app(second)];
+
+        // Loading the assets and syncing with the peer take a while in real time
+        for _ in 0..5_000 {
+            if apps.iter().all(in_game) {
+                break;
+            }
+            for app in &mut apps {
+                app.update();
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(apps.iter().all(in_game), "the rollback run didn't start");
+
+        for update in 0..SIMULATED_FRAMES as usize {
+            for (index, app) in apps.iter_mut().enumerate() {
+                let mut keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+                keyboard_input.release_all();
+                let key_index = update / (13 + 7 * index) + index;
+                keyboard_input.press(DIRECTION_KEYS[key_index % DIRECTION_KEYS.len()]);
+                app.update();
+            }
+        }
+
+        for app in &apps {
+            let rollback_session = app.world().resource::<RollbackSession>();
+            assert!(rollback_session.timeline.frame > SIMULATED_FRAMES / 2);
+            assert!(rollback_session.checked_frames > 0);
+            assert_eq!(rollback_session.desyncs, 0);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    arena::resources::Arena,
+    game::player::{components::PlayerId, PLAYER_TINTS},
+    loading::resources::GameAssets,
+    localization::components::LocalizedText,
+    network::{
+        components::*,
+        protocol::EntityKind,
+        resources::{ClientSession, HostSnapshot},
+        styles::ONLINE_HUD_NODE,
+        SNAPSHOT_INTERVAL,
+    },
+    settings::resources::Settings,
+    widgets::{
+        resources::{UiFont, UiTheme},
+        text_bundle,
+    },
+};
+
+pub fn spawn_online_hud(
+    mut commands: Commands,
+    client_session: Res<ClientSession>,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+) {
+    commands
+        .spawn((
+            ONLINE_HUD_NODE,
+            BorderRadius::all(Val::Px(10.0)),
+            BackgroundColor(ui_theme.panel_color),
+            OnlineHUD,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                text_bundle("", &ui_font, ui_theme.info_font_size, ui_theme.text_color),
+                LocalizedText::new("online.hud.score").with_arg("score", 0),
+                OnlineScoreInfo,
+            ));
+            let run_players = usize::from(client_session.run_players);
+            for (index, tint) in PLAYER_TINTS.into_iter().enumerate().take(run_players) {
+                let player_id = PlayerId(index);
+                parent.spawn((
+                    text_bundle("", &ui_font, ui_theme.small_font_size, tint),
+                    player_info_text(player_id, 0, 0, client_session.player_id),
+                    OnlinePlayerInfo(player_id),
+                ));
+            }
+            parent.spawn((
+                text_bundle("", &ui_font, ui_theme.info_font_size, ui_theme.text_color),
+                LocalizedText::new("online.hud.round_over"),
+                Visibility::Hidden,
+                RoundOverInfo,
+            ));
+        });
+}
+
+pub fn despawn_online_game(
+    mut commands: Commands,
+    hud_query: Query<Entity, With<OnlineHUD>>,
+    network_entity_query: Query<Entity, With<NetworkEntity>>,
+) {
+    for entity in hud_query.iter().chain(&network_entity_query) {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Spawns, moves and despawns the entities of the host's newest snapshot.
+pub fn apply_host_snapshot(
+    mut commands: Commands,
+    mut network_entity_query: Query<(Entity, &NetworkEntity, &mut Interpolation, &Transform)>,
+    host_snapshot: Res<HostSnapshot>,
+    client_session: Res<ClientSession>,
+    arena: Res<Arena>,
+    game_assets: Res<GameAssets>,
+    settings: Res<Settings>,
+) {
+    // The host's arena can be of a different size than the local one
+    let scale = Vec2::new(arena.width, arena.height) / client_session.host_arena.max(Vec2::ONE);
+    let mut snapshot_entities: HashMap<u64, (EntityKind, Vec2)> = host_snapshot
+        .snapshot
+        .entities
+        .iter()
+        .map(|entity| (entity.id, (entity.kind, entity.position * scale)))
+        .collect();
+
+    for (entity, network_entity, mut interpolation, transform) in &mut network_entity_query {
+        match snapshot_entities.remove(&network_entity.id) {
+            Some((_, position)) => {
+                interpolation.from = transform.translation.truncate();
+                interpolation.to = position;
+                interpolation.elapsed = 0.0;
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+
+    let palette = settings.color_palette;
+    for (id, (kind, position)) in snapshot_entities {
+        let sprite = match kind {
+            EntityKind::Player(player_id) => Sprite {
+                color: PLAYER_TINTS[player_id.0],
+                ..Sprite::from_image(game_assets.player_sprite.clone())
+            },
+            EntityKind::Enemy => Sprite {
+                color: palette.enemy_color(),
+                ..Sprite::from_image(game_assets.enemy_sprite.clone())
+            },
+            EntityKind::Star => Sprite {
+                color: palette.pickup_color(),
+                ..Sprite::from_image(game_assets.star_sprite.clone())
+            },
+        };
+        commands.spawn((
+            sprite,
+            Transform::from_translation(position.extend(0.0)),
+            NetworkEntity { id },
+            Interpolation {
+                from: position,
+                to: position,
+                elapsed: 0.0,
+            },
+        ));
+    }
+}
+
+pub fn interpolate_network_entities(
+    mut network_entity_query: Query<(&mut Transform, &mut Interpolation)>,
+    time: Res<Time<Real>>,
+) {
+    for (mut transform, mut interpolation) in &mut network_entity_query {
+        interpolation.elapsed += time.delta_secs();
+        let progress = (interpolation.elapsed / SNAPSHOT_INTERVAL).min(1.0);
+        let position = interpolation.from.lerp(interpolation.to, progress);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+pub fn update_online_hud(
+    mut score_info_query: Query<&mut LocalizedText, With<OnlineScoreInfo>>,
+    mut player_info_query: Query<(&mut LocalizedText, &OnlinePlayerInfo), Without<OnlineScoreInfo>>,
+    host_snapshot: Res<HostSnapshot>,
+    client_session: Res<ClientSession>,
+) {
+    let snapshot = &host_snapshot.snapshot;
+    for mut localized_text in &mut score_info_query {
+        localized_text
+            .set_if_neq(LocalizedText::new("online.hud.score").with_arg("score", snapshot.score));
+    }
+    for (mut localized_text, player_info) in &mut player_info_query {
+        // Players missing from the snapshot are out
+        let (health, stars) = snapshot
+            .players
+            .iter()
+            .find(|player| player.player_id == player_info.0)
+            .map_or((0, 0), |player| (player.health, player.stars));
+        localized_text.set_if_neq(player_info_text(
+            player_info.0,
+            health,
+            stars,
+            client_session.player_id,
+        ));
+    }
+}
+
+pub fn update_round_over_info(
+    mut round_over_info_query: Query<(&mut LocalizedText, &mut Visibility), With<RoundOverInfo>>,
+    client_session: Res<ClientSession>,
+) {
+    for (mut localized_text, mut visibility) in &mut round_over_info_query {
+        match client_session.round_over {
+            Some(score) => {
+                localized_text.set_if_neq(
+                    LocalizedText::new("online.hud.round_over").with_arg("score", score),
+                );
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            None => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+}
+
+fn player_info_text(
+    player_id: PlayerId,
+    health: u32,
+    stars: u32,
+    own_player_id: Option<PlayerId>,
+) -> LocalizedText {
+    let key = match own_player_id == Some(player_id) {
+        true => "online.hud.own_player",
+        false => "online.hud.player",
+    };
+    LocalizedText::new(key)
+        .with_arg("number", player_id.number())
+        .with_arg("health", health)
+        .with_arg("stars", stars)
+}
@@ -0,0 +1,55 @@
+mod resources;
+mod systems;
+
+use bevy::{input::InputSystem, prelude::*};
+
+use crate::AppState;
+
+pub use resources::*;
+use systems::*;
+
+/// Keys whose state is captured in a replay, in bit order.
+const REPLAY_KEYS: [KeyCode; 15] = [
+    KeyCode::KeyW,
+    KeyCode::KeyA,
+    KeyCode::KeyS,
+    KeyCode::KeyD,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowRight,
+    KeyCode::Space,
+    KeyCode::Escape,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyM,
+    KeyCode::KeyC,
+];
+
+/// Records the keyboard input and frame times of a session or plays them back.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        // Loading takes a different number of frames on every launch, so replays only begin after it
+        app.add_systems(
+            OnExit(AppState::Loading),
+            start_playback.run_if(resource_exists::<ReplayMode>),
+        )
+        .add_systems(
+            PreUpdate,
+            play_back_input
+                .after(InputSystem)
+                .run_if(resource_exists::<ReplayMode>)
+                .run_if(not(in_state(AppState::Loading))),
+        )
+        .add_systems(
+            Last,
+            (record_input, advance_playback, save_recording)
+                .chain()
+                .run_if(resource_exists::<ReplayMode>)
+                .run_if(not(in_state(AppState::Loading))),
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Keyboard input of a session together with the seed it was played with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn new(seed: u64) -> Replay {
+        Replay {
+            seed,
+            frames: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    /// Real time that passed in this frame, in seconds.
+    pub delta: f32,
+    /// Bitmask of the pressed replay keys.
+    pub keys: u16,
+}
+
+#[derive(Resource, Debug)]
+pub enum ReplayMode {
+    Recording { path: PathBuf, replay: Replay },
+    Playback { replay: Replay, frame: usize },
+}
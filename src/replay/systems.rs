@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+use super::{resources::*, REPLAY_KEYS};
+
+pub fn start_playback(
+    replay_mode: Res<ReplayMode>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+) {
+    if let ReplayMode::Playback { replay, .. } = replay_mode.as_ref() {
+        if let Some(first_frame) = replay.frames.first() {
+            *time_update_strategy =
+                TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(first_frame.delta));
+        }
+    }
+}
+
+pub fn play_back_input(
+    replay_mode: Res<ReplayMode>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+) {
+    let ReplayMode::Playback { replay, frame } = replay_mode.as_ref() else {
+        return;
+    };
+    let Some(replay_frame) = replay.frames.get(*frame) else {
+        return;
+    };
+
+    for (bit, key) in REPLAY_KEYS.iter().enumerate() {
+        let pressed = replay_frame.keys & (1 << bit) != 0;
+        if pressed && !keyboard_input.pressed(*key) {
+            keyboard_input.press(*key);
+        } else if !pressed && keyboard_input.pressed(*key) {
+            keyboard_input.release(*key);
+        }
+    }
+}
+
+pub fn record_input(
+    mut replay_mode: ResMut<ReplayMode>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+) {
+    let ReplayMode::Recording { replay, .. } = replay_mode.as_mut() else {
+        return;
+    };
+
+    let keys = REPLAY_KEYS
+        .iter()
+        .enumerate()
+        .filter(|(_, key)| keyboard_input.pressed(**key))
+        .fold(0, |keys, (bit, _)| keys | (1 << bit));
+    replay.frames.push(ReplayFrame {
+        delta: time.delta_secs(),
+        keys,
+    });
+}
+
+pub fn advance_playback(
+    mut replay_mode: ResMut<ReplayMode>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+) {
+    let ReplayMode::Playback { replay, frame } = replay_mode.as_mut() else {
+        return;
+    };
+
+    *frame += 1;
+    match replay.frames.get(*frame) {
+        // The frame time has to be set before the next frame starts
+        Some(next_frame) => {
+            *time_update_strategy =
+                TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(next_frame.delta));
+        }
+        None => {
+            info!(target: "replay", "Replay finished after {} frames", replay.frames.len());
+            app_exit_event_writer.send(AppExit::Success);
+        }
+    }
+}
+
+pub fn save_recording(
+    replay_mode: Res<ReplayMode>,
+    mut app_exit_event_reader: EventReader<AppExit>,
+) {
+    if app_exit_event_reader.read().next().is_none() {
+        return;
+    }
+    let ReplayMode::Recording { path, replay } = replay_mode.as_ref() else {
+        return;
+    };
+
+    match ron::to_string(replay) {
+        Ok(contents) => match std::fs::write(path, contents) {
+            Ok(()) => info!(target: "replay", "Saved replay to {}", path.display()),
+            Err(error) => {
+                error!(target: "replay", "Could not save replay to {}: {error}", path.display())
+            }
+        },
+        Err(error) => error!(target: "replay", "Could not serialize replay: {error}"),
+    }
+}
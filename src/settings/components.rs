@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct SettingsMenu;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingKind {
+    Language,
+    ColorPalette,
+    HighContrast,
+    ReducedMotion,
+    AutoPause,
+    ArenaScaling,
+    UiScale,
+    GameSpeed,
+    InputDelay,
+}
+
+/// Text that displays the current value of a setting.
+#[derive(Component)]
+pub struct SettingLabel {
+    pub kind: SettingKind,
+}
@@ -0,0 +1,51 @@
+mod components;
+pub mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+use resources::*;
+use systems::{
+    interactions::{
+        apply_settings, handle_settings_actions, return_to_main_menu, update_setting_labels,
+    },
+    layout::{despawn_settings_menu, spawn_settings_menu},
+};
+
+use crate::AppState;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .add_systems(OnEnter(AppState::Settings), spawn_settings_menu)
+            .add_systems(
+                Update,
+                (
+                    handle_settings_actions,
+                    update_setting_labels.after(handle_settings_actions),
+                    return_to_main_menu,
+                )
+                    .run_if(in_state(AppState::Settings)),
+            )
+            .add_systems(Update, apply_settings.run_if(resource_changed::<Settings>))
+            .add_systems(OnExit(AppState::Settings), despawn_settings_menu);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingChange {
+    NextLanguage,
+    NextColorPalette,
+    ToggleHighContrast,
+    ToggleReducedMotion,
+    ToggleAutoPause,
+    NextArenaScaling,
+    DecreaseUiScale,
+    IncreaseUiScale,
+    DecreaseGameSpeed,
+    IncreaseGameSpeed,
+    DecreaseInputDelay,
+    IncreaseInputDelay,
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{arena::resources::ArenaScaling, localization::Language};
+
+pub const UI_SCALE_STEP: f32 = 0.25;
+pub const UI_SCALE_RANGE: (f32, f32) = (0.5, 2.0);
+pub const GAME_SPEED_STEP: f32 = 0.1;
+pub const GAME_SPEED_RANGE: (f32, f32) = (0.5, 1.0);
+pub const MAX_INPUT_DELAY: u32 = 8;
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub language: Language,
+    pub color_palette: ColorPalette,
+    pub high_contrast: bool,
+    pub reduced_motion: bool,
+    /// Pause the game when the window loses focus or a gamepad disconnects.
+    pub auto_pause: bool,
+    pub arena_scaling: ArenaScaling,
+    pub ui_scale: f32,
+    pub game_speed: f32,
+    /// Simulation frames local inputs are held back in rollback sessions.
+    pub input_delay: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            language: Language::default(),
+            color_palette: ColorPalette::default(),
+            high_contrast: false,
+            reduced_motion: false,
+            auto_pause: true,
+            arena_scaling: ArenaScaling::default(),
+            ui_scale: 1.0,
+            game_speed: 1.0,
+            input_delay: 2,
+        }
+    }
+}
+
+/// Tints for enemies and pickups that stay distinguishable with common color vision deficiencies.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum ColorPalette {
+    #[default]
+    Default,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl ColorPalette {
+    const ALL: [ColorPalette; 4] = [
+        ColorPalette::Default,
+        ColorPalette::Deuteranopia,
+        ColorPalette::Protanopia,
+        ColorPalette::Tritanopia,
+    ];
+
+    pub fn next(self) -> ColorPalette {
+        let index = ColorPalette::ALL
+            .iter()
+            .position(|palette| *palette == self)
+            .unwrap_or_default();
+        ColorPalette::ALL[(index + 1) % ColorPalette::ALL.len()]
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            ColorPalette::Default => "settings.palette.default",
+            ColorPalette::Deuteranopia => "settings.palette.deuteranopia",
+            ColorPalette::Protanopia => "settings.palette.protanopia",
+            ColorPalette::Tritanopia => "settings.palette.tritanopia",
+        }
+    }
+
+    // Colors are taken from the Okabe-Ito palette.
+    pub fn enemy_color(self) -> Color {
+        match self {
+            ColorPalette::Default => Color::WHITE,
+            ColorPalette::Deuteranopia | ColorPalette::Protanopia => {
+                Color::srgb_u8(0, 114, 178) // blue
+            }
+            ColorPalette::Tritanopia => Color::srgb_u8(213, 94, 0), // vermillion
+        }
+    }
+
+    pub fn pickup_color(self) -> Color {
+        match self {
+            ColorPalette::Default => Color::WHITE,
+            ColorPalette::Deuteranopia | ColorPalette::Protanopia => {
+                Color::srgb_u8(240, 228, 66) // yellow
+            }
+            ColorPalette::Tritanopia => Color::srgb_u8(86, 180, 233), // sky blue
+        }
+    }
+
+    pub fn enemy_outline_color(self) -> Color {
+        match self {
+            ColorPalette::Default => Color::srgb(1.0, 0.2, 0.2),
+            _ => self.enemy_color(),
+        }
+    }
+
+    pub fn pickup_outline_color(self) -> Color {
+        match self {
+            ColorPalette::Default => Color::srgb(1.0, 1.0, 0.2),
+            _ => self.pickup_color(),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use super::layout::setting_text;
+use crate::{
+    events::MenuButtonPressed,
+    localization::components::LocalizedText,
+    settings::{
+        components::SettingLabel,
+        resources::{
+            Settings, GAME_SPEED_RANGE, GAME_SPEED_STEP, MAX_INPUT_DELAY, UI_SCALE_RANGE,
+            UI_SCALE_STEP,
+        },
+        SettingChange,
+    },
+    widgets::components::ButtonAction,
+    AppState,
+};
+
+pub fn handle_settings_actions(
+    mut menu_button_event_reader: EventReader<MenuButtonPressed>,
+    mut settings: ResMut<Settings>,
+) {
+    for event in menu_button_event_reader.read() {
+        let ButtonAction::ChangeSetting(setting_change) = event.action else {
+            continue;
+        };
+
+        match setting_change {
+            SettingChange::NextLanguage => {
+                settings.language = settings.language.next();
+            }
+            SettingChange::NextColorPalette => {
+                settings.color_palette = settings.color_palette.next();
+            }
+            SettingChange::ToggleHighContrast => {
+                settings.high_contrast = !settings.high_contrast;
+            }
+            SettingChange::ToggleReducedMotion => {
+                settings.reduced_motion = !settings.reduced_motion;
+            }
+            SettingChange::ToggleAutoPause => {
+                settings.auto_pause = !settings.auto_pause;
+            }
+            SettingChange::NextArenaScaling => {
+                settings.arena_scaling = settings.arena_scaling.next();
+            }
+            SettingChange::DecreaseUiScale => {
+                settings.ui_scale = (settings.ui_scale - UI_SCALE_STEP).max(UI_SCALE_RANGE.0);
+            }
+            SettingChange::IncreaseUiScale => {
+                settings.ui_scale = (settings.ui_scale + UI_SCALE_STEP).min(UI_SCALE_RANGE.1);
+            }
+            SettingChange::DecreaseGameSpeed => {
+                settings.game_speed =
+                    (settings.game_speed - GAME_SPEED_STEP).max(GAME_SPEED_RANGE.0);
+            }
+            SettingChange::IncreaseGameSpeed => {
+                settings.game_speed =
+                    (settings.game_speed + GAME_SPEED_STEP).min(GAME_SPEED_RANGE.1);
+            }
+            SettingChange::DecreaseInputDelay => {
+                settings.input_delay = settings.input_delay.saturating_sub(1);
+            }
+            SettingChange::IncreaseInputDelay => {
+                settings.input_delay = (settings.input_delay + 1).min(MAX_INPUT_DELAY);
+            }
+        }
+    }
+}
+
+pub fn update_setting_labels(
+    mut setting_label_query: Query<(&SettingLabel, &mut LocalizedText)>,
+    settings: Res<Settings>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (setting_label, mut localized_text) in &mut setting_label_query {
+        *localized_text = setting_text(setting_label.kind, &settings);
+    }
+}
+
+pub fn apply_settings(
+    settings: Res<Settings>,
+    mut ui_scale: ResMut<UiScale>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    ui_scale.0 = settings.ui_scale;
+    virtual_time.set_relative_speed(settings.game_speed);
+}
+
+pub fn return_to_main_menu(
+    mut next_app_state: ResMut<NextState<AppState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        next_app_state.set(AppState::MainMenu);
+    }
+}
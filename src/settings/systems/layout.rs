@@ -0,0 +1,260 @@
+use bevy::prelude::*;
+
+use crate::{
+    localization::components::LocalizedText,
+    settings::{
+        components::{SettingKind, SettingLabel, SettingsMenu},
+        resources::Settings,
+        SettingChange,
+    },
+    tween::{components::UiTween, despawn_with_tween, MENU_TRANSITION, MENU_TRANSITION_DURATION},
+    widgets::{
+        components::ButtonAction,
+        resources::{UiFont, UiTheme},
+        spawn_button, spawn_menu_button,
+        styles::{MENU_NODE, MENU_TITLE_NODE},
+        text_bundle,
+    },
+};
+
+const SETTING_BUTTON_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.width = Val::Px(420.0);
+    node.height = Val::Px(50.0);
+    node.align_items = AlignItems::Center;
+    node.justify_content = JustifyContent::Center;
+    node
+};
+
+const STEP_BUTTON_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.width = Val::Px(50.0);
+    node.height = Val::Px(50.0);
+    node.align_items = AlignItems::Center;
+    node.justify_content = JustifyContent::Center;
+    node
+};
+
+const SLIDER_NODE: Node = {
+    let mut node = Node::DEFAULT;
+    node.width = Val::Px(420.0);
+    node.height = Val::Px(50.0);
+    node.flex_direction = FlexDirection::Row;
+    node.align_items = AlignItems::Center;
+    node.justify_content = JustifyContent::SpaceBetween;
+    node
+};
+
+pub fn spawn_settings_menu(
+    mut commands: Commands,
+    ui_theme: Res<UiTheme>,
+    ui_font: Res<UiFont>,
+    settings: Res<Settings>,
+) {
+    let _settings_menu_entity: Entity =
+        build_settings_menu(&mut commands, &ui_theme, &ui_font, &settings);
+}
+
+pub fn despawn_settings_menu(
+    mut commands: Commands,
+    query: Query<Entity, With<SettingsMenu>>,
+    mut tween_query: Query<&mut UiTween>,
+) {
+    for settings_menu_entity in &query {
+        despawn_with_tween(&mut commands, &mut tween_query, settings_menu_entity);
+    }
+}
+
+pub fn setting_text(kind: SettingKind, settings: &Settings) -> LocalizedText {
+    match kind {
+        SettingKind::Language => LocalizedText::new("settings.language")
+            .with_arg("language", settings.language.native_name()),
+        SettingKind::ColorPalette => LocalizedText::new(settings.color_palette.key()),
+        SettingKind::HighContrast => LocalizedText::new(match settings.high_contrast {
+            true => "settings.high_contrast.on",
+            false => "settings.high_contrast.off",
+        }),
+        SettingKind::ReducedMotion => LocalizedText::new(match settings.reduced_motion {
+            true => "settings.reduced_motion.on",
+            false => "settings.reduced_motion.off",
+        }),
+        SettingKind::AutoPause => LocalizedText::new(match settings.auto_pause {
+            true => "settings.auto_pause.on",
+            false => "settings.auto_pause.off",
+        }),
+        SettingKind::ArenaScaling => LocalizedText::new(settings.arena_scaling.key()),
+        SettingKind::UiScale => LocalizedText::new("settings.ui_scale")
+            .with_arg("percent", (settings.ui_scale * 100.0).round()),
+        SettingKind::GameSpeed => LocalizedText::new("settings.game_speed")
+            .with_arg("percent", (settings.game_speed * 100.0).round()),
+        SettingKind::InputDelay => {
+            LocalizedText::new("settings.input_delay").with_arg("frames", settings.input_delay)
+        }
+    }
+}
+
+fn spawn_setting_button(
+    parent: &mut ChildBuilder,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    settings: &Settings,
+    kind: SettingKind,
+    setting_change: SettingChange,
+) {
+    spawn_button(
+        parent,
+        SETTING_BUTTON_NODE,
+        ui_theme,
+        ui_font,
+        (setting_text(kind, settings), SettingLabel { kind }),
+        ButtonAction::ChangeSetting(setting_change),
+    );
+}
+
+fn spawn_setting_slider(
+    parent: &mut ChildBuilder,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    settings: &Settings,
+    kind: SettingKind,
+    [decrease, increase]: [SettingChange; 2],
+) {
+    parent.spawn(SLIDER_NODE).with_children(|parent| {
+        spawn_button(
+            parent,
+            STEP_BUTTON_NODE,
+            ui_theme,
+            ui_font,
+            LocalizedText::new("settings.decrease"),
+            ButtonAction::ChangeSetting(decrease),
+        );
+        parent.spawn((
+            text_bundle("", ui_font, ui_theme.button_font_size, ui_theme.text_color),
+            setting_text(kind, settings),
+            SettingLabel { kind },
+        ));
+        spawn_button(
+            parent,
+            STEP_BUTTON_NODE,
+            ui_theme,
+            ui_font,
+            LocalizedText::new("settings.increase"),
+            ButtonAction::ChangeSetting(increase),
+        );
+    });
+}
+
+fn build_settings_menu(
+    commands: &mut Commands,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    settings: &Settings,
+) -> Entity {
+    commands
+        .spawn((
+            MENU_NODE,
+            SettingsMenu,
+            UiTween::new(MENU_TRANSITION, MENU_TRANSITION_DURATION),
+        ))
+        .with_children(|parent| {
+            // Settings menu title
+            parent.spawn(MENU_TITLE_NODE).with_child((
+                text_bundle("", ui_font, ui_theme.title_font_size, ui_theme.text_color),
+                LocalizedText::new("settings.title"),
+            ));
+            // Language selector
+            spawn_setting_button(
+                parent,
+                ui_theme,
+                ui_font,
+                settings,
+                SettingKind::Language,
+                SettingChange::NextLanguage,
+            );
+            // Accessibility settings
+            spawn_setting_button(
+                parent,
+                ui_theme,
+                ui_font,
+                settings,
+                SettingKind::ColorPalette,
+                SettingChange::NextColorPalette,
+            );
+            spawn_setting_button(
+                parent,
+                ui_theme,
+                ui_font,
+                settings,
+                SettingKind::HighContrast,
+                SettingChange::ToggleHighContrast,
+            );
+            spawn_setting_button(
+                parent,
+                ui_theme,
+                ui_font,
+                settings,
+                SettingKind::ReducedMotion,
+                SettingChange::ToggleReducedMotion,
+            );
+            // Gameplay settings
+            spawn_setting_button(
+                parent,
+                ui_theme,
+                ui_font,
+                settings,
+                SettingKind::AutoPause,
+                SettingChange::ToggleAutoPause,
+            );
+            spawn_setting_button(
+                parent,
+                ui_theme,
+                ui_font,
+                settings,
+                SettingKind::ArenaScaling,
+                SettingChange::NextArenaScaling,
+            );
+            spawn_setting_slider(
+                parent,
+                ui_theme,
+                ui_font,
+                settings,
+                SettingKind::UiScale,
+                [
+                    SettingChange::DecreaseUiScale,
+                    SettingChange::IncreaseUiScale,
+                ],
+            );
+            spawn_setting_slider(
+                parent,
+                ui_theme,
+                ui_font,
+                settings,
+                SettingKind::GameSpeed,
+                [
+                    SettingChange::DecreaseGameSpeed,
+                    SettingChange::IncreaseGameSpeed,
+                ],
+            );
+            // Network settings
+            spawn_setting_slider(
+                parent,
+                ui_theme,
+                ui_font,
+                settings,
+                SettingKind::InputDelay,
+                [
+                    SettingChange::DecreaseInputDelay,
+                    SettingChange::IncreaseInputDelay,
+                ],
+            );
+            // Back button
+            spawn_menu_button(
+                parent,
+                ui_theme,
+                ui_font,
+                LocalizedText::new("settings.back"),
+                ButtonAction::MainMenu,
+            );
+        })
+        .id()
+}
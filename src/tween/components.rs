@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub enum TweenEffect {
+    /// Fades the node and all of its descendants in from full transparency.
+    Fade,
+    /// Slides the node in from the given offset in pixels.
+    Slide { from: Vec2 },
+    /// Scales the node in from the given scale.
+    Scale { from: f32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UiTransition {
+    Entering,
+    Shown,
+    Leaving,
+}
+
+/// Animates a UI node in when spawned and out before it is despawned.
+#[derive(Component)]
+pub struct UiTween {
+    pub effects: &'static [TweenEffect],
+    pub duration: f32,
+    pub elapsed: f32,
+    pub transition: UiTransition,
+}
+
+impl UiTween {
+    pub fn new(effects: &'static [TweenEffect], duration: f32) -> UiTween {
+        UiTween {
+            effects,
+            duration,
+            elapsed: 0.0,
+            transition: UiTransition::Entering,
+        }
+    }
+
+    /// Starts animating the node out. It is despawned once the animation has finished.
+    pub fn leave(&mut self) {
+        if self.transition == UiTransition::Leaving {
+            return;
+        }
+        // Continue from the current progress if the node has not fully entered yet.
+        self.elapsed = match self.transition {
+            UiTransition::Entering => self.duration - self.elapsed,
+            _ => 0.0,
+        };
+        self.transition = UiTransition::Leaving;
+    }
+
+    /// Progress between 0.0 (hidden) and 1.0 (fully shown).
+    pub fn visibility(&self) -> f32 {
+        let progress = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        match self.transition {
+            UiTransition::Entering => ease_out_cubic(progress),
+            UiTransition::Shown => 1.0,
+            UiTransition::Leaving => 1.0 - ease_out_cubic(progress),
+        }
+    }
+}
+
+fn ease_out_cubic(progress: f32) -> f32 {
+    1.0 - (1.0 - progress).powi(3)
+}
+
+/// Alpha values of a node before any fade was applied to it.
+#[derive(Component)]
+pub struct BaseAlpha {
+    pub background: f32,
+    pub text: f32,
+    pub image: f32,
+}
+
+/// Text that counts towards its target value and pops whenever the target changes.
+#[derive(Component, Default)]
+pub struct AnimatedCounter {
+    pub displayed: f32,
+    pub target: u32,
+    pub pop: f32,
+}
+
+impl AnimatedCounter {
+    pub fn set_target(&mut self, target: u32) {
+        if self.target != target {
+            self.target = target;
+            self.pop = 1.0;
+        }
+    }
+}
@@ -0,0 +1,48 @@
+pub mod components;
+mod systems;
+
+use bevy::prelude::*;
+
+use systems::*;
+
+use components::{TweenEffect, UiTween};
+
+/// Duration of menu transitions in seconds.
+pub const MENU_TRANSITION_DURATION: f32 = 0.3;
+pub const MENU_TRANSITION: &[TweenEffect] = &[TweenEffect::Fade, TweenEffect::Scale { from: 0.8 }];
+pub const HUD_TRANSITION: &[TweenEffect] = &[
+    TweenEffect::Fade,
+    TweenEffect::Slide {
+        from: Vec2::new(0.0, -40.0),
+    },
+];
+
+/// Speed at which animated counters approach their target in units per second.
+const COUNTER_SPEED: f32 = 12.0;
+/// Additional scale of a counter right after its target changed.
+const COUNTER_POP_SCALE: f32 = 0.4;
+/// Decay of the counter pop per second.
+const COUNTER_POP_DECAY: f32 = 4.0;
+
+pub struct TweenPlugin;
+
+impl Plugin for TweenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (capture_base_alpha, animate_ui_tweens, animate_counters).chain(),
+        );
+    }
+}
+
+/// Starts the leave animation of a UI node, or despawns it right away if it is not tweened.
+pub fn despawn_with_tween(
+    commands: &mut Commands,
+    tween_query: &mut Query<&mut UiTween>,
+    entity: Entity,
+) {
+    match tween_query.get_mut(entity) {
+        Ok(mut tween) => tween.leave(),
+        Err(_) => commands.entity(entity).despawn_recursive(),
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use super::components::*;
+use super::{COUNTER_POP_DECAY, COUNTER_POP_SCALE, COUNTER_SPEED};
+use crate::settings::resources::Settings;
+
+type AlphaComponents<'a> = (
+    Option<&'a BackgroundColor>,
+    Option<&'a TextColor>,
+    Option<&'a ImageNode>,
+);
+
+type FadedComponents<'a> = (
+    &'a BaseAlpha,
+    Option<&'a mut BackgroundColor>,
+    Option<&'a mut TextColor>,
+    Option<&'a mut ImageNode>,
+);
+
+/// Remembers the alpha values of fading nodes so that fades can be reversed exactly.
+pub fn capture_base_alpha(
+    mut commands: Commands,
+    tween_query: Query<(Entity, &UiTween)>,
+    children_query: Query<&Children>,
+    alpha_query: Query<AlphaComponents, Without<BaseAlpha>>,
+) {
+    for (tween_entity, tween) in &tween_query {
+        if !tween
+            .effects
+            .iter()
+            .any(|effect| matches!(effect, TweenEffect::Fade))
+        {
+            continue;
+        }
+
+        for entity in
+            std::iter::once(tween_entity).chain(children_query.iter_descendants(tween_entity))
+        {
+            if let Ok((background_color, text_color, image_node)) = alpha_query.get(entity) {
+                commands.entity(entity).insert(BaseAlpha {
+                    background: background_color.map_or(1.0, |color| color.0.alpha()),
+                    text: text_color.map_or(1.0, |color| color.0.alpha()),
+                    image: image_node.map_or(1.0, |image| image.color.alpha()),
+                });
+            }
+        }
+    }
+}
+
+pub fn animate_ui_tweens(
+    mut commands: Commands,
+    mut tween_query: Query<(Entity, &mut UiTween, &mut Node, &mut Transform)>,
+    children_query: Query<&Children>,
+    mut alpha_query: Query<FadedComponents>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+) {
+    for (tween_entity, mut tween, mut node, mut transform) in &mut tween_query {
+        if settings.reduced_motion {
+            tween.elapsed = tween.duration;
+        } else if tween.transition != UiTransition::Shown {
+            tween.elapsed += time.delta_secs();
+        }
+        let visibility = tween.visibility();
+
+        for effect in tween.effects {
+            match *effect {
+                TweenEffect::Fade => {
+                    for entity in std::iter::once(tween_entity)
+                        .chain(children_query.iter_descendants(tween_entity))
+                    {
+                        let Ok((base_alpha, background_color, text_color, image_node)) =
+                            alpha_query.get_mut(entity)
+                        else {
+                            continue;
+                        };
+                        if let Some(mut background_color) = background_color {
+                            background_color
+                                .0
+                                .set_alpha(base_alpha.background * visibility);
+                        }
+                        if let Some(mut text_color) = text_color {
+                            text_color.0.set_alpha(base_alpha.text * visibility);
+                        }
+                        if let Some(mut image_node) = image_node {
+                            image_node.color.set_alpha(base_alpha.image * visibility);
+                        }
+                    }
+                }
+                TweenEffect::Slide { from } => {
+                    node.left = Val::Px(from.x * (1.0 - visibility));
+                    node.top = Val::Px(from.y * (1.0 - visibility));
+                }
+                TweenEffect::Scale { from } => {
+                    transform.scale = Vec3::splat(from.lerp(1.0, visibility));
+                }
+            }
+        }
+
+        if tween.elapsed < tween.duration {
+            continue;
+        }
+        match tween.transition {
+            UiTransition::Entering => tween.transition = UiTransition::Shown,
+            UiTransition::Shown => {}
+            UiTransition::Leaving => commands.entity(tween_entity).despawn_recursive(),
+        }
+    }
+}
+
+pub fn animate_counters(
+    mut counter_query: Query<(&mut AnimatedCounter, &mut Text, &mut Transform)>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+) {
+    for (mut counter, mut text, mut transform) in &mut counter_query {
+        if settings.reduced_motion {
+            counter.displayed = counter.target as f32;
+            counter.pop = 0.0;
+        }
+        let target = counter.target as f32;
+        let step =
+            (COUNTER_SPEED * time.delta_secs()).max((target - counter.displayed).abs() * 0.1);
+        let previous = counter.displayed.round();
+        counter.displayed = if counter.displayed < target {
+            (counter.displayed + step).min(target)
+        } else {
+            (counter.displayed - step).max(target)
+        };
+
+        if counter.displayed.round() != previous || text.is_empty() {
+            text.clear();
+            let _ = text.write_str(&format!("{:?}", counter.displayed.round() as u32));
+        }
+
+        counter.pop = (counter.pop - COUNTER_POP_DECAY * time.delta_secs()).max(0.0);
+        transform.scale = Vec3::splat(1.0 + COUNTER_POP_SCALE * counter.pop);
+    }
+}
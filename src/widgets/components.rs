@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use crate::settings::SettingChange;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ButtonAction {
+    Play,
+    ResumeSavedGame,
+    CycleGameMode,
+    CyclePlayers,
+    HostOnlineGame,
+    StartOnlineGame,
+    LeaveLobby,
+    Settings,
+    Quit,
+    Continue,
+    Restart,
+    FinishRun,
+    ConfirmRestart,
+    CancelRestart,
+    MainMenu,
+    ChangeSetting(SettingChange),
+}
+
+#[derive(Component)]
+pub struct MenuButton {
+    pub action: ButtonAction,
+}
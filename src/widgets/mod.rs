@@ -0,0 +1,88 @@
+pub mod components;
+pub mod resources;
+pub mod styles;
+mod systems;
+
+use bevy::prelude::*;
+
+use components::{ButtonAction, MenuButton};
+use resources::*;
+use styles::MENU_BUTTON_NODE;
+use systems::*;
+
+use crate::{events::MenuButtonPressed, localization::components::LocalizedText};
+
+pub const FONT: &str = "fonts/FiraSans-Bold.ttf";
+
+pub struct WidgetsPlugin;
+
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiTheme>()
+            .init_resource::<UiFont>()
+            .add_event::<MenuButtonPressed>()
+            .add_systems(
+                Update,
+                (
+                    interact_with_menu_buttons,
+                    handle_navigation_actions.after(interact_with_menu_buttons),
+                    apply_high_contrast_outlines,
+                ),
+            );
+    }
+}
+
+pub fn text_bundle(
+    value: impl Into<String>,
+    ui_font: &UiFont,
+    font_size: f32,
+    color: Color,
+) -> impl Bundle {
+    (
+        Text::new(value),
+        TextFont {
+            font: ui_font.handle.clone(),
+            font_size,
+            ..default()
+        },
+        TextColor(color),
+    )
+}
+
+pub fn spawn_menu_button<'a>(
+    parent: &'a mut ChildBuilder,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    label: LocalizedText,
+    action: ButtonAction,
+) -> EntityCommands<'a> {
+    spawn_button(parent, MENU_BUTTON_NODE, ui_theme, ui_font, label, action)
+}
+
+/// Spawns a themed button whose text child consists of the given label components.
+pub fn spawn_button<'a>(
+    parent: &'a mut ChildBuilder,
+    node: Node,
+    ui_theme: &UiTheme,
+    ui_font: &UiFont,
+    label: impl Bundle,
+    action: ButtonAction,
+) -> EntityCommands<'a> {
+    let mut button = parent.spawn((
+        node,
+        Button,
+        MenuButton { action },
+        BorderRadius::all(Val::Px(10.0)),
+        BackgroundColor(ui_theme.normal_button_color),
+        Outline::new(Val::Px(3.0), Val::ZERO, Color::NONE),
+    ));
+    button.with_child((
+        text_bundle("", ui_font, ui_theme.button_font_size, ui_theme.text_color),
+        TextLayout {
+            justify: JustifyText::Center,
+            ..default()
+        },
+        label,
+    ));
+    button
+}
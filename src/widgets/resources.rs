@@ -0,0 +1,56 @@
+use bevy::color::palettes::css::DARK_GRAY;
+use bevy::prelude::*;
+
+use crate::loading::resources::GameAssets;
+
+/// Colors and font sizes shared by all menus and HUDs.
+#[derive(Resource, Debug)]
+pub struct UiTheme {
+    pub normal_button_color: Color,
+    pub hovered_button_color: Color,
+    pub pressed_button_color: Color,
+    pub text_color: Color,
+    pub muted_text_color: Color,
+    pub panel_color: Color,
+    pub overlay_color: Color,
+    pub high_contrast_outline_color: Color,
+    pub title_font_size: f32,
+    pub heading_font_size: f32,
+    pub button_font_size: f32,
+    pub info_font_size: f32,
+    pub small_font_size: f32,
+}
+
+impl Default for UiTheme {
+    fn default() -> UiTheme {
+        UiTheme {
+            // dark gray with smaller alpha
+            normal_button_color: Color::srgba(0.25, 0.25, 0.25, 0.7),
+            hovered_button_color: DARK_GRAY.into(),
+            pressed_button_color: Color::BLACK,
+            text_color: Color::WHITE,
+            muted_text_color: Color::srgba(1.0, 1.0, 1.0, 0.5),
+            panel_color: Color::srgba(0.25, 0.25, 0.25, 0.7),
+            overlay_color: Color::srgba(0.0, 0.0, 0.0, 0.8),
+            high_contrast_outline_color: Color::WHITE,
+            title_font_size: 64.0,
+            heading_font_size: 44.0,
+            button_font_size: 32.0,
+            info_font_size: 32.0,
+            small_font_size: 22.0,
+        }
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct UiFont {
+    pub handle: Handle<Font>,
+}
+
+impl FromWorld for UiFont {
+    fn from_world(world: &mut World) -> UiFont {
+        UiFont {
+            handle: world.get_resource_or_init::<GameAssets>().font.clone(),
+        }
+    }
+}
@@ -1,16 +1,6 @@
-use bevy::color::palettes::css::DARK_GRAY;
 use bevy::prelude::*;
 
-pub const NORMAL_BUTTON_COLOR: Srgba = Srgba {
-    red: 0.25,
-    green: 0.25,
-    blue: 0.25,
-    alpha: 0.7,
-}; // dark gray with smaller alpha
-pub const HOVERED_BUTTON_COLOR: Srgba = DARK_GRAY;
-pub const PRESSED_BUTTON_COLOR: Color = Color::BLACK;
-
-pub const NORMAL_BUTTON_NODE: Node = {
+pub const MENU_BUTTON_NODE: Node = {
     let mut node = Node::DEFAULT;
     node.width = Val::Px(250.0);
     node.height = Val::Px(80.0);
@@ -19,7 +9,7 @@ pub const NORMAL_BUTTON_NODE: Node = {
     node
 };
 
-pub const TITLE_NODE: Node = {
+pub const MENU_TITLE_NODE: Node = {
     let mut node = Node::DEFAULT;
     node.width = Val::Px(700.0);
     node.height = Val::Px(150.0);
@@ -30,7 +20,7 @@ pub const TITLE_NODE: Node = {
     node
 };
 
-pub const PAUSE_MENU_NODE: Node = {
+pub const MENU_NODE: Node = {
     let mut node = Node::DEFAULT;
     node.width = Val::Percent(100.0);
     node.height = Val::Percent(100.0);
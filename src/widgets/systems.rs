@@ -0,0 +1,100 @@
+use bevy::{app::AppExit, prelude::*};
+
+use super::components::{ButtonAction, MenuButton};
+use super::resources::UiTheme;
+use crate::{
+    events::MenuButtonPressed,
+    game::SimulationState,
+    settings::resources::Settings,
+    tween::components::{UiTransition, UiTween},
+    AppState,
+};
+
+pub fn interact_with_menu_buttons(
+    mut button_query: Query<
+        (Entity, &Interaction, &MenuButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    parent_query: Query<&Parent>,
+    tween_query: Query<&UiTween>,
+    mut menu_button_event_writer: EventWriter<MenuButtonPressed>,
+    ui_theme: Res<UiTheme>,
+) {
+    for (button_entity, interaction, menu_button, mut background_color) in &mut button_query {
+        // Menus that are animating out already belong to the previous screen
+        let leaving = parent_query
+            .iter_ancestors(button_entity)
+            .filter_map(|ancestor| tween_query.get(ancestor).ok())
+            .any(|tween| tween.transition == UiTransition::Leaving);
+        if leaving {
+            continue;
+        }
+
+        match *interaction {
+            Interaction::Pressed => {
+                *background_color = ui_theme.pressed_button_color.into();
+                menu_button_event_writer.send(MenuButtonPressed {
+                    action: menu_button.action,
+                });
+            }
+            Interaction::Hovered => {
+                *background_color = ui_theme.hovered_button_color.into();
+            }
+            Interaction::None => {
+                *background_color = ui_theme.normal_button_color.into();
+            }
+        }
+    }
+}
+
+pub fn handle_navigation_actions(
+    mut menu_button_event_reader: EventReader<MenuButtonPressed>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_simulation_state: ResMut<NextState<SimulationState>>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+) {
+    for event in menu_button_event_reader.read() {
+        match event.action {
+            ButtonAction::Settings => {
+                next_app_state.set(AppState::Settings);
+            }
+            ButtonAction::Quit => {
+                app_exit_event_writer.send(AppExit::Success);
+            }
+            ButtonAction::Continue => {
+                next_simulation_state.set(SimulationState::Running);
+            }
+            // Run actions are handled by the run lifecycle
+            ButtonAction::Play
+            | ButtonAction::Restart
+            | ButtonAction::FinishRun
+            | ButtonAction::ConfirmRestart
+            | ButtonAction::CancelRestart
+            | ButtonAction::MainMenu
+            | ButtonAction::ResumeSavedGame
+            | ButtonAction::CycleGameMode
+            | ButtonAction::CyclePlayers
+            | ButtonAction::HostOnlineGame
+            | ButtonAction::StartOnlineGame
+            | ButtonAction::LeaveLobby
+            | ButtonAction::ChangeSetting(_) => {}
+        }
+    }
+}
+
+pub fn apply_high_contrast_outlines(
+    mut outline_query: Query<Mut<Outline>, With<MenuButton>>,
+    settings: Res<Settings>,
+    ui_theme: Res<UiTheme>,
+) {
+    let outline_color = match settings.high_contrast {
+        true => ui_theme.high_contrast_outline_color,
+        false => Color::NONE,
+    };
+
+    for mut outline in &mut outline_query {
+        if settings.is_changed() || outline.is_added() {
+            outline.color = outline_color;
+        }
+    }
+}